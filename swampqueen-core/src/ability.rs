@@ -0,0 +1,423 @@
+//! Class abilities and spells. Each [`Class`] has its own list of [`Ability`]s, loaded from the
+//! same data file as its [`ClassProgression`](crate::progression::ClassProgression).
+
+use std::{fmt::Display, str::FromStr};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::content::{ContentKind, load_json};
+use crate::{
+    character::{Class, Resource, Stat, StatValues},
+    combat::Combatant,
+    dice::{DiceError, RollManyRequest, RolledDiceResults, parse_roll_many_str},
+    status::{StatusEffect, StatusKind},
+};
+
+/// A dice roll that scales with a flat bonus and, optionally, one of the roller's stats. Written in
+/// data as e.g. `"2d6 + TheSight"` or `"1d4 - 1"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct ScaledRoll {
+    dice: RollManyRequest,
+    bonus: isize,
+    stat: Option<Stat>,
+}
+impl ScaledRoll {
+    /// Create a roll of the given dice, plus a flat `bonus` and optionally the value of `stat`.
+    #[must_use]
+    pub const fn new(dice: RollManyRequest, bonus: isize, stat: Option<Stat>) -> Self {
+        Self { dice, bonus, stat }
+    }
+
+    /// Roll the dice, returning the individual results alongside the final total once the bonus
+    /// and the roller's `stats` have been added. The total is never negative.
+    #[must_use]
+    pub fn roll(&self, stats: &StatValues) -> (RolledDiceResults, isize) {
+        let results = self.dice.roll();
+        let rolled = isize::try_from(results.total()).unwrap_or(isize::MAX);
+        let scaling = self.stat.map_or(0, |stat| stats.get(stat));
+        (results, (rolled + self.bonus + scaling).max(0))
+    }
+}
+impl FromStr for ScaledRoll {
+    type Err = DiceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut terms = compact.split_inclusive(['+', '-']).map(|term| {
+            term.strip_suffix(['+', '-'])
+                .map_or((term, None), |stripped| (stripped, term.chars().last()))
+        });
+
+        let (dice_str, mut sign) = terms.next().ok_or(DiceError::Empty)?;
+        let mut roll = Self {
+            dice: parse_roll_many_str(dice_str)?,
+            bonus: 0,
+            stat: None,
+        };
+
+        for (term, next_sign) in terms {
+            let negative = match sign {
+                Some('-') => true,
+                Some(_) => false,
+                None => return Err(DiceError::MissingOperator(String::from(term))),
+            };
+            if let Ok(value) = term.parse::<isize>() {
+                roll.bonus += if negative { -value } else { value };
+            } else {
+                let stat: Stat = term
+                    .parse()
+                    .map_err(|_| DiceError::UnknownStat(String::from(term)))?;
+                if negative || roll.stat.replace(stat).is_some() {
+                    return Err(DiceError::TooManyStats);
+                }
+            }
+            sign = next_sign;
+        }
+
+        if sign.is_some() {
+            return Err(DiceError::TrailingOperator(String::from(s)));
+        }
+        Ok(roll)
+    }
+}
+impl TryFrom<String> for ScaledRoll {
+    type Error = DiceError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+impl From<ScaledRoll> for String {
+    fn from(value: ScaledRoll) -> Self {
+        value.to_string()
+    }
+}
+impl Display for ScaledRoll {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dice)?;
+        match self.bonus {
+            0 => {}
+            bonus if bonus < 0 => write!(f, " - {}", -bonus)?,
+            bonus => write!(f, " + {bonus}")?,
+        }
+        if let Some(stat) = self.stat {
+            write!(f, " + {stat:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Who an [`Ability`] can be used on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TargetType {
+    /// Only the one using the ability.
+    Caster,
+    /// A single enemy, chosen by the caster.
+    SingleEnemy,
+    /// Every enemy at once.
+    AllEnemies,
+}
+
+/// What an [`Ability`] does to its targets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum AbilityEffect {
+    /// Deal damage to each target.
+    Damage {
+        /// The amount of damage dealt.
+        roll: ScaledRoll,
+    },
+    /// Restore health to each target.
+    Heal {
+        /// The amount of health restored.
+        roll: ScaledRoll,
+    },
+    /// Apply a status effect to each target.
+    ApplyStatus {
+        /// The kind of status effect applied.
+        kind: StatusKind,
+        /// How many turns the status effect lasts.
+        turns: usize,
+    },
+}
+
+impl AbilityEffect {
+    /// Roll this effect using the caster's `stats`, then apply it to every target.
+    pub(crate) fn apply(
+        self,
+        stats: &StatValues,
+        targets: &mut [&mut dyn Combatant],
+    ) -> AbilityOutcome {
+        let (rolls, amount) = match self {
+            Self::Damage { roll } | Self::Heal { roll } => {
+                let (results, total) = roll.roll(stats);
+                (Some(results), total)
+            }
+            Self::ApplyStatus { turns, .. } => (None, isize::try_from(turns).unwrap_or_default()),
+        };
+
+        for target in targets.iter_mut() {
+            match self {
+                Self::Damage { .. } => target.take_damage(amount),
+                Self::Heal { .. } => target.heal(amount),
+                Self::ApplyStatus { kind, turns } => {
+                    target.apply_status_effect(StatusEffect::new(kind, turns));
+                }
+            }
+        }
+
+        AbilityOutcome {
+            rolls,
+            amount,
+            targets_hit: targets.len(),
+        }
+    }
+}
+
+/// The price paid each time an [`Ability`] is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AbilityCost {
+    /// The resource spent.
+    pub resource: Resource,
+    /// How much of the resource is spent.
+    pub amount: isize,
+}
+
+/// A single ability or spell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ability {
+    id: String,
+    name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    cost: Option<AbilityCost>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    cooldown: usize,
+    target: TargetType,
+    effect: AbilityEffect,
+    #[cfg_attr(feature = "serde", serde(default = "default_min_level"))]
+    min_level: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    class: Option<Class>,
+}
+impl Ability {
+    /// The ID of this ability, unique within its class.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The name shown to the player.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The price paid each time this ability is used, if any.
+    #[must_use]
+    pub const fn cost(&self) -> Option<AbilityCost> {
+        self.cost
+    }
+
+    /// The number of turns that must pass before this ability can be used again.
+    #[must_use]
+    pub const fn cooldown(&self) -> usize {
+        self.cooldown
+    }
+
+    /// Who this ability can be used on.
+    #[must_use]
+    pub const fn target(&self) -> TargetType {
+        self.target
+    }
+
+    /// What this ability does to its targets.
+    #[must_use]
+    pub const fn effect(&self) -> AbilityEffect {
+        self.effect
+    }
+
+    /// The level a character must be to use this ability.
+    #[must_use]
+    pub const fn min_level(&self) -> usize {
+        self.min_level
+    }
+
+    /// The class a character must be to use this ability, if restricted to one.
+    #[must_use]
+    pub const fn class(&self) -> Option<Class> {
+        self.class
+    }
+}
+impl From<&Ability> for String {
+    fn from(value: &Ability) -> Self {
+        value.name.clone()
+    }
+}
+
+#[cfg(feature = "serde")]
+const fn default_min_level() -> usize {
+    1
+}
+
+/// Every [`Ability`] available to a single [`Class`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClassAbilities {
+    abilities: Vec<Ability>,
+}
+impl ClassAbilities {
+    /// Load the [`ClassAbilities`] for the given [`Class`] from the game's
+    /// [content](crate::content).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load(class: Class) -> std::io::Result<Self> {
+        let mut loaded: Self = load_json(ContentKind::Class, class.id())?;
+        for ability in &mut loaded.abilities {
+            ability.class = Some(class);
+        }
+        Ok(loaded)
+    }
+
+    /// Get the ability with the given ID, if it exists.
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&Ability> {
+        self.abilities.iter().find(|ability| ability.id == id)
+    }
+
+    /// Iterate over every ability of the class.
+    pub fn iter(&self) -> impl Iterator<Item = &Ability> {
+        self.abilities.iter()
+    }
+}
+
+/// The reasons an [`Ability`] cannot be used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AbilityError {
+    /// The ability belongs to a different class.
+    WrongClass(Class),
+    /// The caster's level is below the ability's minimum level.
+    LevelTooLow(usize),
+    /// The ability is cooling down for the given number of turns.
+    OnCooldown(usize),
+    /// The caster cannot afford the ability's cost.
+    CannotAfford(AbilityCost),
+    /// The ability needs a target, but none was given.
+    NoTarget,
+}
+impl Display for AbilityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongClass(class) => write!(f, "only a {class} can use this ability"),
+            Self::LevelTooLow(level) => write!(f, "requires level {level}"),
+            Self::OnCooldown(turns) => write!(f, "ready again in {turns} turn(s)"),
+            Self::CannotAfford(cost) => write!(f, "costs {} {}", cost.amount, cost.resource),
+            Self::NoTarget => write!(f, "needs a target"),
+        }
+    }
+}
+impl std::error::Error for AbilityError {}
+
+/// The result of using an [`Ability`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbilityOutcome {
+    /// The dice rolled for the ability's effect, if any.
+    pub rolls: Option<RolledDiceResults>,
+    /// The amount of damage or healing applied to each target, or the number of turns a status
+    /// effect was applied for.
+    pub amount: isize,
+    /// The number of targets affected.
+    pub targets_hit: usize,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scaled_rolls() {
+        let roll: ScaledRoll = "2d6 + TheSight".parse().unwrap();
+        assert_eq!(
+            roll,
+            ScaledRoll {
+                dice: RollManyRequest::new(2, 6),
+                bonus: 0,
+                stat: Some(Stat::TheSight),
+            }
+        );
+
+        let roll: ScaledRoll = "1d4-1+Bulk+3".parse().unwrap();
+        assert_eq!(
+            roll,
+            ScaledRoll {
+                dice: RollManyRequest::new(1, 4),
+                bonus: 2,
+                stat: Some(Stat::Bulk),
+            }
+        );
+        assert_eq!(roll.to_string(), "1d4 + 2 + Bulk");
+
+        let roll: ScaledRoll = "3d8".parse().unwrap();
+        assert_eq!(roll.to_string(), "3d8");
+    }
+
+    #[test]
+    fn parse_invalid_scaled_rolls() {
+        let assert_invalid = |in_str: &str| {
+            let _ = in_str.parse::<ScaledRoll>().unwrap_err();
+        };
+
+        assert_invalid("");
+        assert_invalid("Bulk + 1d6");
+        assert_invalid("1d6 +");
+        assert_invalid("1d6 + Strength");
+        assert_invalid("1d6 - Bulk");
+        assert_invalid("1d6 + Bulk + Cunning");
+    }
+
+    #[test]
+    fn scaled_roll_adds_stat() {
+        let roll: ScaledRoll = "2d1 + TheSight".parse().unwrap();
+        let mut stats = StatValues::default();
+        stats.add_to_stat(Stat::TheSight, 5);
+        let (results, total) = roll.roll(&stats);
+        assert_eq!(results.total(), 2);
+        assert_eq!(total, 7);
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn load_all_class_abilities() {
+        for class in Class::ALL {
+            let abilities = ClassAbilities::try_load(class).unwrap();
+            assert!(abilities.iter().count() >= 2);
+            assert!(
+                abilities
+                    .iter()
+                    .all(|ability| ability.class() == Some(class))
+            );
+        }
+
+        let soothsayer = ClassAbilities::try_load(Class::Soothsayer).unwrap();
+        let fireball = soothsayer.get("fireball").unwrap();
+        assert_eq!(fireball.target(), TargetType::AllEnemies);
+        assert_eq!(
+            fireball.effect(),
+            AbilityEffect::Damage {
+                roll: "2d6 + TheSight".parse().unwrap()
+            }
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Adventures: self-contained stories, each with its own starting
+//! [`Location`](crate::location::Location) and rules for creating characters.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::content::{ContentKind, list, load_json};
+use crate::creation::{CreationMode, CreationRules};
+
+/// A single adventure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Adventure {
+    id: String,
+    name: String,
+    start_location: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    creation: CreationMode,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rules: CreationRules,
+}
+impl Adventure {
+    /// Load an [`Adventure`] from the game's [content](crate::content).
+    ///
+    /// # Arguments
+    ///
+    /// * `adventure_id` - A [`std::str`] corresponding to the ID of the desired adventure. In a
+    ///   content directory, this is the name of the adventure's `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load(adventure_id: &str) -> std::io::Result<Self> {
+        load_json(ContentKind::Adventure, adventure_id)
+    }
+
+    /// List the IDs of every adventure, in alphabetical order.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the adventure directory cannot be read.
+    #[cfg(feature = "serde")]
+    pub fn try_list() -> std::io::Result<Vec<String>> {
+        list(ContentKind::Adventure)
+    }
+
+    /// The ID of this adventure.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The name shown to the player.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The ID of the [`Location`](crate::location::Location) the adventure begins in.
+    #[must_use]
+    pub fn start_location(&self) -> &str {
+        &self.start_location
+    }
+
+    /// How characters are created for this adventure.
+    #[must_use]
+    pub const fn creation(&self) -> &CreationMode {
+        &self.creation
+    }
+
+    /// Any extra restrictions on creating characters for this adventure.
+    #[must_use]
+    pub const fn rules(&self) -> &CreationRules {
+        &self.rules
+    }
+}
+
+#[cfg(all(test, feature = "fs-content"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_adventures() {
+        let ids = Adventure::try_list().unwrap();
+        assert_eq!(ids, ["pointbuy", "rolled", "testadventure"]);
+    }
+
+    #[test]
+    fn load_adventures() {
+        let test = Adventure::try_load("testadventure").unwrap();
+        assert_eq!(test.name(), "Test Adventure");
+        assert_eq!(test.start_location(), "testplace");
+        assert_eq!(*test.creation(), CreationMode::Standard);
+        assert!(test.rules().distinct_bonus_stats);
+
+        let point_buy = Adventure::try_load("pointbuy").unwrap();
+        assert_eq!(
+            *point_buy.creation(),
+            CreationMode::PointBuy {
+                budget: 14,
+                max_per_stat: 5
+            }
+        );
+
+        let rolled = Adventure::try_load("rolled").unwrap();
+        assert!(rolled.creation().roll_stats().is_ok());
+    }
+}
@@ -0,0 +1,151 @@
+//! Character backgrounds: where a character came from before the adventure. A [`Background`] is
+//! loaded from data and layered on top of a character's [`Class`](crate::character::Class) and
+//! [`Race`](crate::character::Race).
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::content::{ContentKind, list, load_json};
+use crate::{character::StatValues, skill::Skill};
+
+/// A character's background, along with everything it grants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Background {
+    id: String,
+    name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    description: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    stats: StatValues,
+    #[cfg_attr(feature = "serde", serde(default))]
+    items: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    proficiencies: Vec<Skill>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    dialogue_tags: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    traits: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    flaws: Vec<String>,
+}
+impl Background {
+    /// Load a [`Background`] from the game's [content](crate::content).
+    ///
+    /// # Arguments
+    ///
+    /// * `background_id` - A [`std::str`] corresponding to the ID of the desired background. In a
+    ///   content directory, this is the name of the background's `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load(background_id: &str) -> std::io::Result<Self> {
+        load_json(ContentKind::Background, background_id)
+    }
+
+    /// Load every [`Background`], in alphabetical order of ID.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the background directory cannot be read, or if any
+    /// background fails to load.
+    #[cfg(feature = "serde")]
+    pub fn try_load_all() -> std::io::Result<Vec<Self>> {
+        list(ContentKind::Background)?
+            .iter()
+            .map(|id| Self::try_load(id))
+            .collect()
+    }
+
+    /// The ID of this background.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The name shown to the player.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// A short description of the background.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// The adjustments made to a character's stats, on top of their class and race.
+    #[must_use]
+    pub const fn stats(&self) -> &StatValues {
+        &self.stats
+    }
+
+    /// The IDs of the items a character with this background starts with.
+    #[must_use]
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// The skills a character with this background is proficient in.
+    #[must_use]
+    pub fn proficiencies(&self) -> &[Skill] {
+        &self.proficiencies
+    }
+
+    /// Tags that unlock extra dialogue options for a character with this background.
+    #[must_use]
+    pub fn dialogue_tags(&self) -> &[String] {
+        &self.dialogue_tags
+    }
+
+    /// The background's beneficial traits.
+    #[must_use]
+    pub fn traits(&self) -> &[String] {
+        &self.traits
+    }
+
+    /// The background's flaws.
+    #[must_use]
+    pub fn flaws(&self) -> &[String] {
+        &self.flaws
+    }
+}
+impl From<&Background> for String {
+    fn from(value: &Background) -> Self {
+        value.name.clone()
+    }
+}
+
+#[cfg(all(test, feature = "fs-content"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::character::Stat;
+
+    #[test]
+    fn load_all_backgrounds() {
+        let backgrounds = Background::try_load_all().unwrap();
+        let ids: Vec<&str> = backgrounds.iter().map(Background::id).collect();
+        assert_eq!(ids, ["bog_smuggler", "exiled_hive_scribe", "mire_hermit"]);
+    }
+
+    #[test]
+    fn load_bog_smuggler() {
+        let smuggler = Background::try_load("bog_smuggler").unwrap();
+        assert_eq!(smuggler.name(), "Bog Smuggler");
+        assert_eq!(smuggler.stats().get(Stat::Slipperiness), 1);
+        assert_eq!(smuggler.stats().get(Stat::Backbone), -1);
+        assert_eq!(smuggler.stats().get(Stat::Bulk), 0);
+        assert!(smuggler.items().contains(&String::from("hooded_lantern")));
+        assert_eq!(
+            smuggler.proficiencies(),
+            [Skill::Lockpicking, Skill::Stealth]
+        );
+        assert_eq!(smuggler.dialogue_tags(), ["smuggler"]);
+    }
+}
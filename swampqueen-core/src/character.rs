@@ -2,18 +2,53 @@
 //! [`Character`], around which all other functionality revolves.
 
 use std::{
+    collections::HashMap,
     fmt::Display,
     ops::{Add, AddAssign},
+    str::FromStr,
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ability::{Ability, AbilityError, AbilityOutcome, TargetType},
+    adventure::Adventure,
+    background::Background,
+    check::CheckResult,
+    combat::Combatant,
+    creation::{CreationError, CreationMode, CreationRules, RolledStats},
+    item::Inventory,
+    progression::{ClassProgression, LevelReward, STARTING_LEVEL},
+    skill::{Proficiencies, Skill},
+    status::{StatusEffect, StatusEffects, StatusTick},
+};
+
+/// The health every [`Character`] has before their [`Stat::Backbone`] is taken into account.
+const BASE_HEALTH: isize = 10;
+
 // TODO better docs
 /// A character.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Character {
     name: String,
     class: Class,
     race: Race,
+    background: Option<Background>,
     stats: StatValues,
+    proficiencies: Proficiencies,
+    health: isize,
+    stamina: isize,
+    focus: isize,
+    luck: isize,
+    status_effects: StatusEffects,
+    level: usize,
+    experience: usize,
+    stat_points: usize,
+    unlocks: Vec<String>,
+    cooldowns: HashMap<String, usize>,
+    inventory: Inventory,
 }
 impl Character {
     // TODO better docs
@@ -30,12 +65,435 @@ impl Character {
         stats.add_to_stat(bonus_stat_1, 1);
         stats.add_to_stat(bonus_stat_2, 1);
 
-        Self {
-            name: String::from(chosen_name),
-            class: chosen_class,
-            race: chosen_race,
+        Self::with_stats(chosen_name, chosen_class, chosen_race, stats)
+    }
+
+    /// Create a new character whose stats were bought with [`CreationMode::PointBuy`]. The
+    /// purchased stats replace those of the chosen class, and the race's bonus is added on top.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`CreationError`] if `purchased` is not valid for the given `mode`.
+    pub fn from_point_buy(
+        chosen_name: &str,
+        chosen_class: Class,
+        chosen_race: Race,
+        mode: &CreationMode,
+        purchased: &StatValues,
+    ) -> Result<Self, CreationError> {
+        mode.validate_point_buy(purchased)?;
+
+        let mut stats = purchased.clone();
+        stats.add_race_bonus(chosen_race);
+        Ok(Self::with_stats(
+            chosen_name,
+            chosen_class,
+            chosen_race,
             stats,
+        ))
+    }
+
+    /// Create a new character whose stats were rolled with [`CreationMode::Rolled`]. The rolled
+    /// stats replace those of the chosen class, and the race's bonus is added on top.
+    #[must_use]
+    pub fn from_rolled(
+        chosen_name: &str,
+        chosen_class: Class,
+        chosen_race: Race,
+        rolled: &RolledStats,
+    ) -> Self {
+        let mut stats = rolled.stats().clone();
+        stats.add_race_bonus(chosen_race);
+        Self::with_stats(chosen_name, chosen_class, chosen_race, stats)
+    }
+
+    /// Create a new character with the given final stats, at full health and resources.
+    fn with_stats(name: &str, class: Class, race: Race, stats: StatValues) -> Self {
+        let mut character = Self {
+            name: String::from(name),
+            class,
+            race,
+            background: None,
+            stats,
+            proficiencies: Proficiencies::default(),
+            health: 0,
+            stamina: 0,
+            focus: 0,
+            luck: 0,
+            status_effects: StatusEffects::default(),
+            level: STARTING_LEVEL,
+            experience: 0,
+            stat_points: 0,
+            unlocks: Vec::new(),
+            cooldowns: HashMap::new(),
+            inventory: Inventory::default(),
+        };
+        for resource in Resource::ALL {
+            *character.resource_mut(resource) = character.max_resource(resource);
+        }
+        for &skill in class.proficiencies().iter().chain(race.proficiencies()) {
+            character.proficiencies.train(skill);
+        }
+        character
+    }
+
+    /// The character's name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The character's class.
+    #[must_use]
+    pub const fn class(&self) -> Class {
+        self.class
+    }
+
+    /// The character's race.
+    #[must_use]
+    pub const fn race(&self) -> Race {
+        self.race
+    }
+
+    /// The character's background, if they have one.
+    #[must_use]
+    pub const fn background(&self) -> Option<&Background> {
+        self.background.as_ref()
+    }
+
+    /// Give a newly created character a [`Background`], adding its stat adjustments on top of
+    /// their class and race, and its starting items to their inventory.
+    pub(crate) fn apply_background(&mut self, background: Background) {
+        self.stats.add_background_bonus(&background);
+        for resource in Resource::ALL {
+            *self.resource_mut(resource) = self.max_resource(resource);
+        }
+        for &skill in background.proficiencies() {
+            self.proficiencies.train(skill);
+        }
+        for item_id in background.items() {
+            self.inventory.add(item_id, 1);
+        }
+        self.background = Some(background);
+    }
+
+    /// The items the character is carrying.
+    #[must_use]
+    pub const fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    /// The items the character is carrying, mutably.
+    pub const fn inventory_mut(&mut self) -> &mut Inventory {
+        &mut self.inventory
+    }
+
+    /// The character's current level.
+    #[must_use]
+    pub const fn level(&self) -> usize {
+        self.level
+    }
+
+    /// The total experience the character has earned.
+    #[must_use]
+    pub const fn experience(&self) -> usize {
+        self.experience
+    }
+
+    /// The number of stats the character may still raise from previous level-ups.
+    #[must_use]
+    pub const fn stat_points(&self) -> usize {
+        self.stat_points
+    }
+
+    /// The class features the character has unlocked by levelling up.
+    #[must_use]
+    pub fn unlocks(&self) -> &[String] {
+        &self.unlocks
+    }
+
+    /// Award experience to the character, levelling them up as many times as the given
+    /// [`ClassProgression`] allows. Returns the rewards for every level gained, which have already
+    /// been applied; any stat points granted must then be spent with [`Character::raise_stat`].
+    pub fn gain_experience(
+        &mut self,
+        amount: usize,
+        progression: &ClassProgression,
+    ) -> Vec<LevelReward> {
+        self.experience += amount;
+
+        let mut rewards = Vec::new();
+        while let Some(reward) = progression.reward(self.level + 1) {
+            if self.experience < reward.experience {
+                break;
+            }
+            self.level = reward.level;
+            self.stat_points += reward.stat_points;
+            self.unlocks.extend(reward.unlocks.iter().cloned());
+            rewards.push(reward.clone());
+        }
+        rewards
+    }
+
+    /// Spend one stat point to raise the given stat by one. Returns `false` if the character has no
+    /// stat points to spend.
+    pub fn raise_stat(&mut self, stat: Stat) -> bool {
+        if self.stat_points == 0 {
+            return false;
+        }
+        self.stat_points -= 1;
+
+        let old_maxima = Resource::ALL.map(|resource| self.max_resource(resource));
+        self.stats.add_to_stat(stat, 1);
+        for (resource, old_max) in Resource::ALL.into_iter().zip(old_maxima) {
+            *self.resource_mut(resource) += self.max_resource(resource) - old_max;
+        }
+        true
+    }
+
+    /// The current amount of the given [`Resource`].
+    #[must_use]
+    pub const fn resource(&self, resource: Resource) -> isize {
+        match resource {
+            Resource::Health => self.health,
+            Resource::Stamina => self.stamina,
+            Resource::Focus => self.focus,
+            Resource::Luck => self.luck,
+        }
+    }
+
+    const fn resource_mut(&mut self, resource: Resource) -> &mut isize {
+        match resource {
+            Resource::Health => &mut self.health,
+            Resource::Stamina => &mut self.stamina,
+            Resource::Focus => &mut self.focus,
+            Resource::Luck => &mut self.luck,
+        }
+    }
+
+    /// The maximum amount of the given [`Resource`], derived from the character's [`Class`] and
+    /// base stats.
+    #[must_use]
+    pub fn max_resource(&self, resource: Resource) -> isize {
+        let from_stats = match resource {
+            Resource::Health => return self.max_health(),
+            Resource::Stamina => self.stats.backbone,
+            Resource::Focus => self.stats.the_sight,
+            Resource::Luck => self.stats.cunning / 2,
+        };
+        (self.class.resource_bonus(resource) + from_stats).max(0)
+    }
+
+    /// Whether the character has enough of the given [`Resource`] to spend `amount` of it. Health
+    /// can never be spent down to zero.
+    #[must_use]
+    pub const fn can_afford(&self, resource: Resource, amount: isize) -> bool {
+        match resource {
+            Resource::Health => self.health > amount,
+            Resource::Stamina | Resource::Focus | Resource::Luck => {
+                self.resource(resource) >= amount
+            }
+        }
+    }
+
+    /// Spend some of the given [`Resource`], returning `false` (and spending nothing) if the
+    /// character cannot afford it.
+    pub const fn spend(&mut self, resource: Resource, amount: isize) -> bool {
+        if !self.can_afford(resource, amount) {
+            return false;
+        }
+        *self.resource_mut(resource) -= amount;
+        true
+    }
+
+    /// Restore the given amount of a [`Resource`], up to the character's maximum.
+    pub fn restore(&mut self, resource: Resource, amount: isize) {
+        let max = self.max_resource(resource);
+        let current = self.resource_mut(resource);
+        *current = (*current + amount).min(max);
+    }
+
+    /// Take a rest, fully restoring health, stamina and focus. Luck is harder to come by, and only
+    /// returns one point at a time.
+    pub fn rest(&mut self) {
+        for resource in [Resource::Health, Resource::Stamina, Resource::Focus] {
+            self.restore(resource, self.max_resource(resource));
+        }
+        self.restore(Resource::Luck, 1);
+    }
+
+    /// Make a check using the given stat, with any active status effects taken into account.
+    #[must_use]
+    pub fn check(&self, stat: Stat, difficulty: isize) -> CheckResult {
+        CheckResult::roll(self.effective_stats().get(stat), difficulty)
+    }
+
+    /// The character's proficiency in every [`Skill`].
+    #[must_use]
+    pub const fn proficiencies(&self) -> &Proficiencies {
+        &self.proficiencies
+    }
+
+    /// The modifier added to checks of a [`Skill`]: the effective value of its parent stat plus the
+    /// bonus for the character's proficiency in it.
+    #[must_use]
+    pub fn skill_modifier(&self, skill: Skill) -> isize {
+        self.effective_stats().get(skill.stat()) + self.proficiencies.get(skill).bonus()
+    }
+
+    /// Roll a check of a [`Skill`] against the given difficulty.
+    #[must_use]
+    pub fn skill_check(&self, skill: Skill, difficulty: isize) -> CheckResult {
+        CheckResult::roll(self.skill_modifier(skill), difficulty)
+    }
+
+    /// Spend a point of [`Resource::Luck`] to reroll a failed check. Returns [`None`] (and spends
+    /// nothing) if the check passed or the character has no luck left.
+    pub fn reroll_check(&mut self, check: &CheckResult) -> Option<CheckResult> {
+        if check.passed() || !self.spend(Resource::Luck, 1) {
+            return None;
         }
+        Some(check.reroll())
+    }
+
+    /// The number of turns before the [`Ability`] with the given ID can be used again.
+    #[must_use]
+    pub fn cooldown(&self, ability_id: &str) -> usize {
+        self.cooldowns.get(ability_id).copied().unwrap_or_default()
+    }
+
+    /// Count down the cooldowns of every ability the character has used.
+    pub fn tick_cooldowns(&mut self) {
+        self.cooldowns.retain(|_, turns| {
+            *turns = turns.saturating_sub(1);
+            *turns > 0
+        });
+    }
+
+    /// Check whether the character is currently able to use the given [`Ability`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns the first [`AbilityError`] preventing the ability from being used.
+    pub fn can_use_ability(&self, ability: &Ability) -> Result<(), AbilityError> {
+        if let Some(class) = ability.class().filter(|&class| class != self.class) {
+            return Err(AbilityError::WrongClass(class));
+        }
+        if self.level < ability.min_level() {
+            return Err(AbilityError::LevelTooLow(ability.min_level()));
+        }
+        match self.cooldown(ability.id()) {
+            0 => {}
+            turns => return Err(AbilityError::OnCooldown(turns)),
+        }
+        if let Some(cost) = ability.cost()
+            && !self.can_afford(cost.resource, cost.amount)
+        {
+            return Err(AbilityError::CannotAfford(cost));
+        }
+        Ok(())
+    }
+
+    /// Use an [`Ability`] on the given targets, paying its cost and starting its cooldown.
+    /// Abilities targeting [`TargetType::Caster`] ignore `targets`, so can be used outside of
+    /// combat by passing no targets at all; a [`TargetType::SingleEnemy`] ability only affects the
+    /// first target.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`AbilityError`] if the ability cannot currently be used, or if it
+    /// needs a target and none were given. Nothing is spent in that case.
+    pub fn use_ability(
+        &mut self,
+        ability: &Ability,
+        targets: &mut [&mut dyn Combatant],
+    ) -> Result<AbilityOutcome, AbilityError> {
+        self.can_use_ability(ability)?;
+
+        let stats = self.effective_stats();
+        let outcome = match ability.target() {
+            TargetType::Caster => ability.effect().apply(&stats, &mut [self]),
+            TargetType::SingleEnemy => {
+                let target = targets.first_mut().ok_or(AbilityError::NoTarget)?;
+                ability.effect().apply(&stats, &mut [&mut **target])
+            }
+            TargetType::AllEnemies => {
+                if targets.is_empty() {
+                    return Err(AbilityError::NoTarget);
+                }
+                ability.effect().apply(&stats, targets)
+            }
+        };
+
+        if let Some(cost) = ability.cost() {
+            self.spend(cost.resource, cost.amount);
+        }
+        if ability.cooldown() > 0 {
+            // The turn the ability is used on counts down too, so add it on
+            self.cooldowns
+                .insert(String::from(ability.id()), ability.cooldown() + 1);
+        }
+        Ok(outcome)
+    }
+
+    /// The character's stats before any [`StatusEffects`] are taken into account.
+    #[must_use]
+    pub const fn base_stats(&self) -> &StatValues {
+        &self.stats
+    }
+
+    /// The character's stats with the modifiers of all active [`StatusEffects`] applied.
+    #[must_use]
+    pub fn effective_stats(&self) -> StatValues {
+        &self.stats + &self.status_effects.modifiers()
+    }
+
+    /// The character's current health.
+    #[must_use]
+    pub const fn health(&self) -> isize {
+        self.health
+    }
+
+    /// The character's maximum health, derived from their base [`Stat::Backbone`].
+    #[must_use]
+    pub const fn max_health(&self) -> isize {
+        BASE_HEALTH + 2 * self.stats.backbone
+    }
+
+    /// Whether the character has run out of health.
+    #[must_use]
+    pub const fn is_down(&self) -> bool {
+        self.health <= 0
+    }
+
+    /// Reduce the character's health by the given amount.
+    pub const fn take_damage(&mut self, amount: isize) {
+        self.health -= amount;
+    }
+
+    /// Restore the given amount of health, up to the character's maximum.
+    pub fn heal(&mut self, amount: isize) {
+        self.health = (self.health + amount).min(self.max_health());
+    }
+
+    /// The status effects currently active on the character.
+    #[must_use]
+    pub const fn status_effects(&self) -> &StatusEffects {
+        &self.status_effects
+    }
+
+    /// Apply a status effect to the character, stacking it with any existing effect of the same
+    /// kind.
+    pub fn apply_status_effect(&mut self, effect: StatusEffect) {
+        self.status_effects.apply(effect);
+    }
+
+    /// End the character's turn, running the per-turn triggers of their status effects (taking any
+    /// damage they deal) and counting down their durations.
+    pub fn tick_status_effects(&mut self) -> StatusTick {
+        let tick = self.status_effects.tick();
+        self.take_damage(isize::try_from(tick.total_damage()).unwrap_or(isize::MAX));
+        tick
     }
 }
 impl Display for Character {
@@ -44,16 +502,381 @@ impl Display for Character {
             f,
             "===={}====
 {} {}
+Level {} ({} XP)
+HP {}/{}
+Stamina {}/{} | Focus {}/{} | Luck {}/{}
 =====================
 {}",
-            self.name, self.race, self.class, self.stats
-        )
+            self.name,
+            self.race,
+            self.class,
+            self.level,
+            self.experience,
+            self.health,
+            self.max_health(),
+            self.stamina,
+            self.max_resource(Resource::Stamina),
+            self.focus,
+            self.max_resource(Resource::Focus),
+            self.luck,
+            self.max_resource(Resource::Luck),
+            self.effective_stats()
+        )?;
+        for effect in self.status_effects.iter() {
+            write!(f, "\n{effect}")?;
+        }
+        if let Some(background) = &self.background {
+            write!(
+                f,
+                "\n=====================\nBackground: {}",
+                background.name()
+            )?;
+            for background_trait in background.traits() {
+                write!(f, "\nTrait: {background_trait}")?;
+            }
+            for flaw in background.flaws() {
+                write!(f, "\nFlaw: {flaw}")?;
+            }
+        }
+        if !self.proficiencies.is_empty() {
+            write!(f, "\nSkills: {}", self.proficiencies)?;
+        }
+        write!(f, "\nInventory: {}", self.inventory)
+    }
+}
+
+/// The longest name a [`Character`] can have, in characters.
+pub const MAX_NAME_LENGTH: usize = 32;
+
+/// Check that the given name is acceptable for a [`Character`]: not blank, no longer than
+/// [`MAX_NAME_LENGTH`], and made only of letters, spaces, hyphens, apostrophes and full stops.
+/// Surrounding whitespace is ignored.
+///
+/// # Errors
+///
+/// This function returns the [`BuildError`] describing what is wrong with the name.
+pub fn validate_name(name: &str) -> Result<(), BuildError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(BuildError::EmptyName);
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(BuildError::NameTooLong);
+    }
+    if let Some(invalid) = name
+        .chars()
+        .find(|&c| !(c.is_alphabetic() || matches!(c, ' ' | '-' | '\'' | '.')))
+    {
+        return Err(BuildError::InvalidNameCharacter(invalid));
+    }
+    Ok(())
+}
+
+/// A step of building a [`Character`] with a [`CharacterBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildStep {
+    /// Choosing a name with [`CharacterBuilder::set_name`].
+    Name,
+    /// Choosing a class with [`CharacterBuilder::set_class`].
+    Class,
+    /// Choosing a race with [`CharacterBuilder::set_race`].
+    Race,
+    /// Choosing a background, or none, with [`CharacterBuilder::set_background`].
+    Background,
+    /// Choosing a bonus stat with [`CharacterBuilder::add_bonus_stat`].
+    BonusStat,
+    /// Buying stats with [`CharacterBuilder::set_purchased_stats`].
+    PointBuy,
+    /// Rolling stats with [`CharacterBuilder::roll_stats`].
+    RollStats,
+}
+
+/// The reasons a [`CharacterBuilder`] step or build can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// The name is blank.
+    EmptyName,
+    /// The name is longer than [`MAX_NAME_LENGTH`].
+    NameTooLong,
+    /// The name contains a character that isn't allowed.
+    InvalidNameCharacter(char),
+    /// The adventure requires both bonus stats to differ, but this one was already chosen.
+    DuplicateBonusStat(Stat),
+    /// Both bonus stats have already been chosen.
+    TooManyBonusStats,
+    /// The chosen stats are invalid for the adventure's [`CreationMode`].
+    Creation(CreationError),
+    /// A step has not been completed yet.
+    Incomplete(BuildStep),
+}
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "You can't have an empty name!"),
+            Self::NameTooLong => {
+                write!(f, "Names can be at most {MAX_NAME_LENGTH} characters long")
+            }
+            Self::InvalidNameCharacter(c) => write!(f, "Names can't contain '{c}'"),
+            Self::DuplicateBonusStat(stat) => {
+                write!(f, "{stat} has already been chosen as a bonus stat")
+            }
+            Self::TooManyBonusStats => write!(f, "Both bonus stats have already been chosen"),
+            Self::Creation(error) => write!(f, "{error}"),
+            Self::Incomplete(step) => match step {
+                BuildStep::Name => write!(f, "A name has not been chosen yet"),
+                BuildStep::Class => write!(f, "A class has not been chosen yet"),
+                BuildStep::Race => write!(f, "A race has not been chosen yet"),
+                BuildStep::Background => write!(f, "A background has not been chosen yet"),
+                BuildStep::BonusStat => write!(f, "The bonus stats have not all been chosen yet"),
+                BuildStep::PointBuy => write!(f, "Stats have not been bought yet"),
+                BuildStep::RollStats => write!(f, "Stats have not been rolled yet"),
+            },
+        }
+    }
+}
+impl std::error::Error for BuildError {}
+impl From<CreationError> for BuildError {
+    fn from(value: CreationError) -> Self {
+        Self::Creation(value)
+    }
+}
+
+/// The number of bonus stats chosen in [`CreationMode::Standard`].
+const NUM_BONUS_STATS: usize = 2;
+
+/// The choices made so far by a [`CharacterBuilder`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct BuilderChoices {
+    name: Option<String>,
+    class: Option<Class>,
+    race: Option<Race>,
+    background: Option<Background>,
+    /// Whether a background has been chosen, since choosing none is allowed.
+    background_chosen: bool,
+    bonus_stats: Vec<Stat>,
+    purchased: Option<StatValues>,
+}
+
+/// Builds a [`Character`] one validated step at a time, following the [`CreationMode`] and
+/// [`CreationRules`] of an adventure. Any step except rolling stats can be undone, so a frontend
+/// can let the player go back; rolled stats are kept so they can't be rerolled for free.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharacterBuilder {
+    mode: CreationMode,
+    rules: CreationRules,
+    choices: BuilderChoices,
+    history: Vec<BuilderChoices>,
+    rolled: Option<RolledStats>,
+}
+impl CharacterBuilder {
+    /// Start building a character for the given [`Adventure`].
+    #[must_use]
+    pub fn new(adventure: &Adventure) -> Self {
+        Self::with_rules(*adventure.creation(), *adventure.rules())
+    }
+
+    /// Start building a character with the given mode and rules.
+    #[must_use]
+    pub fn with_rules(mode: CreationMode, rules: CreationRules) -> Self {
+        Self {
+            mode,
+            rules,
+            choices: BuilderChoices::default(),
+            history: Vec::new(),
+            rolled: None,
+        }
+    }
+
+    /// The [`CreationMode`] this character is being built with.
+    #[must_use]
+    pub const fn mode(&self) -> &CreationMode {
+        &self.mode
+    }
+
+    /// The next step that still needs to be completed, or [`None`] if the character is ready to
+    /// [`build`](CharacterBuilder::build).
+    #[must_use]
+    pub fn next_step(&self) -> Option<BuildStep> {
+        if self.choices.name.is_none() {
+            Some(BuildStep::Name)
+        } else if self.choices.class.is_none() {
+            Some(BuildStep::Class)
+        } else if self.choices.race.is_none() {
+            Some(BuildStep::Race)
+        } else if !self.choices.background_chosen {
+            Some(BuildStep::Background)
+        } else {
+            match self.mode {
+                CreationMode::Standard if self.choices.bonus_stats.len() < NUM_BONUS_STATS => {
+                    Some(BuildStep::BonusStat)
+                }
+                CreationMode::PointBuy { .. } if self.choices.purchased.is_none() => {
+                    Some(BuildStep::PointBuy)
+                }
+                CreationMode::Rolled { .. } if self.rolled.is_none() => Some(BuildStep::RollStats),
+                _ => None,
+            }
+        }
+    }
+
+    /// Remember the current choices so the next change can be undone.
+    fn checkpoint(&mut self) {
+        self.history.push(self.choices.clone());
+    }
+
+    /// Undo the most recent step. Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(choices) => {
+                self.choices = choices;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Choose the character's name, ignoring surrounding whitespace.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`BuildError`] if the name fails [`validate_name`].
+    pub fn set_name(&mut self, name: &str) -> Result<&mut Self, BuildError> {
+        validate_name(name)?;
+        self.checkpoint();
+        self.choices.name = Some(String::from(name.trim()));
+        Ok(self)
+    }
+
+    /// Choose the character's class.
+    pub fn set_class(&mut self, class: Class) -> &mut Self {
+        self.checkpoint();
+        self.choices.class = Some(class);
+        self
+    }
+
+    /// Choose the character's race.
+    pub fn set_race(&mut self, race: Race) -> &mut Self {
+        self.checkpoint();
+        self.choices.race = Some(race);
+        self
+    }
+
+    /// Choose the character's background, or [`None`] for no background.
+    pub fn set_background(&mut self, background: Option<Background>) -> &mut Self {
+        self.checkpoint();
+        self.choices.background = background;
+        self.choices.background_chosen = true;
+        self
+    }
+
+    /// Choose one of the two bonus stats of [`CreationMode::Standard`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`BuildError`] if the adventure uses a different mode, if both
+    /// bonus stats have been chosen, or if the adventure requires distinct bonus stats and this
+    /// one was already chosen.
+    pub fn add_bonus_stat(&mut self, stat: Stat) -> Result<&mut Self, BuildError> {
+        if self.mode != CreationMode::Standard {
+            return Err(CreationError::WrongMode.into());
+        }
+        if self.choices.bonus_stats.len() >= NUM_BONUS_STATS {
+            return Err(BuildError::TooManyBonusStats);
+        }
+        if self.rules.distinct_bonus_stats && self.choices.bonus_stats.contains(&stat) {
+            return Err(BuildError::DuplicateBonusStat(stat));
+        }
+        self.checkpoint();
+        self.choices.bonus_stats.push(stat);
+        Ok(self)
+    }
+
+    /// Choose the stats bought with [`CreationMode::PointBuy`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`BuildError`] if the purchase is invalid for the adventure's mode.
+    pub fn set_purchased_stats(&mut self, purchased: StatValues) -> Result<&mut Self, BuildError> {
+        self.mode.validate_point_buy(&purchased)?;
+        self.checkpoint();
+        self.choices.purchased = Some(purchased);
+        Ok(self)
+    }
+
+    /// Roll stats with [`CreationMode::Rolled`]. Only the first call rolls; later calls return the
+    /// same stats, which can only be changed with [`CharacterBuilder::reroll_stats`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`BuildError`] if the adventure uses a different mode.
+    pub fn roll_stats(&mut self) -> Result<&RolledStats, BuildError> {
+        if self.rolled.is_none() {
+            self.rolled = Some(self.mode.roll_stats()?);
+        }
+        self.rolled
+            .as_ref()
+            .ok_or(BuildError::Incomplete(BuildStep::RollStats))
+    }
+
+    /// Spend one of the adventure's rerolls to roll a fresh set of stats. Returns `false` if there
+    /// are no rerolls left or the stats haven't been rolled yet.
+    pub fn reroll_stats(&mut self) -> bool {
+        self.rolled.as_mut().is_some_and(RolledStats::reroll)
+    }
+
+    /// Create the [`Character`] from the choices made.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`BuildError::Incomplete`] with the first step still to be completed.
+    pub fn build(&self) -> Result<Character, BuildError> {
+        if let Some(step) = self.next_step() {
+            return Err(BuildError::Incomplete(step));
+        }
+        let incomplete = |step| BuildError::Incomplete(step);
+        let name = self
+            .choices
+            .name
+            .as_deref()
+            .ok_or(incomplete(BuildStep::Name))?;
+        let class = self.choices.class.ok_or(incomplete(BuildStep::Class))?;
+        let race = self.choices.race.ok_or(incomplete(BuildStep::Race))?;
+
+        let mut character = match self.mode {
+            CreationMode::Standard => Character::new(
+                name,
+                class,
+                race,
+                self.choices.bonus_stats[0],
+                self.choices.bonus_stats[1],
+            ),
+            CreationMode::PointBuy { .. } => {
+                let purchased = self
+                    .choices
+                    .purchased
+                    .as_ref()
+                    .ok_or(incomplete(BuildStep::PointBuy))?;
+                Character::from_point_buy(name, class, race, &self.mode, purchased)?
+            }
+            CreationMode::Rolled { .. } => {
+                let rolled = self
+                    .rolled
+                    .as_ref()
+                    .ok_or(incomplete(BuildStep::RollStats))?;
+                Character::from_rolled(name, class, race, rolled)
+            }
+        };
+        if let Some(background) = &self.choices.background {
+            character.apply_background(background.clone());
+        }
+        Ok(character)
     }
 }
 
 // TODO better docs
 /// A class.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Class {
     /// TODO
     Soothsayer,
@@ -66,6 +889,63 @@ pub enum Class {
     /// TODO
     Bastion,
 }
+impl Class {
+    /// Every class, in the order they are offered to the player.
+    pub const ALL: [Self; 5] = [
+        Self::Hunter,
+        Self::Warden,
+        Self::Bastion,
+        Self::Soothsayer,
+        Self::Trespasser,
+    ];
+
+    /// The identifier used for this class in data files.
+    #[must_use]
+    pub const fn id(self) -> &'static str {
+        match self {
+            Self::Soothsayer => "soothsayer",
+            Self::Hunter => "hunter",
+            Self::Trespasser => "trespasser",
+            Self::Warden => "warden",
+            Self::Bastion => "bastion",
+        }
+    }
+
+    /// The bonus this class gives to the maximum of the given [`Resource`].
+    const fn resource_bonus(self, resource: Resource) -> isize {
+        match resource {
+            Resource::Health => 0,
+            Resource::Stamina => match self {
+                Self::Bastion => 4,
+                Self::Hunter => 3,
+                Self::Trespasser | Self::Warden => 1,
+                Self::Soothsayer => 0,
+            },
+            Resource::Focus => match self {
+                Self::Soothsayer => 3,
+                Self::Warden => 2,
+                Self::Hunter | Self::Trespasser | Self::Bastion => 0,
+            },
+            Resource::Luck => match self {
+                Self::Trespasser => 3,
+                Self::Soothsayer | Self::Hunter | Self::Warden => 1,
+                Self::Bastion => 0,
+            },
+        }
+    }
+
+    /// The skills every member of this class is trained in.
+    #[must_use]
+    pub const fn proficiencies(self) -> &'static [Skill] {
+        match self {
+            Self::Soothsayer => &[Skill::Lore, Skill::Insight],
+            Self::Hunter => &[Skill::Survival, Skill::Athletics],
+            Self::Trespasser => &[Skill::Lockpicking, Skill::Stealth],
+            Self::Warden => &[Skill::Herbalism, Skill::Persuasion],
+            Self::Bastion => &[Skill::Intimidation, Skill::Endurance],
+        }
+    }
+}
 impl From<Class> for String {
     fn from(value: Class) -> Self {
         (&value).into()
@@ -91,6 +971,7 @@ impl Display for Class {
 // TODO better docs
 /// A race.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Race {
     /// TODO
     AlligatorFolk,
@@ -99,6 +980,20 @@ pub enum Race {
     /// TODO
     GoblinoidFae,
 }
+impl Race {
+    /// Every race, in the order they are offered to the player.
+    pub const ALL: [Self; 3] = [Self::InsectoidFae, Self::GoblinoidFae, Self::AlligatorFolk];
+
+    /// The skills every member of this race is trained in.
+    #[must_use]
+    pub const fn proficiencies(self) -> &'static [Skill] {
+        match self {
+            Self::AlligatorFolk => &[Skill::Intimidation],
+            Self::InsectoidFae => &[Skill::Lore],
+            Self::GoblinoidFae => &[Skill::Stealth],
+        }
+    }
+}
 impl From<&Race> for String {
     fn from(value: &Race) -> Self {
         match value {
@@ -122,6 +1017,7 @@ impl Display for Race {
 // TODO better docs
 /// All the different stats.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Stat {
     /// TODO
     Cunning,
@@ -134,6 +1030,16 @@ pub enum Stat {
     /// TODO
     TheSight,
 }
+impl Stat {
+    /// Every stat, in the order they are offered to the player.
+    pub const ALL: [Self; 5] = [
+        Self::Slipperiness,
+        Self::Cunning,
+        Self::Bulk,
+        Self::Backbone,
+        Self::TheSight,
+    ];
+}
 impl From<&Stat> for String {
     fn from(value: &Stat) -> Self {
         match value {
@@ -150,9 +1056,79 @@ impl From<Stat> for String {
         (&value).into()
     }
 }
+impl FromStr for Stat {
+    type Err = UnknownStat;
+
+    /// Parse a stat from its identifier, e.g. `TheSight`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Cunning" => Ok(Self::Cunning),
+            "Slipperiness" => Ok(Self::Slipperiness),
+            "Bulk" => Ok(Self::Bulk),
+            "Backbone" => Ok(Self::Backbone),
+            "TheSight" => Ok(Self::TheSight),
+            _ => Err(UnknownStat(String::from(s))),
+        }
+    }
+}
+impl Display for Stat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from(self))
+    }
+}
+
+/// A name that isn't the identifier of any [`Stat`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownStat(pub String);
+impl Display for UnknownStat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown stat `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownStat {}
+
+/// A resource a [`Character`] can spend, e.g. to pay for an [`Ability`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Resource {
+    /// The character's own health.
+    Health,
+    /// Physical endurance, fuelled by [`Stat::Backbone`].
+    Stamina,
+    /// Mental energy, fuelled by [`Stat::TheSight`].
+    Focus,
+    /// Spent to reroll failed checks; fuelled by [`Stat::Cunning`].
+    Luck,
+}
+impl Resource {
+    /// Every resource.
+    pub const ALL: [Self; 4] = [Self::Health, Self::Stamina, Self::Focus, Self::Luck];
+}
+impl From<&Resource> for String {
+    fn from(value: &Resource) -> Self {
+        match value {
+            Resource::Health => String::from("Health"),
+            Resource::Stamina => String::from("Stamina"),
+            Resource::Focus => String::from("Focus"),
+            Resource::Luck => String::from("Luck"),
+        }
+    }
+}
+impl From<Resource> for String {
+    fn from(value: Resource) -> Self {
+        (&value).into()
+    }
+}
+impl Display for Resource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from(self))
+    }
+}
 
 /// The stat values of a given [`Character`].
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct StatValues {
     cunning: isize,
     slipperiness: isize,
@@ -171,7 +1147,36 @@ impl StatValues {
         values
     }
 
-    fn add_to_stat(&mut self, stat: Stat, value: isize) {
+    /// Set the value of a single stat.
+    pub const fn set(&mut self, stat: Stat, value: isize) {
+        match stat {
+            Stat::Cunning => self.cunning = value,
+            Stat::Slipperiness => self.slipperiness = value,
+            Stat::Bulk => self.bulk = value,
+            Stat::Backbone => self.backbone = value,
+            Stat::TheSight => self.the_sight = value,
+        }
+    }
+
+    /// The sum of every stat.
+    #[must_use]
+    pub const fn total(&self) -> isize {
+        self.cunning + self.slipperiness + self.bulk + self.backbone + self.the_sight
+    }
+
+    /// Get the value of a single stat.
+    #[must_use]
+    pub const fn get(&self, stat: Stat) -> isize {
+        match stat {
+            Stat::Cunning => self.cunning,
+            Stat::Slipperiness => self.slipperiness,
+            Stat::Bulk => self.bulk,
+            Stat::Backbone => self.backbone,
+            Stat::TheSight => self.the_sight,
+        }
+    }
+
+    pub(crate) const fn add_to_stat(&mut self, stat: Stat, value: isize) {
         match stat {
             Stat::Cunning => self.cunning += value,
             Stat::Slipperiness => self.slipperiness += value,
@@ -246,6 +1251,10 @@ impl StatValues {
             },
         };
     }
+
+    fn add_background_bonus(&mut self, background: &Background) {
+        *self += background.stats().clone();
+    }
 }
 impl<'a> Add<&'a StatValues> for &StatValues {
     type Output = StatValues;
@@ -280,8 +1289,12 @@ impl Display for StatValues {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    #[cfg(feature = "fs-content")]
+    use crate::skill::Proficiency;
+    use crate::status::StatusKind;
 
     #[test]
     fn add_class_race_stats() {
@@ -352,4 +1365,419 @@ mod tests {
 
         assert_eq!(character.stats, expected);
     }
+
+    #[test]
+    fn point_buy_character() {
+        let mode = CreationMode::PointBuy {
+            budget: 10,
+            max_per_stat: 4,
+        };
+        let mut purchased = StatValues::default();
+        purchased.set(Stat::Bulk, 4);
+        purchased.set(Stat::Backbone, 4);
+        purchased.set(Stat::Cunning, 2);
+
+        let character = Character::from_point_buy(
+            "Budget Bertha",
+            Class::Soothsayer,
+            Race::AlligatorFolk,
+            &mode,
+            &purchased,
+        )
+        .unwrap();
+        assert_eq!(
+            character.stats,
+            StatValues {
+                cunning: 2,
+                slipperiness: 0,
+                bulk: 5,
+                backbone: 5,
+                the_sight: 0,
+            }
+        );
+
+        purchased.set(Stat::TheSight, 1);
+        assert_eq!(
+            Character::from_point_buy(
+                "Greedy Greg",
+                Class::Soothsayer,
+                Race::AlligatorFolk,
+                &mode,
+                &purchased,
+            ),
+            Err(CreationError::OverBudget {
+                spent: 11,
+                budget: 10
+            })
+        );
+    }
+
+    #[test]
+    fn rolled_character() {
+        let mode = CreationMode::Rolled {
+            roll: "1d1 + 1".parse().unwrap(),
+            rerolls: 0,
+            min_total: 0,
+        };
+        let rolled = mode.roll_stats().unwrap();
+        let character =
+            Character::from_rolled("Rolled Rita", Class::Hunter, Race::GoblinoidFae, &rolled);
+        assert_eq!(
+            character.stats,
+            StatValues {
+                cunning: 3,
+                slipperiness: 3,
+                bulk: 2,
+                backbone: 2,
+                the_sight: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn validate_names() {
+        assert_eq!(validate_name("Mr. Test"), Ok(()));
+        assert_eq!(validate_name("Ka'li Mire-Walker"), Ok(()));
+        assert_eq!(validate_name("   "), Err(BuildError::EmptyName));
+        assert_eq!(validate_name(&"a".repeat(33)), Err(BuildError::NameTooLong));
+        assert_eq!(validate_name(&"a".repeat(32)), Ok(()));
+        assert_eq!(
+            validate_name("R2-D2"),
+            Err(BuildError::InvalidNameCharacter('2'))
+        );
+    }
+
+    #[test]
+    fn build_standard_character() {
+        let mut builder = CharacterBuilder::with_rules(
+            CreationMode::Standard,
+            CreationRules {
+                distinct_bonus_stats: true,
+            },
+        );
+        assert_eq!(builder.next_step(), Some(BuildStep::Name));
+        assert_eq!(builder.set_name("").err(), Some(BuildError::EmptyName));
+        builder
+            .set_name("  Mr. Test ")
+            .unwrap()
+            .set_class(Class::Bastion)
+            .set_race(Race::InsectoidFae);
+        assert_eq!(
+            builder.build(),
+            Err(BuildError::Incomplete(BuildStep::Background))
+        );
+        builder.set_background(None);
+        let incomplete = builder.build().unwrap_err();
+        assert_eq!(incomplete, BuildError::Incomplete(BuildStep::BonusStat));
+        assert_eq!(
+            incomplete.to_string(),
+            "The bonus stats have not all been chosen yet"
+        );
+
+        builder.add_bonus_stat(Stat::Bulk).unwrap();
+        assert_eq!(
+            builder.add_bonus_stat(Stat::Bulk).err(),
+            Some(BuildError::DuplicateBonusStat(Stat::Bulk))
+        );
+        builder.add_bonus_stat(Stat::Slipperiness).unwrap();
+        assert_eq!(
+            builder.add_bonus_stat(Stat::Cunning).err(),
+            Some(BuildError::TooManyBonusStats)
+        );
+        assert_eq!(builder.next_step(), None);
+
+        let character = builder.build().unwrap();
+        assert_eq!(
+            character,
+            Character::new(
+                "Mr. Test",
+                Class::Bastion,
+                Race::InsectoidFae,
+                Stat::Slipperiness,
+                Stat::Bulk,
+            )
+        );
+    }
+
+    #[test]
+    fn undo_builder_steps() {
+        let mut builder =
+            CharacterBuilder::with_rules(CreationMode::Standard, CreationRules::default());
+        assert!(!builder.undo());
+        builder
+            .set_name("Indecisive Ivy")
+            .unwrap()
+            .set_class(Class::Hunter);
+        assert_eq!(builder.next_step(), Some(BuildStep::Race));
+
+        assert!(builder.undo());
+        assert_eq!(builder.next_step(), Some(BuildStep::Class));
+        builder
+            .set_class(Class::Warden)
+            .set_race(Race::GoblinoidFae)
+            .set_background(None);
+        builder.add_bonus_stat(Stat::Cunning).unwrap();
+        builder.add_bonus_stat(Stat::Cunning).unwrap();
+        assert!(builder.undo());
+        builder.add_bonus_stat(Stat::TheSight).unwrap();
+
+        let character = builder.build().unwrap();
+        assert_eq!(character.class(), Class::Warden);
+        assert_eq!(character.base_stats().get(Stat::Cunning), 7);
+        assert_eq!(character.base_stats().get(Stat::TheSight), 4);
+    }
+
+    #[test]
+    fn build_with_other_modes() {
+        let mut point_buy = CharacterBuilder::with_rules(
+            CreationMode::PointBuy {
+                budget: 5,
+                max_per_stat: 5,
+            },
+            CreationRules::default(),
+        );
+        point_buy
+            .set_name("Pointy")
+            .unwrap()
+            .set_class(Class::Soothsayer)
+            .set_race(Race::AlligatorFolk)
+            .set_background(None);
+        assert_eq!(
+            point_buy.add_bonus_stat(Stat::Bulk).err(),
+            Some(BuildError::Creation(CreationError::WrongMode))
+        );
+        assert_eq!(point_buy.next_step(), Some(BuildStep::PointBuy));
+        let mut purchased = StatValues::default();
+        purchased.set(Stat::TheSight, 5);
+        point_buy.set_purchased_stats(purchased).unwrap();
+        assert_eq!(
+            point_buy.build().unwrap().base_stats().get(Stat::TheSight),
+            5
+        );
+
+        let mut rolled = CharacterBuilder::with_rules(
+            CreationMode::Rolled {
+                roll: "1d6".parse().unwrap(),
+                rerolls: 1,
+                min_total: 0,
+            },
+            CreationRules::default(),
+        );
+        rolled
+            .set_name("Rolly")
+            .unwrap()
+            .set_class(Class::Hunter)
+            .set_race(Race::AlligatorFolk)
+            .set_background(None);
+        assert_eq!(rolled.next_step(), Some(BuildStep::RollStats));
+        assert!(!rolled.reroll_stats());
+        let first = rolled.roll_stats().unwrap().clone();
+        assert_eq!(rolled.roll_stats().unwrap(), &first);
+        assert!(rolled.undo());
+        assert_eq!(rolled.roll_stats().unwrap(), &first);
+        rolled.set_background(None);
+        assert!(rolled.reroll_stats());
+        assert!(!rolled.reroll_stats());
+        assert!(rolled.build().is_ok());
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn build_with_background() {
+        let smuggler = Background::try_load("bog_smuggler").unwrap();
+        let mut builder =
+            CharacterBuilder::with_rules(CreationMode::Standard, CreationRules::default());
+        builder
+            .set_name("Sly Sal")
+            .unwrap()
+            .set_class(Class::Trespasser)
+            .set_race(Race::GoblinoidFae);
+        assert_eq!(builder.next_step(), Some(BuildStep::Background));
+        builder.set_background(Some(smuggler.clone()));
+        builder.add_bonus_stat(Stat::Cunning).unwrap();
+        builder.add_bonus_stat(Stat::Bulk).unwrap();
+
+        let character = builder.build().unwrap();
+        let plain = Character::new(
+            "Sly Sal",
+            Class::Trespasser,
+            Race::GoblinoidFae,
+            Stat::Cunning,
+            Stat::Bulk,
+        );
+        assert_eq!(character.background(), Some(&smuggler));
+        assert_eq!(
+            character.base_stats(),
+            &(plain.base_stats() + smuggler.stats())
+        );
+        assert_eq!(character.health(), character.max_health());
+        assert!(character.inventory().contains("hooded_lantern"));
+        assert!(plain.inventory().is_empty());
+
+        let sheet = character.to_string();
+        assert!(sheet.contains("Background: Bog Smuggler"));
+        assert!(sheet.contains("Flaw: Wanted in three bogs"));
+        assert!(sheet.contains("hooded_lantern"));
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn proficiencies_stack_across_sources() {
+        let mut character = Character::new(
+            "Sneaky Sue",
+            Class::Trespasser,
+            Race::GoblinoidFae,
+            Stat::Cunning,
+            Stat::Bulk,
+        );
+        assert_eq!(
+            character.proficiencies().get(Skill::Stealth),
+            Proficiency::Expert
+        );
+        character.apply_background(Background::try_load("bog_smuggler").unwrap());
+        assert_eq!(
+            character.proficiencies().get(Skill::Stealth),
+            Proficiency::Master
+        );
+        assert_eq!(
+            character.proficiencies().get(Skill::Lockpicking),
+            Proficiency::Expert
+        );
+        assert_eq!(
+            character.proficiencies().get(Skill::Herbalism),
+            Proficiency::Untrained
+        );
+        assert_eq!(
+            character.skill_modifier(Skill::Stealth),
+            character.effective_stats().get(Stat::Slipperiness) + 6
+        );
+        assert_eq!(
+            character.skill_check(Skill::Herbalism, 10).modifier(),
+            character.effective_stats().get(Stat::TheSight)
+        );
+        assert!(character.to_string().contains("Stealth (Master)"));
+    }
+
+    #[test]
+    fn status_effects_modify_stats() {
+        let mut character = Character::new(
+            "Bogged Bob",
+            Class::Trespasser,
+            Race::GoblinoidFae,
+            Stat::Cunning,
+            Stat::Cunning,
+        );
+        character.apply_status_effect(StatusEffect::new(StatusKind::BoggedDown, 2));
+        assert_eq!(character.base_stats().get(Stat::Slipperiness), 6);
+        assert_eq!(character.effective_stats().get(Stat::Slipperiness), 4);
+
+        character.tick_status_effects();
+        character.tick_status_effects();
+        assert_eq!(character.effective_stats().get(Stat::Slipperiness), 6);
+    }
+
+    #[test]
+    fn poison_deals_damage() {
+        let mut character = Character::new(
+            "Sickly Sue",
+            Class::Hunter,
+            Race::AlligatorFolk,
+            Stat::Bulk,
+            Stat::Bulk,
+        );
+        let max_health = character.max_health();
+        assert_eq!(max_health, 22);
+        assert_eq!(character.health(), max_health);
+
+        character.apply_status_effect(StatusEffect::new(StatusKind::Poisoned, 1));
+        let tick = character.tick_status_effects();
+        let damage = isize::try_from(tick.total_damage()).unwrap_or_default();
+        assert!((1..=4).contains(&damage));
+        assert_eq!(character.health(), max_health - damage);
+        assert!(character.status_effects().is_empty());
+    }
+
+    #[test]
+    fn resource_pools() {
+        let mut character = Character::new(
+            "Pooled Pam",
+            Class::Soothsayer,
+            Race::InsectoidFae,
+            Stat::TheSight,
+            Stat::Cunning,
+        );
+        assert_eq!(character.max_resource(Resource::Stamina), 1);
+        assert_eq!(character.max_resource(Resource::Focus), 10);
+        assert_eq!(character.max_resource(Resource::Luck), 3);
+        assert_eq!(character.resource(Resource::Focus), 10);
+
+        assert!(character.spend(Resource::Focus, 10));
+        assert!(!character.spend(Resource::Focus, 1));
+        assert!(!character.spend(Resource::Health, character.health()));
+
+        assert!(character.spend(Resource::Luck, 3));
+        character.take_damage(4);
+        character.rest();
+        assert_eq!(character.resource(Resource::Focus), 10);
+        assert_eq!(character.health(), character.max_health());
+        assert_eq!(character.resource(Resource::Luck), 1);
+    }
+
+    #[test]
+    fn reroll_failed_check_with_luck() {
+        let mut character = Character::new(
+            "Lucky Lou",
+            Class::Trespasser,
+            Race::GoblinoidFae,
+            Stat::Cunning,
+            Stat::Cunning,
+        );
+        let luck = character.resource(Resource::Luck);
+        assert_eq!(luck, 6);
+
+        let impossible = character.check(Stat::Cunning, 100);
+        assert!(!impossible.passed());
+        let reroll = character.reroll_check(&impossible).unwrap();
+        assert_eq!(reroll.difficulty(), 100);
+        assert_eq!(character.resource(Resource::Luck), luck - 1);
+
+        let trivial = character.check(Stat::Cunning, 0);
+        assert!(character.reroll_check(&trivial).is_none());
+        assert_eq!(character.resource(Resource::Luck), luck - 1);
+
+        assert!(character.spend(Resource::Luck, luck - 1));
+        assert!(character.reroll_check(&impossible).is_none());
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn level_up_and_raise_stats() {
+        let progression = ClassProgression::try_load(Class::Warden).unwrap();
+        let mut character = Character::new(
+            "Experienced Ed",
+            Class::Warden,
+            Race::AlligatorFolk,
+            Stat::Cunning,
+            Stat::TheSight,
+        );
+        assert_eq!(character.level(), STARTING_LEVEL);
+        assert!(!character.raise_stat(Stat::Backbone));
+
+        let rewards = character.gain_experience(99, &progression);
+        assert!(rewards.is_empty());
+
+        let rewards = character.gain_experience(200, &progression);
+        assert_eq!(rewards.len(), 2);
+        assert_eq!(character.level(), 3);
+        assert_eq!(character.experience(), 299);
+        assert_eq!(character.stat_points(), 2);
+        assert_eq!(character.unlocks(), [String::from("Swamp Lore")]);
+
+        let old_health = character.health();
+        assert!(character.raise_stat(Stat::Backbone));
+        assert!(character.raise_stat(Stat::Backbone));
+        assert!(!character.raise_stat(Stat::Backbone));
+        assert_eq!(character.base_stats().get(Stat::Backbone), 6);
+        assert_eq!(character.health(), old_health + 4);
+    }
 }
@@ -0,0 +1,98 @@
+//! Checks: rolling a `1d20`, adding a modifier and comparing against a difficulty.
+
+use std::fmt::Display;
+
+use crate::dice::{RollManyRequest, RolledDiceResults};
+
+/// The die rolled for every check.
+pub const CHECK_DIE: RollManyRequest = RollManyRequest::new(1, 20);
+
+/// The outcome of a single check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    roll: RolledDiceResults,
+    modifier: isize,
+    difficulty: isize,
+}
+impl CheckResult {
+    /// Roll a check, adding `modifier` to a roll of the [`CHECK_DIE`] and comparing the total
+    /// against `difficulty`.
+    #[must_use]
+    pub fn roll(modifier: isize, difficulty: isize) -> Self {
+        Self {
+            roll: CHECK_DIE.roll(),
+            modifier,
+            difficulty,
+        }
+    }
+
+    /// Roll the check again with the same modifier and difficulty.
+    #[must_use]
+    pub fn reroll(&self) -> Self {
+        Self::roll(self.modifier, self.difficulty)
+    }
+
+    /// The dice rolled for the check.
+    #[must_use]
+    pub const fn dice(&self) -> &RolledDiceResults {
+        &self.roll
+    }
+
+    /// The modifier added to the roll.
+    #[must_use]
+    pub const fn modifier(&self) -> isize {
+        self.modifier
+    }
+
+    /// The total the check needed to meet or beat.
+    #[must_use]
+    pub const fn difficulty(&self) -> isize {
+        self.difficulty
+    }
+
+    /// The roll plus the modifier.
+    #[must_use]
+    pub fn total(&self) -> isize {
+        isize::try_from(self.roll.total()).unwrap_or(isize::MAX) + self.modifier
+    }
+
+    /// Whether the total met or beat the difficulty.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.total() >= self.difficulty
+    }
+}
+impl Display for CheckResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {:+} = {} vs {}: {}",
+            self.roll,
+            self.modifier,
+            self.total(),
+            self.difficulty,
+            if self.passed() { "passed" } else { "failed" }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_totals() {
+        for _ in 0..100 {
+            let check = CheckResult::roll(3, 15);
+            assert!((4..=23).contains(&check.total()));
+            assert_eq!(check.passed(), check.total() >= 15);
+        }
+    }
+
+    #[test]
+    fn trivial_and_impossible_checks() {
+        assert!(CheckResult::roll(0, 1).passed());
+        assert!(!CheckResult::roll(0, 21).passed());
+        assert!(CheckResult::roll(-5, 21).reroll().difficulty() == 21);
+    }
+}
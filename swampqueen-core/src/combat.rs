@@ -0,0 +1,457 @@
+//! Turn-based combat between [`Character`]s and [`Creature`]s. The principal type of this module is
+//! [`Combat`], which tracks the enemies of a single fight and runs each round.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::content::{ContentKind, load_json};
+use crate::{
+    ability::{Ability, AbilityError, AbilityOutcome, ScaledRoll},
+    character::{Character, Stat, StatValues},
+    dice::{RollManyRequest, RolledDiceResults},
+    status::{StatusEffect, StatusEffects, StatusTick},
+};
+
+/// The attack every [`Character`] can make without using an [`Ability`].
+pub const BASIC_ATTACK: ScaledRoll =
+    ScaledRoll::new(RollManyRequest::new(1, 6), 0, Some(Stat::Bulk));
+
+/// Anything that can take part in combat.
+pub trait Combatant {
+    /// The name shown to the player.
+    fn name(&self) -> &str;
+
+    /// Stats with the modifiers of all active status effects applied.
+    fn effective_stats(&self) -> StatValues;
+
+    /// Current health.
+    fn health(&self) -> isize;
+
+    /// Reduce health by the given amount.
+    fn take_damage(&mut self, amount: isize);
+
+    /// Restore the given amount of health, up to the maximum.
+    fn heal(&mut self, amount: isize);
+
+    /// Apply a status effect, stacking it with any existing effect of the same kind.
+    fn apply_status_effect(&mut self, effect: StatusEffect);
+
+    /// Run the per-turn triggers of all active status effects and count down their durations.
+    fn tick_status_effects(&mut self) -> StatusTick;
+
+    /// Whether this combatant has run out of health.
+    fn is_down(&self) -> bool {
+        self.health() <= 0
+    }
+}
+impl Combatant for Character {
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    fn effective_stats(&self) -> StatValues {
+        self.effective_stats()
+    }
+
+    fn health(&self) -> isize {
+        self.health()
+    }
+
+    fn take_damage(&mut self, amount: isize) {
+        self.take_damage(amount);
+    }
+
+    fn heal(&mut self, amount: isize) {
+        self.heal(amount);
+    }
+
+    fn apply_status_effect(&mut self, effect: StatusEffect) {
+        self.apply_status_effect(effect);
+    }
+
+    fn tick_status_effects(&mut self) -> StatusTick {
+        self.tick_status_effects()
+    }
+}
+
+/// A hostile creature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Creature {
+    id: String,
+    name: String,
+    stats: StatValues,
+    max_health: isize,
+    attack: ScaledRoll,
+    #[cfg_attr(feature = "serde", serde(default))]
+    experience: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    health: isize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    status_effects: StatusEffects,
+}
+impl Creature {
+    /// Load a [`Creature`] from the game's [content](crate::content), at full health.
+    ///
+    /// # Arguments
+    ///
+    /// * `creature_id` - A [`std::str`] corresponding to the ID of the desired creature. In a
+    ///   content directory, this is the name of the creature's `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load(creature_id: &str) -> std::io::Result<Self> {
+        let mut creature: Self = load_json(ContentKind::Creature, creature_id)?;
+        creature.health = creature.max_health;
+        Ok(creature)
+    }
+
+    /// The ID of this kind of creature.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The creature's maximum health.
+    #[must_use]
+    pub const fn max_health(&self) -> isize {
+        self.max_health
+    }
+
+    /// The experience awarded for defeating this creature.
+    #[must_use]
+    pub const fn experience(&self) -> usize {
+        self.experience
+    }
+
+    /// The status effects currently active on the creature.
+    #[must_use]
+    pub const fn status_effects(&self) -> &StatusEffects {
+        &self.status_effects
+    }
+}
+impl Combatant for Creature {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn effective_stats(&self) -> StatValues {
+        &self.stats + &self.status_effects.modifiers()
+    }
+
+    fn health(&self) -> isize {
+        self.health
+    }
+
+    fn take_damage(&mut self, amount: isize) {
+        self.health -= amount;
+    }
+
+    fn heal(&mut self, amount: isize) {
+        self.health = (self.health + amount).min(self.max_health);
+    }
+
+    fn apply_status_effect(&mut self, effect: StatusEffect) {
+        self.status_effects.apply(effect);
+    }
+
+    fn tick_status_effects(&mut self) -> StatusTick {
+        let tick = self.status_effects.tick();
+        self.take_damage(isize::try_from(tick.total_damage()).unwrap_or(isize::MAX));
+        tick
+    }
+}
+impl From<&Creature> for String {
+    fn from(value: &Creature) -> Self {
+        format!("{} ({}/{})", value.name, value.health, value.max_health)
+    }
+}
+
+/// A single attack made during combat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attack {
+    /// The name of the attacker.
+    pub attacker: String,
+    /// The name of the one attacked.
+    pub target: String,
+    /// The dice rolled for the attack.
+    pub rolls: RolledDiceResults,
+    /// The damage dealt.
+    pub damage: isize,
+}
+
+/// Everything that happened at the end of a round.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RoundEnd {
+    /// The status effect ticks of every combatant still standing, alongside their name.
+    pub ticks: Vec<(String, StatusTick)>,
+}
+
+/// How a fight turned out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CombatOutcome {
+    /// Both sides are still standing.
+    Ongoing,
+    /// Every enemy is down.
+    Victory,
+    /// Every hero is down.
+    Defeat,
+}
+
+/// A fight between the heroes and a group of [`Creature`]s. The heroes are passed into each round
+/// rather than owned, in marching order; enemies always attack the first hero still standing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Combat {
+    enemies: Vec<Creature>,
+    round: usize,
+}
+impl Combat {
+    /// Start a fight against the given enemies.
+    #[must_use]
+    pub const fn new(enemies: Vec<Creature>) -> Self {
+        Self { enemies, round: 1 }
+    }
+
+    /// Every enemy in the fight, including those that are down.
+    #[must_use]
+    pub fn enemies(&self) -> &[Creature] {
+        &self.enemies
+    }
+
+    /// The index and value of every enemy still standing.
+    pub fn standing_enemies(&self) -> impl Iterator<Item = (usize, &Creature)> {
+        self.enemies
+            .iter()
+            .enumerate()
+            .filter(|(_, enemy)| !enemy.is_down())
+    }
+
+    /// The current round, starting from 1.
+    #[must_use]
+    pub const fn round(&self) -> usize {
+        self.round
+    }
+
+    /// The total experience awarded for defeating every enemy.
+    #[must_use]
+    pub fn experience(&self) -> usize {
+        self.enemies.iter().map(Creature::experience).sum()
+    }
+
+    /// Make a [`BASIC_ATTACK`] against the enemy at index `target`. Returns [`None`] if there is no
+    /// enemy standing at that index.
+    pub fn attack(&mut self, hero: &Character, target: usize) -> Option<Attack> {
+        let enemy = self.enemies.get_mut(target).filter(|e| !e.is_down())?;
+        let (rolls, damage) = BASIC_ATTACK.roll(&hero.effective_stats());
+        enemy.take_damage(damage);
+        Some(Attack {
+            attacker: String::from(hero.name()),
+            target: enemy.name.clone(),
+            rolls,
+            damage,
+        })
+    }
+
+    /// Use an [`Ability`] against the enemy at index `target`, or against every standing enemy if
+    /// `target` is [`None`]. Use [`Game::use_ability`](crate::game::Game::use_ability) to have it
+    /// reported as events.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`AbilityError`] if the hero cannot currently use the ability.
+    pub fn use_ability(
+        &mut self,
+        hero: &mut Character,
+        ability: &Ability,
+        target: Option<usize>,
+    ) -> Result<AbilityOutcome, AbilityError> {
+        let mut targets: Vec<&mut dyn Combatant> = self
+            .enemies
+            .iter_mut()
+            .enumerate()
+            .filter(|(i, enemy)| !enemy.is_down() && target.is_none_or(|t| t == *i))
+            .map(|(_, enemy)| enemy as &mut dyn Combatant)
+            .collect();
+        hero.use_ability(ability, &mut targets)
+    }
+
+    /// Every standing enemy attacks the first standing hero.
+    pub fn enemy_turn(&mut self, heroes: &mut [Character]) -> Vec<Attack> {
+        let mut attacks = Vec::new();
+        for enemy in self.enemies.iter().filter(|e| !e.is_down()) {
+            let Some(hero) = heroes.iter_mut().find(|hero| !hero.is_down()) else {
+                break;
+            };
+            let (rolls, damage) = enemy.attack.roll(&enemy.effective_stats());
+            hero.take_damage(damage);
+            attacks.push(Attack {
+                attacker: enemy.name.clone(),
+                target: String::from(hero.name()),
+                rolls,
+                damage,
+            });
+        }
+        attacks
+    }
+
+    /// End the round, ticking the status effects of every standing combatant and the ability
+    /// cooldowns of every hero.
+    pub fn end_round(&mut self, heroes: &mut [Character]) -> RoundEnd {
+        let mut end = RoundEnd::default();
+        for hero in heroes.iter_mut() {
+            hero.tick_cooldowns();
+            if !hero.is_down() {
+                end.ticks
+                    .push((String::from(hero.name()), hero.tick_status_effects()));
+            }
+        }
+        for enemy in self.enemies.iter_mut().filter(|e| !e.is_down()) {
+            end.ticks
+                .push((enemy.name.clone(), enemy.tick_status_effects()));
+        }
+        self.round += 1;
+        end
+    }
+
+    /// How the fight currently stands.
+    #[must_use]
+    pub fn outcome(&self, heroes: &[Character]) -> CombatOutcome {
+        if heroes.iter().all(Character::is_down) {
+            CombatOutcome::Defeat
+        } else if self.enemies.iter().all(Combatant::is_down) {
+            CombatOutcome::Victory
+        } else {
+            CombatOutcome::Ongoing
+        }
+    }
+}
+
+#[cfg(all(test, feature = "fs-content"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{
+        ability::ClassAbilities,
+        character::{Class, Race},
+        status::StatusKind,
+    };
+
+    fn test_hero(class: Class) -> Character {
+        Character::new(
+            "Hero",
+            class,
+            Race::AlligatorFolk,
+            Stat::Bulk,
+            Stat::Backbone,
+        )
+    }
+
+    #[test]
+    fn load_creature() {
+        let lurker = Creature::try_load("bog_lurker").unwrap();
+        assert_eq!(lurker.name(), "Bog Lurker");
+        assert_eq!(lurker.health(), lurker.max_health());
+    }
+
+    #[test]
+    fn fight_until_victory() {
+        let mut heroes = [test_hero(Class::Bastion)];
+        let mut combat = Combat::new(vec![Creature::try_load("swamp_rat").unwrap()]);
+
+        while combat.outcome(&heroes) == CombatOutcome::Ongoing {
+            let (target, _) = combat.standing_enemies().next().unwrap();
+            let attack = combat.attack(&heroes[0], target).unwrap();
+            // 1d6 + Bulk, with a Bastion's Bulk at 7
+            assert!((8..=13).contains(&attack.damage));
+            combat.enemy_turn(&mut heroes);
+            combat.end_round(&mut heroes);
+        }
+
+        assert_eq!(combat.outcome(&heroes), CombatOutcome::Victory);
+        assert_eq!(combat.experience(), 10);
+        assert!(combat.attack(&heroes[0], 0).is_none());
+    }
+
+    #[test]
+    fn area_ability_hits_every_enemy_then_cools_down() {
+        let abilities = ClassAbilities::try_load(Class::Soothsayer).unwrap();
+        let fireball = abilities.get("fireball").unwrap();
+        let mut heroes = [test_hero(Class::Soothsayer)];
+        let mut combat = Combat::new(vec![
+            Creature::try_load("bog_lurker").unwrap(),
+            Creature::try_load("bog_lurker").unwrap(),
+        ]);
+
+        let outcome = combat.use_ability(&mut heroes[0], fireball, None).unwrap();
+        assert_eq!(outcome.targets_hit, 2);
+        for enemy in combat.enemies() {
+            assert_eq!(enemy.health(), enemy.max_health() - outcome.amount);
+        }
+        assert_eq!(
+            combat.use_ability(&mut heroes[0], fireball, None),
+            Err(AbilityError::OnCooldown(fireball.cooldown() + 1))
+        );
+
+        for _ in 0..=fireball.cooldown() {
+            combat.end_round(&mut heroes);
+        }
+        assert!(heroes[0].can_use_ability(fireball).is_ok());
+    }
+
+    #[test]
+    fn ability_requirements() {
+        let abilities = ClassAbilities::try_load(Class::Soothsayer).unwrap();
+        let mut hunter = test_hero(Class::Hunter);
+        let mut soothsayer = test_hero(Class::Soothsayer);
+
+        let fireball = abilities.get("fireball").unwrap();
+        assert_eq!(
+            hunter.can_use_ability(fireball),
+            Err(AbilityError::WrongClass(Class::Soothsayer))
+        );
+
+        let omen = abilities.get("dread_omen").unwrap();
+        assert_eq!(
+            soothsayer.can_use_ability(omen),
+            Err(AbilityError::LevelTooLow(3))
+        );
+
+        let mend = abilities.get("mend").unwrap();
+        soothsayer.take_damage(5);
+        let outcome = soothsayer.use_ability(mend, &mut []).unwrap();
+        assert_eq!(
+            soothsayer.health(),
+            (soothsayer.max_health() - 5 + outcome.amount).min(soothsayer.max_health())
+        );
+        assert_eq!(
+            hunter.use_ability(abilities.get("dread_omen").unwrap(), &mut []),
+            Err(AbilityError::WrongClass(Class::Soothsayer))
+        );
+    }
+
+    #[test]
+    fn status_ability_on_single_target() {
+        let abilities = ClassAbilities::try_load(Class::Trespasser).unwrap();
+        let mut heroes = [test_hero(Class::Trespasser)];
+        let mut combat = Combat::new(vec![
+            Creature::try_load("swamp_rat").unwrap(),
+            Creature::try_load("bog_lurker").unwrap(),
+        ]);
+
+        let mud_trap = abilities.get("mud_trap").unwrap();
+        combat
+            .use_ability(&mut heroes[0], mud_trap, Some(1))
+            .unwrap();
+        assert!(combat.enemies()[0].status_effects().is_empty());
+        assert!(
+            combat.enemies()[1]
+                .status_effects()
+                .get(StatusKind::BoggedDown)
+                .is_some()
+        );
+    }
+}
@@ -0,0 +1,817 @@
+//! Typed commands in the style of classic interactive fiction: `look`, `examine loudspeaker`,
+//! `go north`, `take cable`, `talk to mossback`, `inventory`, `journal`, `roll 3d6`, `history`.
+//!
+//! [`parse`] turns a line of input into a [`Command`], matching its nouns against a [`Vocabulary`]
+//! of what the party can see, carry and go to, and [`execute`] carries it out in a [`Game`].
+//! Because [`parse`] rejects anything that can't be done, it can be used as the validator of
+//! [`ui::input`](crate::ui::input) so the player is asked again with the reason.
+//!
+//! | Command            | Synonyms                              | Noun                          |
+//! |--------------------|---------------------------------------|-------------------------------|
+//! | `look`             | `l`                                   | none, or as for `examine`     |
+//! | `examine <thing>`  | `x`, `inspect`, `look at`, `read`     | location text key or item     |
+//! | `go <direction>`   | `walk`, `move`, `head`, or just `north`, `n`... | an exit             |
+//! | `take <item>`      | `get`, `grab`, `pick up`              | an item lying here            |
+//! | `talk to <npc>`    | `talk`, `speak to`, `speak`           | an NPC here                   |
+//! | `say <number>`     | or just the number                    | a response, while talking     |
+//! | `inventory`        | `inv`, `i`                            | none                          |
+//! | `journal`          | `quests`, `j`                         | none                          |
+//! | `roll <dice>`      |                                       | dice such as `1d20 + Cunning` |
+//! | `history [page]`   | `rolls`                               | none, or a page number        |
+//! | `help`             | `?`                                   | none                          |
+//! | `quit`             | `q`, `exit`                           | none                          |
+//!
+//! Nouns can be shortened to any of their words (`cable` for `frayed_cable`), and `the`, `a` and
+//! `an` are ignored. If a short noun could mean more than one thing, the player is asked which.
+//!
+//! Talking to an NPC lists the responses the party can give, numbered from 1. A fight started by
+//! a response isn't played out here; the conversation just ends.
+
+use std::{collections::HashSet, fmt::Display};
+
+use crate::{
+    dialogue::DialogueError,
+    game::{Game, GameError},
+    location::Location,
+};
+
+/// Every verb and its synonyms. Phrases of more than one word come before any single word they
+/// start with.
+const VERBS: &[(&str, Verb)] = &[
+    ("look at", Verb::Examine),
+    ("look", Verb::Look),
+    ("l", Verb::Look),
+    ("examine", Verb::Examine),
+    ("x", Verb::Examine),
+    ("inspect", Verb::Examine),
+    ("read", Verb::Examine),
+    ("go", Verb::Go),
+    ("walk", Verb::Go),
+    ("move", Verb::Go),
+    ("head", Verb::Go),
+    ("take", Verb::Take),
+    ("get", Verb::Take),
+    ("grab", Verb::Take),
+    ("pick up", Verb::Take),
+    ("talk to", Verb::Talk),
+    ("talk", Verb::Talk),
+    ("speak to", Verb::Talk),
+    ("speak", Verb::Talk),
+    ("say", Verb::Say),
+    ("inventory", Verb::Inventory),
+    ("inv", Verb::Inventory),
+    ("i", Verb::Inventory),
+    ("journal", Verb::Journal),
+    ("quests", Verb::Journal),
+    ("j", Verb::Journal),
+    ("roll", Verb::Roll),
+    ("history", Verb::History),
+    ("rolls", Verb::History),
+    ("help", Verb::Help),
+    ("?", Verb::Help),
+    ("quit", Verb::Quit),
+    ("q", Verb::Quit),
+    ("exit", Verb::Quit),
+];
+
+/// Every direction and its abbreviation.
+const DIRECTIONS: &[(&str, &str)] = &[
+    ("n", "north"),
+    ("s", "south"),
+    ("e", "east"),
+    ("w", "west"),
+    ("ne", "northeast"),
+    ("nw", "northwest"),
+    ("se", "southeast"),
+    ("sw", "southwest"),
+    ("u", "up"),
+    ("d", "down"),
+];
+
+/// How many rolls [`Command::History`] shows per page.
+const HISTORY_PAGE_LENGTH: usize = 10;
+
+/// Words ignored in nouns.
+const ARTICLES: &[&str] = &["the", "a", "an"];
+
+/// The text shown for [`Command::Help`].
+const HELP: &str = "You can type: look, examine <thing>, go <direction>, take <item>, \
+                    talk to <someone>, say <number>, inventory, journal, roll <dice>, \
+                    history [page], help, quit";
+
+/// What a command does, before its noun is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Verb {
+    Look,
+    Examine,
+    Go,
+    Take,
+    Talk,
+    Say,
+    Inventory,
+    Journal,
+    Roll,
+    History,
+    Help,
+    Quit,
+}
+
+/// A parsed command, with its noun resolved to an ID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Describe the current location again.
+    Look,
+    /// Look closely at something: either a key of the location's text, or an item's ID.
+    Examine(String),
+    /// Leave in a direction.
+    Go(String),
+    /// Pick up the item with this ID.
+    Take(String),
+    /// Start talking to the NPC with this ID.
+    Talk(String),
+    /// Give a response in the current conversation, counting from 1 among those the party can
+    /// give.
+    Say(usize),
+    /// List what the party is carrying.
+    Inventory,
+    /// Show the quests the party has started.
+    Journal,
+    /// Roll dice written like `3d6` or `1d20 + Cunning`.
+    Roll(String),
+    /// Show a page of the roll history, counting from 1 for the most recent rolls.
+    History(usize),
+    /// List the commands.
+    Help,
+    /// Stop typing commands.
+    Quit,
+}
+
+/// Everything a command's noun can refer to at the moment.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Vocabulary {
+    /// Keys of the location's text, other than its introduction.
+    scenery: Vec<String>,
+    /// IDs of the items the party is carrying.
+    carried: Vec<String>,
+    /// IDs of the items lying at the location.
+    lying: Vec<String>,
+    /// Directions the party can leave in.
+    exits: Vec<String>,
+    /// IDs of the NPCs at the location.
+    npcs: Vec<String>,
+    /// How many responses the party can give in the current conversation.
+    responses: usize,
+}
+impl Vocabulary {
+    /// Everything the party can refer to in the game as it is now.
+    #[must_use]
+    pub fn new(game: &Game) -> Self {
+        let location = game.location();
+        Self {
+            scenery: location
+                .into_iter()
+                .flat_map(Location::text_keys)
+                .filter(|key| *key != "intro")
+                .map(String::from)
+                .collect(),
+            carried: game
+                .party()
+                .into_iter()
+                .flat_map(|party| party.inventory().iter())
+                .map(|(item, _)| String::from(item))
+                .collect(),
+            lying: game.items_here().into_iter().map(String::from).collect(),
+            exits: location
+                .into_iter()
+                .flat_map(Location::exits)
+                .map(String::from)
+                .collect(),
+            npcs: location
+                .into_iter()
+                .flat_map(Location::npcs)
+                .cloned()
+                .collect(),
+            responses: game
+                .conversation()
+                .zip(game.party())
+                .map_or(0, |(conversation, party)| {
+                    conversation.responses(party, game.state()).count()
+                }),
+        }
+    }
+}
+
+/// Parse a line of input into a [`Command`].
+///
+/// # Errors
+///
+/// This function returns a [`CommandError`] if the verb isn't known, or the noun is missing,
+/// doesn't match anything or could mean more than one thing.
+pub fn parse(input: &str, vocabulary: &Vocabulary) -> Result<Command, CommandError> {
+    let original = input.trim();
+    let input = original.to_lowercase();
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let Some(&first) = words.first() else {
+        return Err(CommandError::Empty);
+    };
+
+    // A direction on its own means going that way, and a number on its own while talking means
+    // saying that response
+    if words.len() == 1 && is_direction(first) {
+        return go(&words, vocabulary);
+    }
+    if words.len() == 1 && vocabulary.responses > 0 && first.parse::<usize>().is_ok() {
+        return say(&words, vocabulary);
+    }
+
+    let (verb, rest) = VERBS
+        .iter()
+        .find_map(|&(phrase, verb)| {
+            let phrase: Vec<&str> = phrase.split(' ').collect();
+            words
+                .starts_with(&phrase)
+                .then(|| (verb, &words[phrase.len()..]))
+        })
+        .ok_or_else(|| CommandError::UnknownVerb(String::from(first)))?;
+    let noun: Vec<&str> = rest
+        .iter()
+        .copied()
+        .filter(|word| !ARTICLES.contains(word))
+        .collect();
+
+    match verb {
+        Verb::Look if noun.is_empty() => Ok(Command::Look),
+        Verb::Look | Verb::Examine => {
+            let candidates: Vec<String> =
+                [&vocabulary.scenery, &vocabulary.carried, &vocabulary.lying]
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .collect();
+            resolve("examine", &noun, &candidates).map(Command::Examine)
+        }
+        Verb::Go => go(&noun, vocabulary),
+        Verb::Take => resolve("take", &noun, &vocabulary.lying).map(Command::Take),
+        Verb::Talk => resolve("talk to", &noun, &vocabulary.npcs).map(Command::Talk),
+        Verb::Say => say(&noun, vocabulary),
+        Verb::Inventory => Ok(Command::Inventory),
+        Verb::Journal => Ok(Command::Journal),
+        Verb::Roll if rest.is_empty() => Err(CommandError::MissingNoun("roll")),
+        // Stats in the dice are case sensitive, so keep the notation as typed
+        Verb::Roll => Ok(Command::Roll(
+            original
+                .split_whitespace()
+                .skip(words.len() - rest.len())
+                .collect(),
+        )),
+        Verb::History => match noun.as_slice() {
+            [] => Ok(Command::History(1)),
+            [page] => page
+                .parse()
+                .ok()
+                .filter(|page| *page > 0)
+                .map(Command::History)
+                .ok_or_else(|| CommandError::UnknownNoun(String::from(*page))),
+            _ => Err(CommandError::UnknownNoun(noun.join(" "))),
+        },
+        Verb::Help => Ok(Command::Help),
+        Verb::Quit => Ok(Command::Quit),
+    }
+}
+
+/// Whether `word` is a direction or the abbreviation of one.
+fn is_direction(word: &str) -> bool {
+    DIRECTIONS
+        .iter()
+        .any(|(short, full)| *short == word || *full == word)
+}
+
+/// The full name of a direction, if `word` is an abbreviation of one.
+fn direction(word: &str) -> &str {
+    DIRECTIONS
+        .iter()
+        .find(|(short, _)| *short == word)
+        .map_or(word, |(_, full)| full)
+}
+
+fn go(noun: &[&str], vocabulary: &Vocabulary) -> Result<Command, CommandError> {
+    let noun: Vec<&str> = noun.iter().map(|word| direction(word)).collect();
+    resolve("go", &noun, &vocabulary.exits).map(Command::Go)
+}
+
+fn say(noun: &[&str], vocabulary: &Vocabulary) -> Result<Command, CommandError> {
+    if vocabulary.responses == 0 {
+        return Err(CommandError::NotTalking);
+    }
+    match noun {
+        [] => Err(CommandError::MissingNoun("say")),
+        [number] => number
+            .parse()
+            .ok()
+            .filter(|number| (1..=vocabulary.responses).contains(number))
+            .map(Command::Say)
+            .ok_or_else(|| CommandError::UnknownNoun(String::from(*number))),
+        _ => Err(CommandError::UnknownNoun(noun.join(" "))),
+    }
+}
+
+/// Find the one candidate ID a noun refers to. A noun matches an ID if every word of it is a
+/// word of the ID, and matching an ID exactly beats matching part of one. An ID among the
+/// candidates more than once, like an item both carried and lying here, only counts once.
+fn resolve(
+    verb: &'static str,
+    noun: &[&str],
+    candidates: &[String],
+) -> Result<String, CommandError> {
+    if noun.is_empty() {
+        return Err(CommandError::MissingNoun(verb));
+    }
+    let phrase = noun.join(" ");
+    let mut matches: Vec<&String> = candidates
+        .iter()
+        .filter(|candidate| {
+            let name = noun_name(candidate);
+            noun.iter()
+                .all(|word| name.split(' ').any(|part| part == *word))
+        })
+        .collect();
+    let mut seen = HashSet::new();
+    matches.retain(|candidate| seen.insert(*candidate));
+
+    if let Some(exact) = matches
+        .iter()
+        .find(|candidate| noun_name(candidate) == phrase)
+    {
+        return Ok((*exact).clone());
+    }
+    match matches.as_slice() {
+        [] => Err(CommandError::UnknownNoun(phrase)),
+        [only] => Ok((*only).clone()),
+        many => Err(CommandError::Ambiguous(
+            many.iter().map(|candidate| noun_name(candidate)).collect(),
+        )),
+    }
+}
+
+/// The name the player uses for an ID: `frayed_cable` is `frayed cable`.
+#[must_use]
+pub fn noun_name(id: &str) -> String {
+    id.replace('_', " ").to_lowercase()
+}
+
+/// Carry out a command, returning the lines of text to show the player.
+///
+/// # Errors
+///
+/// This function returns a [`GameError`] if the command can't be carried out, e.g. because the
+/// dice aren't written correctly or the next location can't be entered.
+pub fn execute(game: &mut Game, command: &Command) -> Result<Vec<String>, GameError> {
+    match command {
+        Command::Look => Ok(describe(game)),
+        Command::Examine(key) => {
+            let location = game.location().ok_or(GameError::NoLocation)?;
+            let context = game.condition_context().ok_or(GameError::NoParty)?;
+            let text: Vec<String> = location
+                .paragraphs_for(key, &context)
+                .map(String::from)
+                .collect();
+            if !text.is_empty() {
+                Ok(text)
+            } else if location.text_keys().any(|text_key| text_key == key) {
+                Ok(vec![String::from("You see nothing special.")])
+            } else {
+                Ok(vec![format!(
+                    "You look closely at the {}, but see nothing special.",
+                    noun_name(key)
+                )])
+            }
+        }
+        Command::Go(direction) => {
+            let mut outcome = game.go(direction)?;
+            let mut lines = describe(game);
+            lines.append(&mut outcome.messages);
+            while let Some(location_id) = outcome.go_to.take() {
+                outcome = game.enter_location(&location_id)?;
+                lines.extend(describe(game));
+                lines.append(&mut outcome.messages);
+            }
+            Ok(lines)
+        }
+        Command::Take(item) => {
+            game.take(item)?;
+            let mut lines = vec![format!("You take the {}.", noun_name(item))];
+            lines.extend(game.update_quests()?);
+            Ok(lines)
+        }
+        Command::Talk(npc) => {
+            game.talk(npc)?;
+            Ok(converse(game))
+        }
+        Command::Say(number) => {
+            let npc = game
+                .conversation()
+                .map(|conversation| String::from(conversation.npc().name()));
+            let index = game
+                .conversation()
+                .zip(game.party())
+                .and_then(|(conversation, party)| {
+                    let mut responses = conversation.responses(party, game.state());
+                    number.checked_sub(1).and_then(|n| responses.nth(n))
+                })
+                .map(|(index, _)| index)
+                .ok_or(GameError::Dialogue(DialogueError::NoSuchResponse(*number)))?;
+            let outcome = game.say(index)?;
+
+            let mut lines: Vec<String> = outcome.check.iter().map(ToString::to_string).collect();
+            if let Some(creatures) = outcome.combat {
+                let creatures: Vec<String> = creatures.iter().map(|id| noun_name(id)).collect();
+                lines.push(format!(
+                    "A fight breaks out with the {}!",
+                    creatures.join(" and the ")
+                ));
+            }
+            match (game.conversation(), npc) {
+                (Some(_), _) => lines.extend(converse(game)),
+                (None, Some(npc)) => lines.push(format!("You stop talking to {npc}.")),
+                (None, None) => {}
+            }
+            lines.extend(game.update_quests()?);
+            Ok(lines)
+        }
+        Command::Inventory => {
+            let party = game.party().ok_or(GameError::NoParty)?;
+            Ok(vec![format!("You are carrying: {}", party.inventory())])
+        }
+        Command::Journal => Ok(game
+            .state()
+            .journal()
+            .to_string()
+            .lines()
+            .map(String::from)
+            .collect()),
+        Command::Roll(dice) => Ok(vec![game.roll(dice)?.to_string()]),
+        Command::History(page) => Ok(history(game, *page)),
+        Command::Help => Ok(vec![String::from(HELP)]),
+        Command::Quit => Ok(Vec::new()),
+    }
+}
+
+/// The current location's name and introduction, as shown when the party enters it.
+pub(crate) fn introduce(game: &Game) -> Vec<String> {
+    let (Some(location), Some(context)) = (game.location(), game.condition_context()) else {
+        return Vec::new();
+    };
+    let mut lines = vec![format!("== {} ==", location.name())];
+    lines.extend(location.paragraphs_for("intro", &context).map(String::from));
+    lines
+}
+
+/// [`introduce`], followed by what is lying around and the ways out.
+fn describe(game: &Game) -> Vec<String> {
+    let mut lines = introduce(game);
+    let items = game.items_here();
+    if !items.is_empty() {
+        let items: Vec<String> = items.into_iter().map(noun_name).collect();
+        lines.push(format!("You can see: {}", items.join(", ")));
+    }
+    if let Some(location) = game.location()
+        && location.exits().next().is_some()
+    {
+        lines.push(format!(
+            "Exits: {}",
+            location.exits().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    lines
+}
+
+/// What the NPC being talked to says, followed by the numbered responses the party can give.
+fn converse(game: &Game) -> Vec<String> {
+    let (Some(conversation), Some(party)) = (game.conversation(), game.party()) else {
+        return Vec::new();
+    };
+    let mut lines: Vec<String> = conversation
+        .node()
+        .into_iter()
+        .flat_map(|node| node.text().iter().cloned())
+        .collect();
+    lines.extend(
+        conversation
+            .responses(party, game.state())
+            .zip(1..)
+            .map(|((_, response), number)| format!("{number}. {}", response.text())),
+    );
+    lines
+}
+
+/// One page of the roll history, oldest roll first, with page 1 holding the most recent rolls.
+fn history(game: &Game, page: usize) -> Vec<String> {
+    let rolls = game.state().roll_history();
+    if rolls.is_empty() {
+        return vec![String::from("You haven't rolled anything yet.")];
+    }
+    let pages = rolls.len().div_ceil(HISTORY_PAGE_LENGTH);
+    let page = page.min(pages);
+    let end = rolls.len() - (page - 1) * HISTORY_PAGE_LENGTH;
+    let start = end.saturating_sub(HISTORY_PAGE_LENGTH);
+
+    let mut lines = vec![format!("Roll history, page {page} of {pages}:")];
+    lines.extend(
+        rolls[start..end]
+            .iter()
+            .zip(start + 1..)
+            .map(|(roll, number)| format!("{number:>3}. {roll}")),
+    );
+    if page < pages {
+        lines.push(format!("Type \"history {}\" for older rolls.", page + 1));
+    }
+    lines
+}
+
+/// The reasons a line of input isn't a [`Command`] that can be carried out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// Nothing was typed.
+    Empty,
+    /// The first word isn't a known verb.
+    UnknownVerb(String),
+    /// The verb needs a noun, but none was given.
+    MissingNoun(&'static str),
+    /// The noun doesn't match anything the verb can apply to.
+    UnknownNoun(String),
+    /// The noun could mean any of these things.
+    Ambiguous(Vec<String>),
+    /// A response was given, but the party isn't talking to anyone.
+    NotTalking,
+}
+impl Display for CommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "What would you like to do? (Type \"help\" for commands)"),
+            Self::UnknownVerb(verb) => write!(f, "I don't know how to \"{verb}\""),
+            Self::MissingNoun("go") => write!(f, "Where do you want to go?"),
+            Self::MissingNoun("talk to") => write!(f, "Who do you want to talk to?"),
+            Self::MissingNoun("say") => write!(f, "Which response do you want to give?"),
+            Self::MissingNoun(verb) => write!(f, "What do you want to {verb}?"),
+            Self::UnknownNoun(noun) => write!(f, "You can't see any \"{noun}\" here"),
+            Self::Ambiguous(candidates) => match candidates.split_last() {
+                Some((last, rest)) => {
+                    write!(f, "Which do you mean: the {}", rest.join(", the "))?;
+                    write!(f, " or the {last}?")
+                }
+                None => write!(f, "Which do you mean?"),
+            },
+            Self::NotTalking => write!(f, "You aren't talking to anyone"),
+        }
+    }
+}
+impl std::error::Error for CommandError {}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "fs-content")]
+    use crate::{
+        adventure::Adventure,
+        character::{CharacterBuilder, Class, Race, Stat},
+    };
+
+    fn vocabulary() -> Vocabulary {
+        Vocabulary {
+            scenery: vec![String::from("loudspeaker"), String::from("lights")],
+            carried: vec![String::from("hooded_lantern"), String::from("lantern")],
+            lying: vec![String::from("frayed_cable"), String::from("rusty_cable")],
+            exits: vec![String::from("north"), String::from("down")],
+            npcs: vec![String::from("old_mossback")],
+            responses: 0,
+        }
+    }
+
+    #[test]
+    fn parse_verbs_and_synonyms() {
+        let vocabulary = vocabulary();
+        let parse = |input| parse(input, &vocabulary);
+        assert_eq!(parse("look"), Ok(Command::Look));
+        assert_eq!(parse("  L "), Ok(Command::Look));
+        assert_eq!(
+            parse("examine the loudspeaker"),
+            Ok(Command::Examine(String::from("loudspeaker")))
+        );
+        assert_eq!(
+            parse("look at lights"),
+            Ok(Command::Examine(String::from("lights")))
+        );
+        assert_eq!(
+            parse("x hooded lantern"),
+            Ok(Command::Examine(String::from("hooded_lantern")))
+        );
+        assert_eq!(parse("go north"), Ok(Command::Go(String::from("north"))));
+        assert_eq!(parse("walk n"), Ok(Command::Go(String::from("north"))));
+        assert_eq!(parse("d"), Ok(Command::Go(String::from("down"))));
+        assert_eq!(parse("North"), Ok(Command::Go(String::from("north"))));
+        assert_eq!(
+            parse("pick up the frayed cable"),
+            Ok(Command::Take(String::from("frayed_cable")))
+        );
+        assert_eq!(
+            parse("get frayed"),
+            Ok(Command::Take(String::from("frayed_cable")))
+        );
+        assert_eq!(
+            parse("talk to old mossback"),
+            Ok(Command::Talk(String::from("old_mossback")))
+        );
+        assert_eq!(
+            parse("speak mossback"),
+            Ok(Command::Talk(String::from("old_mossback")))
+        );
+        assert_eq!(parse("i"), Ok(Command::Inventory));
+        assert_eq!(parse("quests"), Ok(Command::Journal));
+        assert_eq!(parse("roll 3d6"), Ok(Command::Roll(String::from("3d6"))));
+        assert_eq!(
+            parse("roll 1d20 + Cunning"),
+            Ok(Command::Roll(String::from("1d20+Cunning")))
+        );
+        assert_eq!(parse("history"), Ok(Command::History(1)));
+        assert_eq!(parse("rolls 3"), Ok(Command::History(3)));
+        assert_eq!(parse("?"), Ok(Command::Help));
+        assert_eq!(parse("quit"), Ok(Command::Quit));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let vocabulary = vocabulary();
+        let parse = |input| parse(input, &vocabulary);
+        assert_eq!(parse("   "), Err(CommandError::Empty));
+        assert_eq!(
+            parse("dance wildly"),
+            Err(CommandError::UnknownVerb(String::from("dance")))
+        );
+        assert_eq!(parse("take"), Err(CommandError::MissingNoun("take")));
+        assert_eq!(parse("say 1"), Err(CommandError::NotTalking));
+        assert_eq!(
+            parse("1"),
+            Err(CommandError::UnknownVerb(String::from("1")))
+        );
+        assert_eq!(parse("roll"), Err(CommandError::MissingNoun("roll")));
+        assert_eq!(
+            parse("history 0"),
+            Err(CommandError::UnknownNoun(String::from("0")))
+        );
+        assert_eq!(
+            parse("go south"),
+            Err(CommandError::UnknownNoun(String::from("south")))
+        );
+        assert_eq!(
+            parse("s"),
+            Err(CommandError::UnknownNoun(String::from("south")))
+        );
+        // Items being carried can be examined, but not taken again
+        assert_eq!(
+            parse("take lantern"),
+            Err(CommandError::UnknownNoun(String::from("lantern")))
+        );
+
+        // An exact match isn't ambiguous, but a partial one can be
+        assert_eq!(
+            parse("examine lantern"),
+            Ok(Command::Examine(String::from("lantern")))
+        );
+        let err = parse("take cable").unwrap_err();
+        assert_eq!(
+            err,
+            CommandError::Ambiguous(vec![
+                String::from("frayed cable"),
+                String::from("rusty cable")
+            ])
+        );
+        assert_eq!(
+            err.to_string(),
+            "Which do you mean: the frayed cable or the rusty cable?"
+        );
+    }
+
+    #[test]
+    fn parse_items_both_carried_and_lying_here() {
+        let mut vocabulary = vocabulary();
+        vocabulary.carried.push(String::from("frayed_cable"));
+        vocabulary.lying.reverse();
+        let parse = |input| parse(input, &vocabulary);
+        assert_eq!(
+            parse("x frayed"),
+            Ok(Command::Examine(String::from("frayed_cable")))
+        );
+        assert_eq!(
+            parse("x cable"),
+            Err(CommandError::Ambiguous(vec![
+                String::from("frayed cable"),
+                String::from("rusty cable")
+            ]))
+        );
+    }
+
+    #[test]
+    fn parse_responses() {
+        let vocabulary = Vocabulary {
+            responses: 2,
+            ..vocabulary()
+        };
+        let parse = |input| parse(input, &vocabulary);
+        assert_eq!(parse("say 1"), Ok(Command::Say(1)));
+        assert_eq!(parse("2"), Ok(Command::Say(2)));
+        assert_eq!(parse("say"), Err(CommandError::MissingNoun("say")));
+        assert_eq!(
+            parse("say 3"),
+            Err(CommandError::UnknownNoun(String::from("3")))
+        );
+        assert_eq!(
+            parse("0"),
+            Err(CommandError::UnknownNoun(String::from("0")))
+        );
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn execute_commands() {
+        let adventure = Adventure::try_load("testadventure").unwrap();
+        let mut builder = CharacterBuilder::new(&adventure);
+        builder
+            .set_name("Rummage")
+            .unwrap()
+            .set_class(Class::Hunter)
+            .set_race(Race::GoblinoidFae)
+            .set_background(None);
+        builder.add_bonus_stat(Stat::Bulk).unwrap();
+        builder.add_bonus_stat(Stat::Cunning).unwrap();
+        let mut game = Game::default();
+        game.create_character(&builder).unwrap();
+        game.enter_location("testplace").unwrap();
+
+        let mut run = |input: &str| {
+            let command = parse(input, &Vocabulary::new(&game)).unwrap();
+            execute(&mut game, &command).unwrap()
+        };
+        let look = run("look");
+        assert_eq!(look[0], "== Test Place ==");
+        assert!(look.contains(&String::from("You can see: frayed cable")));
+        assert!(look.contains(&String::from("Exits: north")));
+        assert!(run("x loudspeaker")[0].contains("battered tin horn"));
+        assert!(run("examine key")[0].contains("rusty key"));
+        assert_eq!(run("take cable"), ["You take the frayed cable."]);
+        assert!(run("inventory")[0].contains("frayed_cable"));
+        assert!(run("roll 2d6")[0].starts_with("2d6: "));
+        assert!(run("roll 1d20 + Cunning")[0].contains(", total "));
+        let history = run("history");
+        assert_eq!(history[0], "Roll history, page 1 of 1:");
+        assert!(history[1].starts_with("  1. 2d6: "));
+        assert!(history[2].starts_with("  2. 1d20 + Cunning: "));
+
+        assert_eq!(run("journal"), ["The journal is empty."]);
+        let corridor = run("n");
+        assert_eq!(corridor[0], "== Test Corridor ==");
+        assert!(corridor.contains(&String::from("New quest: Mossback's Secret")));
+        let journal = run("j");
+        assert_eq!(journal[0], "== Mossback's Secret ==");
+        assert_eq!(journal.len(), 3);
+        assert!(run("read jars")[0].contains("NOT A FROG"));
+
+        let greeting = run("talk to mossback");
+        assert!(greeting[0].contains("ridiculous reed hat"));
+        assert_eq!(greeting[2], "1. Ask about the bog");
+        assert_eq!(greeting.last().unwrap(), "4. Leave");
+        let bog = run("1");
+        assert!(bog[0].starts_with("\"The bog?"));
+        assert_eq!(bog[1], "1. Take the charm");
+        let done = run("say 1");
+        assert_eq!(done[0], "You stop talking to Old Mossback.");
+        assert!(done[1].contains("A bog lurker has been stalking the reeds"));
+        assert!(run("i")[0].contains("frog_charm"));
+
+        let back = run("go s");
+        assert!(
+            back.iter()
+                .any(|line| line.contains("Back again, Rummage?"))
+        );
+        assert!(!back.iter().any(|line| line.starts_with("You can see")));
+
+        assert_eq!(
+            parse("take cable", &Vocabulary::new(&game)),
+            Err(CommandError::UnknownNoun(String::from("cable")))
+        );
+        let bad_dice = parse("roll lots", &Vocabulary::new(&game)).unwrap();
+        assert!(matches!(
+            execute(&mut game, &bad_dice),
+            Err(GameError::Dice(_))
+        ));
+        for _ in 0..10 {
+            execute(&mut game, &Command::Roll(String::from("1d4"))).unwrap();
+        }
+        let newest = execute(&mut game, &Command::History(1)).unwrap();
+        assert_eq!(newest[0], "Roll history, page 1 of 2:");
+        assert!(newest[1].starts_with("  3. 1d4: "));
+        assert_eq!(newest[11], "Type \"history 2\" for older rolls.");
+        let oldest = execute(&mut game, &Command::History(5)).unwrap();
+        assert_eq!(oldest.len(), 3);
+    }
+}
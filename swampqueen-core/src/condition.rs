@@ -0,0 +1,790 @@
+//! A small expression language for conditions in content files, e.g.
+//! `flag.met_queen && player.stat.cunning >= 4 || player.race == "AlligatorFolk"`.
+//!
+//! A [`Condition`] is parsed and type-checked once, when its file is loaded, and can then be
+//! evaluated any number of times against a [`ConditionContext`]. The variables available are:
+//!
+//! | Variable                 | Type    | Meaning                                          |
+//! |--------------------------|---------|--------------------------------------------------|
+//! | `flag.<name>`            | boolean | Whether the story flag has been set              |
+//! | `item.<id>`              | number  | How many of the item the party holds             |
+//! | `player.stat.<stat>`     | number  | The leader's stat, e.g. `player.stat.the_sight`  |
+//! | `player.skill.<skill>`   | number  | The leader's skill modifier, e.g. `player.skill.stealth` |
+//! | `player.race`            | race    | Compared against a race name, e.g. `"InsectoidFae"` |
+//! | `player.class`           | class   | Compared against a class name, e.g. `"Soothsayer"` |
+//! | `player.level`           | number  | The leader's level                               |
+//! | `party.size`             | number  | The number of party members                      |
+//! | `npc.disposition`        | number  | How the NPC being talked to feels about the party |
+//!
+//! Expressions combine these with `==`, `!=`, `<`, `<=`, `>`, `>=`, `!`, `&&`, `||` and
+//! parentheses. `&&` binds more tightly than `||`.
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    character::{Class, Race, Stat},
+    game::GameState,
+    party::Party,
+    skill::Skill,
+};
+
+/// Everything a [`Condition`] can be evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub struct ConditionContext<'a> {
+    party: &'a Party,
+    state: &'a GameState,
+    npc: Option<&'a str>,
+}
+impl<'a> ConditionContext<'a> {
+    /// Create a context for the given party and game state.
+    #[must_use]
+    pub const fn new(party: &'a Party, state: &'a GameState) -> Self {
+        Self {
+            party,
+            state,
+            npc: None,
+        }
+    }
+
+    /// Set the ID of the NPC being talked to, for `npc.disposition`.
+    #[must_use]
+    pub const fn with_npc(mut self, npc_id: &'a str) -> Self {
+        self.npc = Some(npc_id);
+        self
+    }
+}
+
+/// A parsed and type-checked condition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "String", into = "String"))]
+pub struct Condition {
+    source: String,
+    expr: Expr,
+}
+impl Condition {
+    /// Whether the condition holds in the given context.
+    #[must_use]
+    pub fn evaluate(&self, context: &ConditionContext<'_>) -> bool {
+        matches!(self.expr.evaluate(context), Value::Bool(true))
+    }
+}
+impl Default for Condition {
+    /// A condition that always holds.
+    fn default() -> Self {
+        Self {
+            source: String::from("true"),
+            expr: Expr::Literal(Value::Bool(true)),
+        }
+    }
+}
+impl std::str::FromStr for Condition {
+    type Err = ConditionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            index: 0,
+            end: s.len(),
+        };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(ConditionError::Parse {
+                position: token.position,
+                message: format!("unexpected {}", token.kind),
+            });
+        }
+        let expr = expr.check()?;
+        if expr.ty() != Type::Bool {
+            return Err(ConditionError::Type(format!(
+                "a condition must be a boolean, not a {}",
+                expr.ty()
+            )));
+        }
+        Ok(Self {
+            source: String::from(s.trim()),
+            expr,
+        })
+    }
+}
+impl TryFrom<String> for Condition {
+    type Error = ConditionError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+impl From<Condition> for String {
+    fn from(value: Condition) -> Self {
+        value.source
+    }
+}
+impl Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+/// The reasons a condition cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConditionError {
+    /// The condition is not valid syntax.
+    Parse {
+        /// The byte offset the problem was found at.
+        position: usize,
+        /// What is wrong.
+        message: String,
+    },
+    /// The condition is valid syntax, but doesn't make sense, e.g. comparing a stat to a race.
+    Type(String),
+}
+impl Display for ConditionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Parse { position, message } => write!(f, "at {position}: {message}"),
+            Self::Type(message) => write!(f, "{message}"),
+        }
+    }
+}
+impl std::error::Error for ConditionError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Ident(String),
+    Number(isize),
+    Str(String),
+    Dot,
+    LParen,
+    RParen,
+    Not,
+    And,
+    Or,
+    Op(Op),
+}
+impl Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ident(ident) => write!(f, "`{ident}`"),
+            Self::Number(n) => write!(f, "`{n}`"),
+            Self::Str(s) => write!(f, "\"{s}\""),
+            Self::Dot => write!(f, "`.`"),
+            Self::LParen => write!(f, "`(`"),
+            Self::RParen => write!(f, "`)`"),
+            Self::Not => write!(f, "`!`"),
+            Self::And => write!(f, "`&&`"),
+            Self::Or => write!(f, "`||`"),
+            Self::Op(op) => write!(f, "`{op}`"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+/// Split a condition into tokens.
+fn tokenize(s: &str) -> Result<Vec<Token>, ConditionError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        let mut next_is = |expected: char| chars.next_if(|&(_, c)| c == expected).is_some();
+        let kind = match c {
+            c if c.is_whitespace() => continue,
+            '.' => TokenKind::Dot,
+            '(' => TokenKind::LParen,
+            ')' => TokenKind::RParen,
+            '&' if next_is('&') => TokenKind::And,
+            '|' if next_is('|') => TokenKind::Or,
+            '=' if next_is('=') => TokenKind::Op(Op::Eq),
+            '!' if next_is('=') => TokenKind::Op(Op::Ne),
+            '!' => TokenKind::Not,
+            '<' if next_is('=') => TokenKind::Op(Op::Le),
+            '<' => TokenKind::Op(Op::Lt),
+            '>' if next_is('=') => TokenKind::Op(Op::Ge),
+            '>' => TokenKind::Op(Op::Gt),
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, c)) => string.push(c),
+                        None => {
+                            return Err(ConditionError::Parse {
+                                position,
+                                message: String::from("unterminated string"),
+                            });
+                        }
+                    }
+                }
+                TokenKind::Str(string)
+            }
+            c if c.is_ascii_digit() || c == '-' => {
+                let mut number = String::from(c);
+                while let Some((_, digit)) = chars.next_if(|(_, c)| c.is_ascii_digit()) {
+                    number.push(digit);
+                }
+                TokenKind::Number(number.parse().map_err(|_| ConditionError::Parse {
+                    position,
+                    message: format!("invalid number `{number}`"),
+                })?)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let mut ident = String::from(c);
+                while let Some((_, c)) = chars.next_if(|(_, c)| c.is_alphanumeric() || *c == '_') {
+                    ident.push(c);
+                }
+                TokenKind::Ident(ident)
+            }
+            c => {
+                return Err(ConditionError::Parse {
+                    position,
+                    message: format!("unexpected character `{c}`"),
+                });
+            }
+        };
+        tokens.push(Token { kind, position });
+    }
+    Ok(tokens)
+}
+
+/// A comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+impl Op {
+    const fn is_ordering(self) -> bool {
+        matches!(self, Self::Lt | Self::Le | Self::Gt | Self::Ge)
+    }
+}
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let op = match self {
+            Self::Eq => "==",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        };
+        write!(f, "{op}")
+    }
+}
+
+/// A recursive descent parser over a list of tokens.
+struct Parser<'t> {
+    tokens: &'t [Token],
+    index: usize,
+    /// The position reported for errors at the end of the input.
+    end: usize,
+}
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.index);
+        self.index += 1;
+        token
+    }
+
+    fn eat(&mut self, kind: &TokenKind) -> bool {
+        if self.peek().is_some_and(|token| token.kind == *kind) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error<T>(&self, message: &str) -> Result<T, ConditionError> {
+        Err(ConditionError::Parse {
+            position: self.peek().map_or(self.end, |token| token.position),
+            message: String::from(message),
+        })
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ConditionError> {
+        let mut expr = self.parse_and()?;
+        while self.eat(&TokenKind::Or) {
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ConditionError> {
+        let mut expr = self.parse_not()?;
+        while self.eat(&TokenKind::And) {
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, ConditionError> {
+        if self.eat(&TokenKind::Not) {
+            Ok(Expr::Not(Box::new(self.parse_not()?)))
+        } else {
+            self.parse_comparison()
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ConditionError> {
+        let left = self.parse_atom()?;
+        if let Some(&Token {
+            kind: TokenKind::Op(op),
+            ..
+        }) = self.peek()
+        {
+            self.index += 1;
+            let right = self.parse_atom()?;
+            return Ok(Expr::Compare(Box::new(left), op, Box::new(right)));
+        }
+        Ok(left)
+    }
+
+    fn parse_atom(&mut self) -> Result<Expr, ConditionError> {
+        let Some(token) = self.next().cloned() else {
+            return self.error("unexpected end of condition");
+        };
+        match token.kind {
+            TokenKind::LParen => {
+                let expr = self.parse_or()?;
+                if !self.eat(&TokenKind::RParen) {
+                    return self.error("expected `)`");
+                }
+                Ok(expr)
+            }
+            TokenKind::Number(n) => Ok(Expr::Literal(Value::Int(n))),
+            TokenKind::Str(s) => Ok(Expr::Literal(Value::Str(s))),
+            TokenKind::Ident(ident) if ident == "true" => Ok(Expr::Literal(Value::Bool(true))),
+            TokenKind::Ident(ident) if ident == "false" => Ok(Expr::Literal(Value::Bool(false))),
+            TokenKind::Ident(ident) => {
+                let mut path = vec![ident];
+                while self.eat(&TokenKind::Dot) {
+                    let Some(Token {
+                        kind: TokenKind::Ident(ident),
+                        ..
+                    }) = self.peek()
+                    else {
+                        return self.error("expected a name after `.`");
+                    };
+                    path.push(ident.clone());
+                    self.index += 1;
+                }
+                Variable::from_path(&path)
+                    .map(Expr::Variable)
+                    .ok_or_else(|| ConditionError::Parse {
+                        position: token.position,
+                        message: format!("unknown variable `{}`", path.join(".")),
+                    })
+            }
+            kind => Err(ConditionError::Parse {
+                position: token.position,
+                message: format!("unexpected {kind}"),
+            }),
+        }
+    }
+}
+
+/// Convert an identifier like `TheSight` to `the_sight`.
+fn snake_case(ident: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in ident.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// The stat with the given snake case name, e.g. `the_sight`.
+pub(crate) fn stat_named(name: &str) -> Option<Stat> {
+    Stat::ALL
+        .into_iter()
+        .find(|stat| snake_case(&format!("{stat:?}")) == name)
+}
+
+/// The skill with the given snake case name, e.g. `lockpicking`.
+pub(crate) fn skill_named(name: &str) -> Option<Skill> {
+    Skill::ALL
+        .into_iter()
+        .find(|skill| snake_case(&format!("{skill:?}")) == name)
+}
+
+/// Something a condition can look up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Variable {
+    Flag(String),
+    Item(String),
+    Stat(Stat),
+    Skill(Skill),
+    Race,
+    Class,
+    Level,
+    PartySize,
+    Disposition,
+}
+impl Variable {
+    fn from_path(path: &[String]) -> Option<Self> {
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        match path.as_slice() {
+            ["flag", flag] => Some(Self::Flag(String::from(*flag))),
+            ["item", item] => Some(Self::Item(String::from(*item))),
+            ["player", "stat", stat] => stat_named(stat).map(Self::Stat),
+            ["player", "skill", skill] => skill_named(skill).map(Self::Skill),
+            ["player", "race"] => Some(Self::Race),
+            ["player", "class"] => Some(Self::Class),
+            ["player", "level"] => Some(Self::Level),
+            ["party", "size"] => Some(Self::PartySize),
+            ["npc", "disposition"] => Some(Self::Disposition),
+            _ => None,
+        }
+    }
+
+    const fn ty(&self) -> Type {
+        match self {
+            Self::Flag(_) => Type::Bool,
+            Self::Race => Type::Race,
+            Self::Class => Type::Class,
+            Self::Item(_)
+            | Self::Stat(_)
+            | Self::Skill(_)
+            | Self::Level
+            | Self::PartySize
+            | Self::Disposition => Type::Int,
+        }
+    }
+
+    fn evaluate(&self, context: &ConditionContext<'_>) -> Value {
+        let leader = context.party.leader();
+        match self {
+            Self::Flag(flag) => Value::Bool(context.state.has_flag(flag)),
+            Self::Item(item) => Value::Int(
+                isize::try_from(context.party.inventory().count(item)).unwrap_or(isize::MAX),
+            ),
+            Self::Stat(stat) => Value::Int(leader.effective_stats().get(*stat)),
+            Self::Skill(skill) => Value::Int(leader.skill_modifier(*skill)),
+            Self::Race => Value::Race(leader.race()),
+            Self::Class => Value::Class(leader.class()),
+            Self::Level => Value::Int(isize::try_from(leader.level()).unwrap_or(isize::MAX)),
+            Self::PartySize => {
+                Value::Int(isize::try_from(context.party.members().len()).unwrap_or(isize::MAX))
+            }
+            Self::Disposition => {
+                Value::Int(context.npc.map_or(0, |npc| context.state.disposition(npc)))
+            }
+        }
+    }
+}
+
+/// The type of an expression, checked when the condition is parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Type {
+    Bool,
+    Int,
+    Str,
+    Race,
+    Class,
+}
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Bool => "boolean",
+            Self::Int => "number",
+            Self::Str => "string",
+            Self::Race => "race",
+            Self::Class => "class",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// The value of an expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Bool(bool),
+    Int(isize),
+    Str(String),
+    Race(Race),
+    Class(Class),
+}
+impl Value {
+    const fn ty(&self) -> Type {
+        match self {
+            Self::Bool(_) => Type::Bool,
+            Self::Int(_) => Type::Int,
+            Self::Str(_) => Type::Str,
+            Self::Race(_) => Type::Race,
+            Self::Class(_) => Type::Class,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Literal(Value),
+    Variable(Variable),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Compare(Box<Expr>, Op, Box<Expr>),
+}
+impl Expr {
+    fn ty(&self) -> Type {
+        match self {
+            Self::Literal(value) => value.ty(),
+            Self::Variable(variable) => variable.ty(),
+            Self::Not(_) | Self::And(..) | Self::Or(..) | Self::Compare(..) => Type::Bool,
+        }
+    }
+
+    /// Type-check the expression, turning string literals compared against races and classes
+    /// into those races and classes.
+    fn check(self) -> Result<Self, ConditionError> {
+        let expect_bool = |expr: Self, operator: &str| {
+            let expr = expr.check()?;
+            if expr.ty() == Type::Bool {
+                Ok(Box::new(expr))
+            } else {
+                Err(ConditionError::Type(format!(
+                    "`{operator}` needs a boolean, not a {}",
+                    expr.ty()
+                )))
+            }
+        };
+        match self {
+            Self::Literal(_) | Self::Variable(_) => Ok(self),
+            Self::Not(expr) => Ok(Self::Not(expect_bool(*expr, "!")?)),
+            Self::And(left, right) => Ok(Self::And(
+                expect_bool(*left, "&&")?,
+                expect_bool(*right, "&&")?,
+            )),
+            Self::Or(left, right) => Ok(Self::Or(
+                expect_bool(*left, "||")?,
+                expect_bool(*right, "||")?,
+            )),
+            Self::Compare(left, op, right) => {
+                let left = left.check()?;
+                let right = right.check()?;
+                let (left, right) = match (left.ty(), right.ty()) {
+                    (Type::Race | Type::Class, Type::Str) => {
+                        let right = right.resolve_name(left.ty())?;
+                        (left, right)
+                    }
+                    (Type::Str, Type::Race | Type::Class) => {
+                        let left = left.resolve_name(right.ty())?;
+                        (left, right)
+                    }
+                    _ => (left, right),
+                };
+                if left.ty() != right.ty() {
+                    return Err(ConditionError::Type(format!(
+                        "can't compare a {} with a {}",
+                        left.ty(),
+                        right.ty()
+                    )));
+                }
+                if op.is_ordering() && left.ty() != Type::Int {
+                    return Err(ConditionError::Type(format!(
+                        "`{op}` needs numbers, not a {}",
+                        left.ty()
+                    )));
+                }
+                Ok(Self::Compare(Box::new(left), op, Box::new(right)))
+            }
+        }
+    }
+
+    /// Turn a string literal into the race or class it names.
+    fn resolve_name(self, ty: Type) -> Result<Self, ConditionError> {
+        let Self::Literal(Value::Str(name)) = &self else {
+            return Ok(self);
+        };
+        let value = match ty {
+            Type::Race => Race::ALL
+                .into_iter()
+                .find(|race| format!("{race:?}") == *name)
+                .map(Value::Race),
+            Type::Class => Class::ALL
+                .into_iter()
+                .find(|class| format!("{class:?}") == *name)
+                .map(Value::Class),
+            _ => None,
+        };
+        value
+            .map(Self::Literal)
+            .ok_or_else(|| ConditionError::Type(format!("\"{name}\" is not a known {ty}")))
+    }
+
+    fn evaluate(&self, context: &ConditionContext<'_>) -> Value {
+        match self {
+            Self::Literal(value) => value.clone(),
+            Self::Variable(variable) => variable.evaluate(context),
+            Self::Not(expr) => Value::Bool(!expr.holds(context)),
+            Self::And(left, right) => Value::Bool(left.holds(context) && right.holds(context)),
+            Self::Or(left, right) => Value::Bool(left.holds(context) || right.holds(context)),
+            Self::Compare(left, op, right) => {
+                let left = left.evaluate(context);
+                let right = right.evaluate(context);
+                Value::Bool(match (op, &left, &right) {
+                    (Op::Eq, ..) => left == right,
+                    (Op::Ne, ..) => left != right,
+                    (Op::Lt, Value::Int(l), Value::Int(r)) => l < r,
+                    (Op::Le, Value::Int(l), Value::Int(r)) => l <= r,
+                    (Op::Gt, Value::Int(l), Value::Int(r)) => l > r,
+                    (Op::Ge, Value::Int(l), Value::Int(r)) => l >= r,
+                    // Ruled out by type-checking
+                    _ => false,
+                })
+            }
+        }
+    }
+
+    fn holds(&self, context: &ConditionContext<'_>) -> bool {
+        matches!(self.evaluate(context), Value::Bool(true))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::character::Character;
+
+    fn new_party(class: Class, race: Race) -> Party {
+        Party::new(Character::new(
+            "Conditional Carl",
+            class,
+            race,
+            Stat::Cunning,
+            Stat::Cunning,
+        ))
+    }
+
+    fn holds(condition: &str, party: &Party, state: &GameState) -> bool {
+        condition
+            .parse::<Condition>()
+            .unwrap()
+            .evaluate(&ConditionContext::new(party, state))
+    }
+
+    #[test]
+    fn evaluate_conditions() {
+        // Warden + Goblinoid Fae + 2 bonus points: 5 + 1 + 2 = 8 cunning
+        let party = new_party(Class::Warden, Race::GoblinoidFae);
+        let mut state = GameState::default();
+
+        assert!(holds("true", &party, &state));
+        assert!(!holds("flag.met_queen", &party, &state));
+        assert!(holds("player.stat.cunning >= 8", &party, &state));
+        assert!(!holds("player.stat.cunning > 8", &party, &state));
+        assert!(holds("player.stat.the_sight == 3", &party, &state));
+        assert!(holds("player.race == \"GoblinoidFae\"", &party, &state));
+        assert!(holds("\"Warden\" == player.class", &party, &state));
+        assert!(holds("player.class != \"Hunter\"", &party, &state));
+        assert!(holds(
+            "player.level == 1 && party.size == 1",
+            &party,
+            &state
+        ));
+        assert!(holds("item.frog_charm == 0", &party, &state));
+        assert!(holds("player.skill.herbalism == 5", &party, &state));
+
+        let example =
+            "flag.met_queen && player.stat.cunning >= 4 || player.race == \"AlligatorFolk\"";
+        assert!(!holds(example, &party, &state));
+        state.set_flag("met_queen");
+        assert!(holds(example, &party, &state));
+        assert!(holds(
+            example,
+            &new_party(Class::Hunter, Race::AlligatorFolk),
+            &GameState::default()
+        ));
+
+        assert!(holds("!(flag.met_queen && false)", &party, &state));
+        assert!(holds("!!flag.met_queen", &party, &state));
+    }
+
+    #[test]
+    fn npc_disposition() {
+        let party = new_party(Class::Warden, Race::GoblinoidFae);
+        let mut state = GameState::default();
+        state.change_disposition("old_mossback", 3);
+        let condition: Condition = "npc.disposition >= 3".parse().unwrap();
+        assert!(
+            condition.evaluate(&ConditionContext::new(&party, &state).with_npc("old_mossback"))
+        );
+        assert!(!condition.evaluate(&ConditionContext::new(&party, &state).with_npc("someone")));
+        assert!(!condition.evaluate(&ConditionContext::new(&party, &state)));
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (condition, position) in [
+            ("", 0),
+            ("flag.", 5),
+            ("player.stat.cunning >=", 22),
+            ("(flag.a", 7),
+            ("flag.a flag.b", 7),
+            ("flag.a & flag.b", 7),
+            ("player.mood", 0),
+            ("player.stat.charisma > 1", 0),
+            ("\"unterminated", 0),
+        ] {
+            assert!(
+                matches!(
+                    condition.parse::<Condition>(),
+                    Err(ConditionError::Parse { position: p, .. }) if p == position
+                ),
+                "{condition}: {:?}",
+                condition.parse::<Condition>()
+            );
+        }
+    }
+
+    #[test]
+    fn type_errors() {
+        for condition in [
+            "player.stat.bulk",
+            "player.race == \"Elf\"",
+            "player.class == \"AlligatorFolk\"",
+            "player.stat.bulk == player.race",
+            "player.race > \"AlligatorFolk\"",
+            "flag.a && 3",
+            "!player.level",
+            "flag.a < flag.b",
+            "\"a\" == 1",
+        ] {
+            assert!(
+                matches!(condition.parse::<Condition>(), Err(ConditionError::Type(_))),
+                "{condition}"
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let condition: Condition =
+            serde_json::from_str(r#""flag.a || player.stat.bulk >= -1""#).unwrap();
+        assert_eq!(
+            serde_json::to_string(&condition).unwrap(),
+            r#""flag.a || player.stat.bulk >= -1""#
+        );
+        assert!(serde_json::from_str::<Condition>(r#""flag.a ||""#).is_err());
+        assert_eq!(Condition::default().to_string(), "true");
+    }
+}
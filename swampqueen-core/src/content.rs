@@ -0,0 +1,434 @@
+//! Where the game's data comes from: adventures, locations, classes and the rest.
+//!
+//! Content is looked up by [`ContentKind`] and ID. Anything given to [`provide`] as a string is
+//! used first, which is how content reaches the game where there is no filesystem, such as in a
+//! browser. Otherwise, each [`ContentSource`] is tried in turn:
+//!
+//! 1. Any added with [`add_source`], most recent first.
+//! 2. With the `fs-content` feature, the content [`Directory`], where each kind has its own folder
+//!    (e.g. `locations/testplace.json`).
+//! 3. With the `embedded-content` feature, the default adventure's content, `Embedded` in the
+//!    binary when it was built, so that it can be played without the files beside it.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Debug, Display},
+    io,
+    str::FromStr,
+    sync::{LazyLock, PoisonError, RwLock},
+};
+
+#[cfg(feature = "fs-content")]
+use camino::{Utf8Path, Utf8PathBuf};
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "fs-content")]
+use crate::files::{try_list_files, try_load_text};
+
+// TODO this path is only for testing purposes; eventually it will need to be determined based on
+// config and the like
+#[cfg(feature = "fs-content")]
+static CONTENT_DIR: LazyLock<Utf8PathBuf> = LazyLock::new(|| {
+    let mut path = Utf8PathBuf::new();
+    path.push(env!("CARGO_MANIFEST_DIR"));
+    path.push("testfiles/");
+    path
+});
+
+/// Content given to [`provide`], by kind and ID.
+static PROVIDED: LazyLock<RwLock<BTreeMap<(ContentKind, String), String>>> =
+    LazyLock::new(RwLock::default);
+
+/// Where to look for content that wasn't provided, in order.
+static SOURCES: LazyLock<RwLock<Vec<Box<dyn ContentSource>>>> = LazyLock::new(|| {
+    RwLock::new(vec![
+        #[cfg(feature = "fs-content")]
+        Box::new(Directory::new(CONTENT_DIR.clone())),
+        #[cfg(feature = "embedded-content")]
+        Box::new(Embedded),
+    ])
+});
+
+/// The kinds of content the game loads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContentKind {
+    /// [`Adventure`](crate::adventure::Adventure)s.
+    Adventure,
+    /// [`Background`](crate::background::Background)s.
+    Background,
+    /// Each [`Class`](crate::character::Class)'s abilities and progression, by class ID.
+    Class,
+    /// [`Creature`](crate::combat::Creature)s.
+    Creature,
+    /// [`Dialogue`](crate::dialogue::Dialogue)s.
+    Dialogue,
+    /// [`Location`](crate::location::Location)s.
+    Location,
+    /// [`Npc`](crate::dialogue::Npc)s.
+    Npc,
+    /// [`Quest`](crate::quest::Quest)s.
+    Quest,
+    /// [`Script`](crate::script::Script) sources.
+    Script,
+}
+impl ContentKind {
+    /// Every kind of content.
+    pub const ALL: [Self; 9] = [
+        Self::Adventure,
+        Self::Background,
+        Self::Class,
+        Self::Creature,
+        Self::Dialogue,
+        Self::Location,
+        Self::Npc,
+        Self::Quest,
+        Self::Script,
+    ];
+
+    /// The name of the folder this kind of content is kept in.
+    #[must_use]
+    pub const fn dir(self) -> &'static str {
+        match self {
+            Self::Adventure => "adventures",
+            Self::Background => "backgrounds",
+            Self::Class => "classes",
+            Self::Creature => "creatures",
+            Self::Dialogue => "dialogue",
+            Self::Location => "locations",
+            Self::Npc => "npcs",
+            Self::Quest => "quests",
+            Self::Script => "scripts",
+        }
+    }
+
+    /// The extension of this kind of content's files, without the leading `.`.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Script => "rhai",
+            _ => "json",
+        }
+    }
+}
+impl Display for ContentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.dir())
+    }
+}
+impl FromStr for ContentKind {
+    type Err = UnknownContentKind;
+
+    /// Parse a kind of content from the name of its folder, e.g. `locations`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.dir() == s)
+            .ok_or_else(|| UnknownContentKind(String::from(s)))
+    }
+}
+
+/// A name that isn't the folder of any [`ContentKind`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownContentKind(pub String);
+impl Display for UnknownContentKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown kind of content `{}`", self.0)
+    }
+}
+impl std::error::Error for UnknownContentKind {}
+
+/// Provide the text of a piece of content, to be used in place of any file with the same kind and
+/// ID.
+pub fn provide(kind: ContentKind, id: &str, text: impl Into<String>) {
+    PROVIDED
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert((kind, String::from(id)), text.into());
+}
+
+/// Look for content in the given source before any other, except content that was
+/// [provided](provide).
+pub fn add_source(source: impl ContentSource + 'static) {
+    SOURCES
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(0, Box::new(source));
+}
+
+/// The IDs of every piece of content of the given kind, in alphabetical order.
+///
+/// # Errors
+///
+/// This function returns an error if one of the [`ContentSource`]s can't be listed.
+pub fn list(kind: ContentKind) -> io::Result<Vec<String>> {
+    let mut ids: BTreeSet<String> = PROVIDED
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .keys()
+        .filter(|(provided, _)| *provided == kind)
+        .map(|(_, id)| id.clone())
+        .collect();
+    for source in SOURCES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+    {
+        ids.extend(source.list(kind)?);
+    }
+    Ok(ids.into_iter().collect())
+}
+
+/// Load the text of a piece of content.
+///
+/// # Errors
+///
+/// This function returns an error if the content wasn't provided and none of the
+/// [`ContentSource`]s have it, or one of them can't be read.
+pub fn load_text(kind: ContentKind, id: &str) -> io::Result<String> {
+    let provided = PROVIDED
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&(kind, String::from(id)))
+        .cloned();
+    if let Some(text) = provided {
+        return Ok(text);
+    }
+
+    for source in SOURCES
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+    {
+        if let Some(text) = source.load_text(kind, id)? {
+            return Ok(text);
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("there is no {kind} content called `{id}`"),
+    ))
+}
+
+/// Load a piece of JSON content into the given type.
+///
+/// # Errors
+///
+/// This function returns an error if the content can't be [loaded](load_text) or deserialized.
+#[cfg(feature = "serde")]
+pub fn load_json<D: DeserializeOwned>(kind: ContentKind, id: &str) -> io::Result<D> {
+    parse_json(&load_text(kind, id)?)
+}
+
+/// Deserialize the text of a piece of JSON content into the given type, e.g. text read straight
+/// from a [`ContentSource`].
+///
+/// # Errors
+///
+/// This function returns an error if the text can't be deserialized.
+#[cfg(feature = "serde")]
+pub fn parse_json<D: DeserializeOwned>(text: &str) -> io::Result<D> {
+    Ok(serde_json::from_str(text)?)
+}
+
+/// Somewhere the game's content can be found.
+pub trait ContentSource: Debug + Send + Sync {
+    /// The IDs of every piece of content of the given kind this source has, in any order.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the source can't be listed.
+    fn list(&self, kind: ContentKind) -> io::Result<Vec<String>>;
+
+    /// The text of a piece of content, or [`None`] if this source doesn't have it.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the source has the content but it can't be read.
+    fn load_text(&self, kind: ContentKind, id: &str) -> io::Result<Option<String>>;
+}
+
+/// A directory of content files, with a folder for each [`ContentKind`]. A directory or folder
+/// that doesn't exist is treated as empty.
+#[cfg(feature = "fs-content")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Directory {
+    path: Utf8PathBuf,
+}
+#[cfg(feature = "fs-content")]
+impl Directory {
+    /// A source of content in the directory at the given path.
+    pub fn new(path: impl Into<Utf8PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The path to the directory.
+    #[must_use]
+    pub fn path(&self) -> &Utf8Path {
+        &self.path
+    }
+}
+#[cfg(feature = "fs-content")]
+impl ContentSource for Directory {
+    fn list(&self, kind: ContentKind) -> io::Result<Vec<String>> {
+        match try_list_files(self.path.join(kind.dir()), kind.extension()) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            listed => listed,
+        }
+    }
+
+    fn load_text(&self, kind: ContentKind, id: &str) -> io::Result<Option<String>> {
+        let mut path = self.path.join(kind.dir());
+        path.push(format!("{id}.{}", kind.extension()));
+        match try_load_text(path) {
+            Ok(text) => Ok(Some(text)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Pairs every embedded file with its kind and ID.
+#[cfg(feature = "embedded-content")]
+macro_rules! embedded {
+    ($(($kind:ident, $id:literal)),* $(,)?) => {
+        &[$((
+            ContentKind::$kind,
+            $id,
+            include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/testfiles/",
+                embedded!(@dir $kind),
+                "/",
+                $id,
+                embedded!(@ext $kind),
+            )),
+        )),*]
+    };
+    (@dir Adventure) => { "adventures" };
+    (@dir Background) => { "backgrounds" };
+    (@dir Class) => { "classes" };
+    (@dir Creature) => { "creatures" };
+    (@dir Dialogue) => { "dialogue" };
+    (@dir Location) => { "locations" };
+    (@dir Npc) => { "npcs" };
+    (@dir Quest) => { "quests" };
+    (@dir Script) => { "scripts" };
+    (@ext Script) => { ".rhai" };
+    (@ext $kind:ident) => { ".json" };
+}
+
+/// The default adventure's content, by kind and ID.
+#[cfg(feature = "embedded-content")]
+const EMBEDDED: &[(ContentKind, &str, &str)] = embedded![
+    (Adventure, "pointbuy"),
+    (Adventure, "rolled"),
+    (Adventure, "testadventure"),
+    (Background, "bog_smuggler"),
+    (Background, "exiled_hive_scribe"),
+    (Background, "mire_hermit"),
+    (Class, "bastion"),
+    (Class, "hunter"),
+    (Class, "soothsayer"),
+    (Class, "trespasser"),
+    (Class, "warden"),
+    (Creature, "bog_lurker"),
+    (Creature, "swamp_rat"),
+    (Dialogue, "old_mossback"),
+    (Location, "testcorridor"),
+    (Location, "testplace"),
+    (Npc, "old_mossback"),
+    (Quest, "mossbacks_secret"),
+    (Script, "testplace_choice"),
+    (Script, "testplace_enter"),
+];
+
+/// The default adventure's content, built into the binary.
+#[cfg(feature = "embedded-content")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Embedded;
+#[cfg(feature = "embedded-content")]
+impl ContentSource for Embedded {
+    fn list(&self, kind: ContentKind) -> io::Result<Vec<String>> {
+        Ok(EMBEDDED
+            .iter()
+            .filter(|(embedded, _, _)| *embedded == kind)
+            .map(|(_, id, _)| String::from(*id))
+            .collect())
+    }
+
+    fn load_text(&self, kind: ContentKind, id: &str) -> io::Result<Option<String>> {
+        Ok(EMBEDDED
+            .iter()
+            .find(|(embedded, embedded_id, _)| *embedded == kind && *embedded_id == id)
+            .map(|(_, _, text)| String::from(*text)))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::location::Location;
+
+    #[test]
+    fn provided_content_comes_first() {
+        let location = r#"{
+            "id": "providedplace",
+            "name": "Provided Place",
+            "text": { "intro": ["Nobody wrote this down."] }
+        }"#;
+        provide(ContentKind::Location, "providedplace", location);
+
+        let loaded: Location = load_json(ContentKind::Location, "providedplace").unwrap();
+        assert_eq!(loaded.name(), "Provided Place");
+        assert!(
+            list(ContentKind::Location)
+                .unwrap()
+                .contains(&String::from("providedplace"))
+        );
+        assert!(load_text(ContentKind::Location, "nowhere").is_err());
+
+        assert_eq!(
+            "scripts".parse::<ContentKind>().unwrap(),
+            ContentKind::Script
+        );
+        assert!("maps".parse::<ContentKind>().is_err());
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn missing_directories_are_empty() {
+        let nowhere = Directory::new("testfiles/nowhere");
+        assert!(nowhere.list(ContentKind::Location).unwrap().is_empty());
+        assert_eq!(
+            nowhere
+                .load_text(ContentKind::Location, "testplace")
+                .unwrap(),
+            None
+        );
+        assert!(
+            Directory::new("testfiles")
+                .load_text(ContentKind::Location, "testplace")
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[cfg(all(feature = "fs-content", feature = "embedded-content"))]
+    #[test]
+    fn all_content_is_embedded() {
+        let files = Directory::new(CONTENT_DIR.clone());
+        for kind in ContentKind::ALL {
+            let mut embedded = Embedded.list(kind).unwrap();
+            embedded.sort();
+            assert_eq!(embedded, files.list(kind).unwrap(), "{kind}");
+            for id in embedded {
+                assert_eq!(
+                    Embedded.load_text(kind, &id).unwrap(),
+                    files.load_text(kind, &id).unwrap()
+                );
+            }
+        }
+    }
+}
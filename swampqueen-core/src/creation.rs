@@ -0,0 +1,285 @@
+//! The ways a [`Character`](crate::character::Character)'s starting stats can be chosen. Each
+//! [`Adventure`](crate::adventure::Adventure) picks one [`CreationMode`]; the core validates the
+//! player's choices for that mode, while frontends are only responsible for presenting them.
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ability::ScaledRoll,
+    character::{Stat, StatValues},
+};
+
+/// The number of times a set of rolled stats is silently rerolled for falling below the minimum
+/// total, before the last set is accepted anyway.
+const MAX_AUTOMATIC_REROLLS: usize = 100;
+
+/// How a character's starting stats are chosen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "mode"))]
+pub enum CreationMode {
+    /// Stats come from the chosen [`Class`](crate::character::Class) and
+    /// [`Race`](crate::character::Race), plus two bonus stats raised by one each.
+    #[default]
+    Standard,
+    /// The player spends a budget of points on their stats, one point per stat point, in place of
+    /// their class's stats. Their race's bonus is added on top.
+    PointBuy {
+        /// The number of points available to spend.
+        budget: isize,
+        /// The highest value any single stat may be bought up to.
+        max_per_stat: isize,
+    },
+    /// Every stat is rolled in place of the character's class's stats. Their race's bonus is added
+    /// on top.
+    Rolled {
+        /// The roll made for each stat, e.g. `"4d6kh3"`.
+        roll: ScaledRoll,
+        /// How many times the player may choose to reroll the whole set.
+        #[cfg_attr(feature = "serde", serde(default))]
+        rerolls: usize,
+        /// Sets whose stats add up to less than this are rerolled automatically.
+        #[cfg_attr(feature = "serde", serde(default))]
+        min_total: isize,
+    },
+}
+impl CreationMode {
+    /// Check that the given purchased stats are valid for this [`CreationMode::PointBuy`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`CreationError`] if this is not a point-buy mode, if any stat is
+    /// negative or above the cap, or if the total cost exceeds the budget.
+    pub fn validate_point_buy(&self, purchased: &StatValues) -> Result<(), CreationError> {
+        let Self::PointBuy {
+            budget,
+            max_per_stat,
+        } = *self
+        else {
+            return Err(CreationError::WrongMode);
+        };
+
+        for stat in Stat::ALL {
+            let value = purchased.get(stat);
+            if !(0..=max_per_stat).contains(&value) {
+                return Err(CreationError::StatOutOfRange {
+                    stat,
+                    value,
+                    max: max_per_stat,
+                });
+            }
+        }
+        let spent = purchased.total();
+        if spent > budget {
+            return Err(CreationError::OverBudget { spent, budget });
+        }
+        Ok(())
+    }
+
+    /// Roll a fresh set of stats for this [`CreationMode::Rolled`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`CreationError::WrongMode`] if this is not a rolled mode.
+    pub fn roll_stats(&self) -> Result<RolledStats, CreationError> {
+        let Self::Rolled {
+            roll,
+            rerolls,
+            min_total,
+        } = *self
+        else {
+            return Err(CreationError::WrongMode);
+        };
+
+        Ok(RolledStats {
+            stats: roll_stat_set(roll, min_total),
+            roll,
+            min_total,
+            rerolls_left: rerolls,
+        })
+    }
+}
+
+/// Roll every stat, rerolling the set while it falls below `min_total`.
+fn roll_stat_set(roll: ScaledRoll, min_total: isize) -> StatValues {
+    let mut stats = StatValues::default();
+    for _ in 0..=MAX_AUTOMATIC_REROLLS {
+        for stat in Stat::ALL {
+            stats.set(stat, roll.roll(&StatValues::default()).1);
+        }
+        if stats.total() >= min_total {
+            break;
+        }
+    }
+    stats
+}
+
+/// Extra restrictions an adventure places on character creation.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CreationRules {
+    /// Whether the two bonus stats of [`CreationMode::Standard`] must be different stats.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub distinct_bonus_stats: bool,
+}
+
+/// A set of stats rolled by [`CreationMode::roll_stats`]. These can only be created by rolling, so
+/// a frontend cannot hand the core a set of stats that was never rolled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolledStats {
+    stats: StatValues,
+    roll: ScaledRoll,
+    min_total: isize,
+    rerolls_left: usize,
+}
+impl RolledStats {
+    /// The rolled stats, before any racial bonus is applied.
+    #[must_use]
+    pub const fn stats(&self) -> &StatValues {
+        &self.stats
+    }
+
+    /// The number of times the player may still reroll the set.
+    #[must_use]
+    pub const fn rerolls_left(&self) -> usize {
+        self.rerolls_left
+    }
+
+    /// Throw away the current set and roll a new one. Returns `false` (keeping the current set) if
+    /// there are no rerolls left.
+    pub fn reroll(&mut self) -> bool {
+        if self.rerolls_left == 0 {
+            return false;
+        }
+        self.rerolls_left -= 1;
+        self.stats = roll_stat_set(self.roll, self.min_total);
+        true
+    }
+}
+
+/// The reasons a character cannot be created as requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CreationError {
+    /// The requested kind of creation is not the one the adventure uses.
+    WrongMode,
+    /// More points were spent than the budget allows.
+    OverBudget {
+        /// The points spent.
+        spent: isize,
+        /// The points available.
+        budget: isize,
+    },
+    /// A stat was bought below zero or above the cap.
+    StatOutOfRange {
+        /// The offending stat.
+        stat: Stat,
+        /// The value it was bought up to.
+        value: isize,
+        /// The highest value allowed.
+        max: isize,
+    },
+}
+impl Display for CreationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::WrongMode => write!(f, "this adventure uses a different creation mode"),
+            Self::OverBudget { spent, budget } => {
+                write!(f, "spent {spent} points, but only {budget} are available")
+            }
+            Self::StatOutOfRange { stat, value, max } => {
+                write!(f, "{stat} must be between 0 and {max}, not {value}")
+            }
+        }
+    }
+}
+impl std::error::Error for CreationError {}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const POINT_BUY: CreationMode = CreationMode::PointBuy {
+        budget: 12,
+        max_per_stat: 5,
+    };
+
+    fn stats(values: [isize; 5]) -> StatValues {
+        let mut stats = StatValues::default();
+        for (stat, value) in Stat::ALL.into_iter().zip(values) {
+            stats.set(stat, value);
+        }
+        stats
+    }
+
+    #[test]
+    fn valid_point_buy() {
+        POINT_BUY
+            .validate_point_buy(&stats([5, 5, 2, 0, 0]))
+            .unwrap();
+        POINT_BUY
+            .validate_point_buy(&stats([1, 1, 1, 1, 1]))
+            .unwrap();
+    }
+
+    #[test]
+    fn invalid_point_buy() {
+        assert_eq!(
+            POINT_BUY.validate_point_buy(&stats([5, 5, 3, 0, 0])),
+            Err(CreationError::OverBudget {
+                spent: 13,
+                budget: 12
+            })
+        );
+        assert_eq!(
+            POINT_BUY.validate_point_buy(&stats([6, 0, 0, 0, 0])),
+            Err(CreationError::StatOutOfRange {
+                stat: Stat::ALL[0],
+                value: 6,
+                max: 5
+            })
+        );
+        assert!(matches!(
+            POINT_BUY.validate_point_buy(&stats([0, -1, 0, 0, 0])),
+            Err(CreationError::StatOutOfRange { value: -1, .. })
+        ));
+        assert_eq!(
+            CreationMode::Standard.validate_point_buy(&StatValues::default()),
+            Err(CreationError::WrongMode)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rolled_stats() {
+        let mode: CreationMode =
+            serde_json::from_str(r#"{"mode": "Rolled", "roll": "4d3kh2 - 2", "rerolls": 1}"#)
+                .unwrap();
+        let mut rolled = mode.roll_stats().unwrap();
+        for stat in Stat::ALL {
+            assert!((0..=4).contains(&rolled.stats().get(stat)));
+        }
+        assert!(rolled.reroll());
+        assert!(!rolled.reroll());
+        assert_eq!(rolled.rerolls_left(), 0);
+        assert_eq!(
+            CreationMode::Standard.roll_stats(),
+            Err(CreationError::WrongMode)
+        );
+    }
+
+    #[test]
+    fn rolled_stats_respect_min_total() {
+        let mode = CreationMode::Rolled {
+            roll: "1d2".parse().unwrap(),
+            rerolls: 0,
+            min_total: 8,
+        };
+        for _ in 0..20 {
+            assert!(mode.roll_stats().unwrap().stats().total() >= 8);
+        }
+    }
+}
@@ -0,0 +1,499 @@
+//! NPCs and the dialogue trees used to talk to them. A [`Dialogue`] is a set of [`DialogueNode`]s,
+//! each with text and the [`Response`]s the player may give. Responses can be gated on
+//! a [`Condition`], trigger [`Effect`]s and call for skill checks.
+
+use std::{collections::HashMap, fmt::Display};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::content::{ContentKind, load_json};
+use crate::{
+    character::Race,
+    check::CheckResult,
+    condition::{Condition, ConditionContext},
+    game::GameState,
+    party::Party,
+    skill::Skill,
+};
+
+/// A non-player character the party can talk to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Npc {
+    id: String,
+    name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    race: Option<Race>,
+    dialogue: String,
+}
+impl Npc {
+    /// Load an [`Npc`] from the game's [content](crate::content).
+    ///
+    /// # Arguments
+    ///
+    /// * `npc_id` - A [`std::str`] corresponding to the ID of the desired NPC. In a content
+    ///   directory, this is the name of the NPC's `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load(npc_id: &str) -> std::io::Result<Self> {
+        load_json(ContentKind::Npc, npc_id)
+    }
+
+    /// The ID of this NPC.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The name shown to the player.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The NPC's race, if they are one of the playable races.
+    #[must_use]
+    pub const fn race(&self) -> Option<Race> {
+        self.race
+    }
+
+    /// The ID of the [`Dialogue`] used when talking to this NPC.
+    #[must_use]
+    pub fn dialogue_id(&self) -> &str {
+        &self.dialogue
+    }
+}
+
+/// A dialogue tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Dialogue {
+    id: String,
+    start: String,
+    nodes: HashMap<String, DialogueNode>,
+}
+impl Dialogue {
+    /// Load a [`Dialogue`] from the game's [content](crate::content).
+    ///
+    /// # Arguments
+    ///
+    /// * `dialogue_id` - A [`std::str`] corresponding to the ID of the desired dialogue. In a
+    ///   content directory, this is the name of the dialogue's `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load(dialogue_id: &str) -> std::io::Result<Self> {
+        load_json(ContentKind::Dialogue, dialogue_id)
+    }
+
+    /// The ID of this dialogue.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The node with the given ID.
+    #[must_use]
+    pub fn node(&self, node_id: &str) -> Option<&DialogueNode> {
+        self.nodes.get(node_id)
+    }
+}
+
+/// A single step of a [`Dialogue`]: what the NPC says, and how the player may respond.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DialogueNode {
+    text: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    responses: Vec<Response>,
+}
+impl DialogueNode {
+    /// The paragraphs of text spoken at this node.
+    #[must_use]
+    pub fn text(&self) -> &[String] {
+        &self.text
+    }
+
+    /// Every response, whether or not its condition holds.
+    #[must_use]
+    pub fn responses(&self) -> &[Response] {
+        &self.responses
+    }
+}
+
+/// Something the player can say.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Response {
+    text: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    condition: Condition,
+    #[cfg_attr(feature = "serde", serde(default))]
+    effects: Vec<Effect>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    check: Option<DialogueCheck>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    next: Option<String>,
+}
+impl Response {
+    /// The text shown to the player.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether this response's condition holds. `npc.disposition` is that of the given NPC.
+    #[must_use]
+    pub fn is_available(&self, npc: &Npc, party: &Party, state: &GameState) -> bool {
+        self.condition
+            .evaluate(&ConditionContext::new(party, state).with_npc(npc.id()))
+    }
+}
+impl From<&Response> for String {
+    fn from(value: &Response) -> Self {
+        value.text.clone()
+    }
+}
+
+/// A skill check made when choosing a [`Response`], which decides the next node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DialogueCheck {
+    /// The skill checked by the party's leader.
+    pub skill: Skill,
+    /// The total the check must meet or beat.
+    pub difficulty: isize,
+    /// The node moved to if the check passes, or [`None`] to end the conversation.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pass: Option<String>,
+    /// The node moved to if the check fails, or [`None`] to end the conversation.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fail: Option<String>,
+}
+
+/// Something that happens when a [`Response`] is chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum Effect {
+    /// Set a story flag.
+    SetFlag {
+        /// The flag.
+        flag: String,
+    },
+    /// Clear a story flag.
+    ClearFlag {
+        /// The flag.
+        flag: String,
+    },
+    /// Give the party an item.
+    GiveItem {
+        /// The item's ID.
+        item: String,
+        /// How many to give.
+        #[cfg_attr(feature = "serde", serde(default = "one"))]
+        count: usize,
+    },
+    /// Take an item from the party, if they have it.
+    TakeItem {
+        /// The item's ID.
+        item: String,
+        /// How many to take.
+        #[cfg_attr(feature = "serde", serde(default = "one"))]
+        count: usize,
+    },
+    /// Change how the NPC feels about the party.
+    ChangeDisposition {
+        /// The amount to change it by.
+        amount: isize,
+    },
+    /// Start a fight. The frontend is responsible for running the
+    /// [`Combat`](crate::combat::Combat).
+    StartCombat {
+        /// The IDs of the creatures to fight.
+        creatures: Vec<String>,
+    },
+}
+impl Effect {
+    /// Apply this effect. Returns the creatures to fight if this starts combat.
+    fn apply(&self, npc: &Npc, party: &mut Party, state: &mut GameState) -> Option<Vec<String>> {
+        match self {
+            Self::SetFlag { flag } => state.set_flag(flag),
+            Self::ClearFlag { flag } => state.clear_flag(flag),
+            Self::GiveItem { item, count } => party.inventory_mut().add(item, *count),
+            Self::TakeItem { item, count } => {
+                party.inventory_mut().remove(item, *count);
+            }
+            Self::ChangeDisposition { amount } => state.change_disposition(npc.id(), *amount),
+            Self::StartCombat { creatures } => return Some(creatures.clone()),
+        }
+        None
+    }
+}
+
+#[cfg(feature = "serde")]
+const fn one() -> usize {
+    1
+}
+
+/// What happened when a [`Response`] was chosen.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ResponseOutcome {
+    /// The skill check made, if the response called for one.
+    pub check: Option<CheckResult>,
+    /// The IDs of the creatures to fight, if the response started combat.
+    pub combat: Option<Vec<String>>,
+}
+
+/// The reasons a [`Response`] cannot be chosen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DialogueError {
+    /// The conversation is over.
+    Ended,
+    /// There is no response with the given index at the current node.
+    NoSuchResponse(usize),
+    /// The response's condition doesn't hold.
+    Unavailable,
+}
+impl Display for DialogueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Ended => write!(f, "the conversation is over"),
+            Self::NoSuchResponse(index) => write!(f, "there is no response {index}"),
+            Self::Unavailable => write!(f, "that response isn't available"),
+        }
+    }
+}
+impl std::error::Error for DialogueError {}
+
+/// A conversation in progress with an [`Npc`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Conversation {
+    npc: Npc,
+    dialogue: Dialogue,
+    current: Option<String>,
+}
+impl Conversation {
+    /// Start talking to an NPC, loading their [`Dialogue`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the NPC's dialogue cannot be loaded.
+    #[cfg(feature = "serde")]
+    pub fn try_start(npc: Npc) -> std::io::Result<Self> {
+        let dialogue = Dialogue::try_load(npc.dialogue_id())?;
+        Ok(Self::new(npc, dialogue))
+    }
+
+    /// Start talking to an NPC with the given dialogue.
+    #[must_use]
+    pub fn new(npc: Npc, dialogue: Dialogue) -> Self {
+        let current = Some(dialogue.start.clone());
+        Self {
+            npc,
+            dialogue,
+            current,
+        }
+    }
+
+    /// The NPC being talked to.
+    #[must_use]
+    pub const fn npc(&self) -> &Npc {
+        &self.npc
+    }
+
+    /// The current node, or [`None`] if the conversation is over.
+    #[must_use]
+    pub fn node(&self) -> Option<&DialogueNode> {
+        self.current
+            .as_deref()
+            .and_then(|node_id| self.dialogue.node(node_id))
+    }
+
+    /// The responses available at the current node, along with their indices.
+    pub fn responses<'a>(
+        &'a self,
+        party: &'a Party,
+        state: &'a GameState,
+    ) -> impl Iterator<Item = (usize, &'a Response)> {
+        self.node()
+            .into_iter()
+            .flat_map(|node| node.responses.iter().enumerate())
+            .filter(|(_, response)| response.is_available(&self.npc, party, state))
+    }
+
+    /// Choose the response at `index` of the current node, applying its effects, making its
+    /// check and moving to the next node.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`DialogueError`] if the conversation is over, there is no such
+    /// response or its condition doesn't hold.
+    pub fn respond(
+        &mut self,
+        index: usize,
+        party: &mut Party,
+        state: &mut GameState,
+    ) -> Result<ResponseOutcome, DialogueError> {
+        let node = self.node().ok_or(DialogueError::Ended)?;
+        let response = node
+            .responses
+            .get(index)
+            .ok_or(DialogueError::NoSuchResponse(index))?
+            .clone();
+        if !response.is_available(&self.npc, party, state) {
+            return Err(DialogueError::Unavailable);
+        }
+
+        let mut outcome = ResponseOutcome::default();
+        for effect in &response.effects {
+            if let Some(creatures) = effect.apply(&self.npc, party, state) {
+                outcome.combat = Some(creatures);
+            }
+        }
+        self.current = match &response.check {
+            Some(check) => {
+                let result = party.leader().skill_check(check.skill, check.difficulty);
+                let next = if result.passed() {
+                    check.pass.clone()
+                } else {
+                    check.fail.clone()
+                };
+                outcome.check = Some(result);
+                next
+            }
+            None => response.next,
+        };
+        Ok(outcome)
+    }
+
+    /// Whether the conversation is over.
+    #[must_use]
+    pub fn is_over(&self) -> bool {
+        self.node().is_none()
+    }
+}
+
+#[cfg(all(test, feature = "fs-content"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::character::{Character, Class, Stat};
+
+    fn party(race: Race) -> Party {
+        Party::new(Character::new(
+            "Talker",
+            Class::Warden,
+            race,
+            Stat::Cunning,
+            Stat::Cunning,
+        ))
+    }
+
+    fn response_texts(
+        conversation: &Conversation,
+        party: &Party,
+        state: &GameState,
+    ) -> Vec<String> {
+        conversation
+            .responses(party, state)
+            .map(|(_, response)| String::from(response))
+            .collect()
+    }
+
+    #[test]
+    fn load_npc_and_dialogue() {
+        let npc = Npc::try_load("old_mossback").unwrap();
+        assert_eq!(npc.name(), "Old Mossback");
+        assert_eq!(npc.race(), Some(Race::AlligatorFolk));
+        let conversation = Conversation::try_start(npc).unwrap();
+        assert!(!conversation.is_over());
+        assert!(!conversation.node().unwrap().text().is_empty());
+    }
+
+    #[test]
+    fn responses_depend_on_conditions() {
+        let npc = Npc::try_load("old_mossback").unwrap();
+        let conversation = Conversation::try_start(npc).unwrap();
+        let mut state = GameState::default();
+
+        let goblin = party(Race::GoblinoidFae);
+        let gator = party(Race::AlligatorFolk);
+        let goblin_responses = response_texts(&conversation, &goblin, &state);
+        let gator_responses = response_texts(&conversation, &gator, &state);
+        assert_eq!(gator_responses.len(), goblin_responses.len() + 1);
+
+        state.set_flag("mossback_angered");
+        assert!(response_texts(&conversation, &goblin, &state).len() < goblin_responses.len());
+    }
+
+    #[test]
+    fn responses_apply_effects() {
+        let npc = Npc::try_load("old_mossback").unwrap();
+        let mut conversation = Conversation::try_start(npc).unwrap();
+        let mut party = party(Race::GoblinoidFae);
+        let mut state = GameState::default();
+
+        // "Ask about the bog"
+        let outcome = conversation.respond(0, &mut party, &mut state).unwrap();
+        assert_eq!(outcome, ResponseOutcome::default());
+        assert!(state.has_flag("asked_about_bog"));
+        assert_eq!(state.disposition("old_mossback"), 1);
+        // "Take the charm"
+        conversation.respond(0, &mut party, &mut state).unwrap();
+        assert!(party.inventory().contains("frog_charm"));
+        assert!(conversation.is_over());
+        assert_eq!(
+            conversation.respond(0, &mut party, &mut state),
+            Err(DialogueError::Ended)
+        );
+    }
+
+    #[test]
+    fn responses_can_start_combat_and_checks() {
+        let npc = Npc::try_load("old_mossback").unwrap();
+        let mut party = party(Race::GoblinoidFae);
+        let mut state = GameState::default();
+
+        let mut conversation = Conversation::try_start(npc.clone()).unwrap();
+        let (insult, _) = conversation
+            .responses(&party, &state)
+            .find(|(_, response)| response.text().starts_with("Insult"))
+            .unwrap();
+        let outcome = conversation
+            .respond(insult, &mut party, &mut state)
+            .unwrap();
+        assert_eq!(outcome.combat, Some(vec![String::from("bog_lurker")]));
+        assert!(conversation.is_over());
+
+        // Persuasion is no longer offered once he's angry
+        let mut state = GameState::default();
+        let mut conversation = Conversation::try_start(npc).unwrap();
+        let (persuade, _) = conversation
+            .responses(&party, &state)
+            .find(|(_, response)| response.text().starts_with("Persuade"))
+            .unwrap();
+        let outcome = conversation
+            .respond(persuade, &mut party, &mut state)
+            .unwrap();
+        let check = outcome.check.unwrap();
+        assert_eq!(check.difficulty(), 14);
+        assert_eq!(conversation.is_over(), !check.passed());
+
+        assert_eq!(
+            Conversation::try_start(Npc::try_load("old_mossback").unwrap())
+                .unwrap()
+                .respond(99, &mut party, &mut state),
+            Err(DialogueError::NoSuchResponse(99))
+        );
+    }
+}
@@ -1,23 +1,81 @@
 //! Functionality related to dice-rolling.
 
-use std::{fmt::Display, sync::OnceLock};
+use std::{cell::RefCell, fmt::Display, sync::OnceLock};
 
-use color_eyre::eyre::{self, eyre};
-use rand;
+use rand::{self, Rng, SeedableRng, rngs::StdRng};
 use regex::Regex;
 
 static ROLL_REG: OnceLock<Regex> = OnceLock::new();
 
+/// The most dice a single roll may have, so that a typo can't take all the memory in the world.
+pub const MAX_DICE: usize = 1000;
+
+/// The most faces a die may have.
+pub const MAX_FACES: usize = 1000;
+
+thread_local! {
+    /// The generator set by [`seed_dice`], if any.
+    static SEEDED_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
 fn get_roll_reg() -> &'static Regex {
     #[allow(clippy::unwrap_used)]
-    ROLL_REG.get_or_init(|| Regex::new(r"^(\d+)d(\d+)$").unwrap())
+    ROLL_REG.get_or_init(|| Regex::new(r"^(\d+)d(\d+)(?:kh(\d+))?$").unwrap())
 }
 
-/// A request to roll a given number of dice, each with a given number of sides.
+/// A request to roll a given number of dice, each with a given number of sides, optionally only
+/// keeping the highest few.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub struct RollManyRequest {
     num_dice: usize,
     num_faces: usize,
+    keep_highest: Option<usize>,
+}
+impl RollManyRequest {
+    /// Create a request to roll `num_dice` dice, each with `num_faces` faces.
+    #[must_use]
+    pub const fn new(num_dice: usize, num_faces: usize) -> Self {
+        Self {
+            num_dice,
+            num_faces,
+            keep_highest: None,
+        }
+    }
+
+    /// Only count the highest `keep` dice towards the total.
+    #[must_use]
+    pub const fn keep_highest(mut self, keep: usize) -> Self {
+        self.keep_highest = Some(keep);
+        self
+    }
+
+    /// The number of dice rolled.
+    #[must_use]
+    pub const fn num_dice(&self) -> usize {
+        self.num_dice
+    }
+
+    /// The number of faces on each die.
+    #[must_use]
+    pub const fn num_faces(&self) -> usize {
+        self.num_faces
+    }
+
+    /// The number of dice counted towards the total.
+    #[must_use]
+    pub fn num_kept(&self) -> usize {
+        self.keep_highest
+            .map_or(self.num_dice, |keep| keep.min(self.num_dice))
+    }
+
+    /// Roll the dice described by this request.
+    #[must_use]
+    pub fn roll(self) -> RolledDiceResults {
+        RolledDiceResults {
+            request: self,
+            results: roll_many(self),
+        }
+    }
 }
 
 /// A set of results from a group of rolled dice stemming from a [`RollManyRequest`].
@@ -26,6 +84,40 @@ pub struct RolledDiceResults {
     request: RollManyRequest,
     results: Vec<usize>,
 }
+impl Display for RollManyRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}d{}", self.num_dice, self.num_faces)?;
+        if let Some(keep) = self.keep_highest {
+            write!(f, "kh{keep}")?;
+        }
+        Ok(())
+    }
+}
+impl RolledDiceResults {
+    /// The sum of all the kept dice.
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.results
+            .iter()
+            .zip(self.kept())
+            .filter(|(_, kept)| *kept)
+            .map(|(result, _)| result)
+            .sum()
+    }
+
+    /// Whether each rolled die counts towards the total, in the order they were rolled.
+    #[must_use]
+    pub fn kept(&self) -> Vec<bool> {
+        let mut by_value: Vec<usize> = (0..self.results.len()).collect();
+        by_value.sort_by_key(|&i| std::cmp::Reverse(self.results[i]));
+
+        let mut kept = vec![false; self.results.len()];
+        for &i in by_value.iter().take(self.request.num_kept()) {
+            kept[i] = true;
+        }
+        kept
+    }
+}
 impl Display for RolledDiceResults {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self.results.len() {
@@ -36,24 +128,35 @@ impl Display for RolledDiceResults {
                 write!(f, "{}", self.results[0])
             }
             _ => {
-                let results_str = self.results.iter().fold(String::new(), |acc, &num| {
-                    if acc.is_empty() {
-                        num.to_string()
-                    } else {
-                        acc + "+" + &num.to_string()
-                    }
-                });
-                let results_sum: usize = self.results.iter().sum();
-                write!(f, "{results_str}={results_sum}")
+                // Dropped dice are shown in brackets
+                let results_str = self.results.iter().zip(self.kept()).fold(
+                    String::new(),
+                    |acc, (&num, kept)| {
+                        let num_str = if kept {
+                            num.to_string()
+                        } else {
+                            format!("[{num}]")
+                        };
+                        if acc.is_empty() {
+                            num_str
+                        } else {
+                            acc + "+" + num_str.as_str()
+                        }
+                    },
+                );
+                write!(f, "{results_str}={}", self.total())
             }
         }
     }
 }
 
-/// Take a string of format `XdY`, where
+/// Take a string of format `XdY` or `XdYkhZ`, where
 ///
 /// - `X` is the number of dice being rolled.
 /// - `Y` is the number of faces the dice have.
+/// - `Z`, if given, is the number of highest dice to keep.
+///
+/// `X` can be at most [`MAX_DICE`], `Y` must be from 1 to [`MAX_FACES`] and `Z` can't be 0.
 ///
 /// Returns [`RolledDiceResults`], representing the numerical values of all the rolled dice and the
 /// parsed request.
@@ -66,44 +169,97 @@ impl Display for RolledDiceResults {
 /// let results = roll_many_from_str("3d6");
 /// // Roll a d20
 /// let d20_result = roll_many_from_str("1d20");
+/// // Roll four six-sided dice, keeping the highest three
+/// let stat_result = roll_many_from_str("4d6kh3");
 /// ```
 ///
 /// # Errors
 ///
-/// This function returns [`Err`] if the `input_str` arg is the incorrect format.
-pub fn roll_many_from_str(input_str: &str) -> eyre::Result<RolledDiceResults> {
+/// This function returns [`DiceError::Invalid`] if the `input_str` arg is the incorrect format or
+/// out of bounds.
+pub fn roll_many_from_str(input_str: &str) -> Result<RolledDiceResults, DiceError> {
     let request = parse_roll_many_str(input_str)?;
-    let results = roll_many(request);
-    Ok(RolledDiceResults { request, results })
+    Ok(request.roll())
 }
 
 /// Take a string and, if it's the correct format, return a [`RollManyRequest`].
-fn parse_roll_many_str(input_str: &str) -> eyre::Result<RollManyRequest> {
-    let captures = get_roll_reg()
-        .captures(input_str)
-        .ok_or_else(|| eyre!("invalid input"))?;
+pub(crate) fn parse_roll_many_str(input_str: &str) -> Result<RollManyRequest, DiceError> {
+    let invalid = || DiceError::Invalid(String::from(input_str));
+    let captures = get_roll_reg().captures(input_str).ok_or_else(invalid)?;
 
-    let num_dice = captures[1].parse()?;
-    let num_faces = captures[2].parse()?;
+    let num_dice: usize = captures[1].parse().map_err(|_| invalid())?;
+    let num_faces: usize = captures[2].parse().map_err(|_| invalid())?;
+    let keep_highest: Option<usize> = captures
+        .get(3)
+        .map(|keep| keep.as_str().parse())
+        .transpose()
+        .map_err(|_| invalid())?;
+    if num_dice > MAX_DICE || !(1..=MAX_FACES).contains(&num_faces) || keep_highest == Some(0) {
+        return Err(invalid());
+    }
 
     Ok(RollManyRequest {
         num_dice,
         num_faces,
+        keep_highest,
     })
 }
 
+/// The reasons a roll can't be parsed from its notation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiceError {
+    /// The dice aren't written like `3d6` or `4d6kh3`.
+    Invalid(String),
+    /// Nothing was written.
+    Empty,
+    /// Two terms aren't separated by `+` or `-`.
+    MissingOperator(String),
+    /// The roll ends with `+` or `-`.
+    TrailingOperator(String),
+    /// A term is neither a number nor a [`Stat`](crate::character::Stat).
+    UnknownStat(String),
+    /// More than one stat is added, or a stat is subtracted.
+    TooManyStats,
+}
+impl Display for DiceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(dice) => write!(f, "`{dice}` isn't dice notation like 3d6"),
+            Self::Empty => write!(f, "empty roll"),
+            Self::MissingOperator(term) => write!(f, "missing operator before `{term}`"),
+            Self::TrailingOperator(roll) => write!(f, "trailing operator in `{roll}`"),
+            Self::UnknownStat(stat) => write!(f, "unknown stat `{stat}`"),
+            Self::TooManyStats => write!(f, "a roll can only add a single stat"),
+        }
+    }
+}
+impl std::error::Error for DiceError {}
+
 /// The function `roll_die` takes in a single `usize` as an argument.
 /// That `usize` represents how many faces the dice has.
 /// It returns a single `usize` that represents the number that was rolled.
+/// A die with no faces always rolls a `0`.
 #[must_use]
 pub fn roll_die(num_faces: usize) -> usize {
+    if num_faces == 0 {
+        return 0;
+    }
     // Set the "result" variable (which has data type "usize") to a random number from 1 to "num_faces"
     // (inclusive)
-    let result: usize = rand::random_range(1..=num_faces);
+    let result: usize = SEEDED_RNG.with_borrow_mut(|rng| match rng {
+        Some(rng) => rng.random_range(1..=num_faces),
+        None => rand::random_range(1..=num_faces),
+    });
     // Return the random number you rolled!
     result
 }
 
+/// Make every roll on the current thread follow from the given seed, so that the same seed
+/// always gives the same rolls. Pass [`None`] to go back to unpredictable rolls.
+pub fn seed_dice(seed: Option<u64>) {
+    SEEDED_RNG.set(seed.map(StdRng::seed_from_u64));
+}
+
 /// Roll a given number of dice with the given number of faces, both corresponding to the provided
 /// [`RollManyRequest`].
 #[must_use]
@@ -124,6 +280,16 @@ mod tests {
         assert!((1..=num_faces).contains(&result));
     }
 
+    #[test]
+    fn seeded_rolls_repeat() {
+        seed_dice(Some(42));
+        let first: Vec<usize> = (0..20).map(|_| roll_die(20)).collect();
+        seed_dice(Some(42));
+        let second: Vec<usize> = (0..20).map(|_| roll_die(20)).collect();
+        seed_dice(None);
+        assert_eq!(first, second);
+    }
+
     #[test]
     fn roll_1_d0() {
         assert_eq!(roll_die(0), 0);
@@ -150,17 +316,45 @@ mod tests {
         let assert_valid = |in_str: &str, exp_ndice: usize, exp_nfaces: usize| {
             assert_eq!(
                 parse_roll_many_str(in_str).unwrap(),
-                RollManyRequest {
-                    num_dice: exp_ndice,
-                    num_faces: exp_nfaces,
-                }
+                RollManyRequest::new(exp_ndice, exp_nfaces)
             );
         };
 
         assert_valid("1d6", 1, 6);
         assert_valid("5d20", 5, 20);
-        assert_valid("0d0", 0, 0);
+        assert_valid("0d6", 0, 6);
         assert_valid("999d999", 999, 999);
+        assert_valid("1000d1000", MAX_DICE, MAX_FACES);
+    }
+
+    #[test]
+    fn parse_keep_highest_strs() {
+        assert_eq!(
+            parse_roll_many_str("4d6kh3").unwrap(),
+            RollManyRequest::new(4, 6).keep_highest(3)
+        );
+        assert_eq!(
+            parse_roll_many_str("2d20kh1").unwrap().to_string(),
+            "2d20kh1"
+        );
+        let _ = parse_roll_many_str("4d6kh").unwrap_err();
+        let _ = parse_roll_many_str("4d6kl3").unwrap_err();
+    }
+
+    #[test]
+    fn keep_highest_totals() {
+        let results = RolledDiceResults {
+            request: RollManyRequest::new(4, 6).keep_highest(3),
+            results: vec![3, 1, 6, 3],
+        };
+        assert_eq!(results.kept(), vec![true, false, true, true]);
+        assert_eq!(results.total(), 12);
+        assert_eq!(results.to_string(), "3+[1]+6+3=12");
+
+        for _ in 0..NUM_TEST_TRIALS {
+            let results = roll_many_from_str("4d6kh3").unwrap();
+            assert!((3..=18).contains(&results.total()));
+        }
     }
 
     #[test]
@@ -179,6 +373,26 @@ mod tests {
         assert_invalid("1");
     }
 
+    #[test]
+    fn parse_out_of_bounds_roll_many_strs() {
+        for in_str in [
+            "999999999999999999d6",
+            "99999999999999999999999d6",
+            "1001d6",
+            "1d1001",
+            "1d0",
+            "0d0",
+            "3d6kh0",
+        ] {
+            assert_eq!(
+                parse_roll_many_str(in_str),
+                Err(DiceError::Invalid(String::from(in_str))),
+                "{in_str}"
+            );
+        }
+        assert!(roll_many_from_str("999999999999999999d6").is_err());
+    }
+
     #[test]
     fn roll_many_d20s() {
         let num_dice = NUM_TEST_TRIALS;
@@ -186,6 +400,7 @@ mod tests {
         let req = RollManyRequest {
             num_dice,
             num_faces,
+            keep_highest: None,
         };
 
         let results = roll_many(req);
@@ -202,6 +417,7 @@ mod tests {
         let req = RollManyRequest {
             num_dice,
             num_faces,
+            keep_highest: None,
         };
 
         let results = roll_many(req);
@@ -216,6 +432,7 @@ mod tests {
         let req = RollManyRequest {
             num_dice,
             num_faces,
+            keep_highest: None,
         };
 
         let results = roll_many(req);
@@ -230,6 +447,7 @@ mod tests {
         let exp_req = RollManyRequest {
             num_dice,
             num_faces,
+            keep_highest: None,
         };
         let result = roll_many_from_str(in_str).unwrap();
         assert_eq!(exp_req, result.request);
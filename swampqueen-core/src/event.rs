@@ -0,0 +1,156 @@
+//! Events: a typed record of what happens during a [`Game`](crate::game::Game), for achievements,
+//! logging and frontends. Subscribe with [`Game::subscribe`](crate::game::Game::subscribe) and
+//! every [`GameEvent`] is passed to the subscriber as it happens.
+
+use std::fmt::{Debug, Display};
+
+use crate::{check::CheckResult, dice::RolledDiceResults};
+
+/// Something that happened during a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEvent {
+    /// A character was created and joined the party.
+    CharacterCreated {
+        /// The character's name.
+        name: String,
+    },
+    /// The party entered a location.
+    LocationEntered {
+        /// The location's ID.
+        location_id: String,
+    },
+    /// Dice were rolled, whether on their own or as part of a check or attack.
+    DiceRolled {
+        /// The dice and their results.
+        results: RolledDiceResults,
+    },
+    /// A character made a check, which either passed or failed.
+    CheckMade {
+        /// The character's name.
+        character: String,
+        /// The check's result.
+        check: CheckResult,
+    },
+    /// A party member took damage.
+    DamageTaken {
+        /// The character's name.
+        character: String,
+        /// How much damage they took.
+        amount: isize,
+    },
+    /// A party member used an ability.
+    AbilityUsed {
+        /// The character's name.
+        character: String,
+        /// The ability's name.
+        ability: String,
+    },
+    /// Items were added to the party's inventory.
+    ItemGained {
+        /// The item's ID.
+        item: String,
+        /// How many were added.
+        count: usize,
+    },
+}
+impl Display for GameEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CharacterCreated { name } => write!(f, "{name} joined the party"),
+            Self::LocationEntered { location_id } => write!(f, "entered {location_id}"),
+            Self::DiceRolled { results } => write!(f, "rolled {results}"),
+            Self::CheckMade { character, check } => write!(f, "{character} made a check: {check}"),
+            Self::DamageTaken { character, amount } => {
+                write!(f, "{character} took {amount} damage")
+            }
+            Self::AbilityUsed { character, ability } => write!(f, "{character} used {ability}"),
+            Self::ItemGained { item, count } => write!(f, "gained {item} x{count}"),
+        }
+    }
+}
+
+/// Identifies a subscriber so that it can later be removed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriberId(usize);
+
+/// Something called with every event.
+type Subscriber = Box<dyn FnMut(&GameEvent)>;
+
+/// A list of subscribers, each called with every event in the order they subscribed.
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<(SubscriberId, Subscriber)>,
+    next_id: usize,
+}
+impl EventBus {
+    /// Add a subscriber.
+    pub fn subscribe<F>(&mut self, subscriber: F) -> SubscriberId
+    where
+        F: FnMut(&GameEvent) + 'static,
+    {
+        let id = SubscriberId(self.next_id);
+        self.next_id += 1;
+        self.subscribers.push((id, Box::new(subscriber)));
+        id
+    }
+
+    /// Remove a subscriber. Returns `false` if it had already been removed.
+    pub fn unsubscribe(&mut self, id: SubscriberId) -> bool {
+        let before = self.subscribers.len();
+        self.subscribers.retain(|(subscriber, _)| *subscriber != id);
+        self.subscribers.len() < before
+    }
+
+    /// Pass an event to every subscriber.
+    pub fn emit(&mut self, event: &GameEvent) {
+        for (_, subscriber) in &mut self.subscribers {
+            subscriber(event);
+        }
+    }
+}
+impl Debug for EventBus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventBus")
+            .field("subscribers", &self.subscribers.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    #[test]
+    fn subscribe_and_unsubscribe() {
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        let mut bus = EventBus::default();
+        let first = {
+            let seen = Rc::clone(&seen);
+            bus.subscribe(move |event| seen.borrow_mut().push(format!("first: {event}")))
+        };
+        {
+            let seen = Rc::clone(&seen);
+            bus.subscribe(move |event| seen.borrow_mut().push(format!("second: {event}")));
+        }
+
+        bus.emit(&GameEvent::LocationEntered {
+            location_id: String::from("testplace"),
+        });
+        assert!(bus.unsubscribe(first));
+        assert!(!bus.unsubscribe(first));
+        bus.emit(&GameEvent::ItemGained {
+            item: String::from("frog_charm"),
+            count: 1,
+        });
+        assert_eq!(
+            *seen.borrow(),
+            [
+                "first: entered testplace",
+                "second: entered testplace",
+                "second: gained frog_charm x1"
+            ]
+        );
+    }
+}
@@ -1,7 +1,10 @@
-use std::{fs::File, io};
+use std::{
+    fs::{self, File},
+    io,
+};
 
 use camino::Utf8Path;
-use serde::de::DeserializeOwned;
+use serde::{Serialize, de::DeserializeOwned};
 
 /// Load a `.json` file into the given type.
 ///
@@ -23,13 +26,82 @@ where
     Ok(serde_json::from_reader(file)?)
 }
 
+/// Load a text file, such as a script, into a [`String`].
+///
+/// # Arguments
+///
+/// * `path` - Any type implementing [`AsRef<Utf8Path>`], denoting the path to the file.
+///
+/// # Errors
+///
+/// * When there is a problem calling [`fs::read_to_string`].
+pub fn try_load_text<P>(path: P) -> io::Result<String>
+where
+    P: AsRef<Utf8Path>,
+{
+    fs::read_to_string(path.as_ref())
+}
+
+/// Save the given value to a `.json` file, replacing it if it already exists.
+///
+/// # Arguments
+///
+/// * `path` - Any type implementing [`AsRef<Utf8Path>`], denoting the path to the `.json` file.
+///
+/// * `value` - The value to save.
+///
+/// # Errors
+///
+/// * When there is a problem calling [`File::create`].
+///
+/// * When there is a problem calling [`serde_json::to_writer_pretty`].
+pub fn try_save_json<P, S>(path: P, value: &S) -> io::Result<()>
+where
+    P: AsRef<Utf8Path>,
+    S: Serialize,
+{
+    let file = File::create(path.as_ref())?;
+    Ok(serde_json::to_writer_pretty(file, value)?)
+}
+
+/// List the names (without extension) of every file with the given extension in a directory, in
+/// alphabetical order.
+///
+/// # Arguments
+///
+/// * `dir` - Any type implementing [`AsRef<Utf8Path>`], denoting the path to the directory.
+///
+/// * `extension` - The extension to look for, without the leading `.`.
+///
+/// # Errors
+///
+/// * When there is a problem calling [`fs::read_dir`] or reading one of its entries.
+pub fn try_list_files<P>(dir: P, extension: &str) -> io::Result<Vec<String>>
+where
+    P: AsRef<Utf8Path>,
+{
+    let mut names = Vec::new();
+    for entry in fs::read_dir(dir.as_ref())? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == extension)
+            && let Some(name) = path.file_stem().and_then(|stem| stem.to_str())
+        {
+            names.push(String::from(name));
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
+    use camino::Utf8PathBuf;
     use serde::Deserialize;
 
     use super::*;
 
-    #[derive(Deserialize, Debug)]
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
     struct TestStruct {
         name: String,
         numbers: Vec<i32>,
@@ -45,4 +117,28 @@ mod tests {
         assert_eq!(expected_name, my_test_struct.name);
         assert_eq!(expected_numbers, my_test_struct.numbers);
     }
+
+    #[test]
+    fn list_test_json() {
+        assert_eq!(try_list_files("testfiles", "json").unwrap(), ["test"]);
+        assert_eq!(
+            try_list_files("testfiles/locations", "json").unwrap(),
+            ["testcorridor", "testplace"]
+        );
+    }
+
+    #[test]
+    fn save_and_reload_json() {
+        let original = TestStruct {
+            name: String::from("saved!"),
+            numbers: vec![4, 2],
+        };
+        let mut path = Utf8PathBuf::try_from(std::env::temp_dir()).unwrap();
+        path.push(format!("swampqueen-save-test-{}.json", std::process::id()));
+
+        try_save_json(&path, &original).unwrap();
+        let reloaded: TestStruct = try_load_json(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(original, reloaded);
+    }
 }
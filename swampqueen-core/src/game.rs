@@ -0,0 +1,1432 @@
+//! A game in progress. [`Game`] ties the party, the [`GameState`] and the current location
+//! together and reports what happens as [`GameEvent`]s; [`GameState`] is everything that isn't tied
+//! to any one [`Character`]: story flags, how every NPC feels about the party and the quest
+//! [`Journal`].
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    io,
+};
+
+#[cfg(feature = "fs-content")]
+use camino::Utf8Path;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "fs-content")]
+use crate::files::{try_load_json, try_save_json};
+use crate::{
+    ability::{Ability, AbilityError, AbilityOutcome, ScaledRoll},
+    character::{BuildError, Character, CharacterBuilder, Stat},
+    check::CheckResult,
+    combat::{Attack, Combat},
+    condition::ConditionContext,
+    dialogue::{Conversation, DialogueError, ResponseOutcome},
+    dice::DiceError,
+    event::{EventBus, GameEvent, SubscriberId},
+    item::Inventory,
+    location::Location,
+    party::{Party, PartyError},
+    quest::{Journal, Quest, QuestUpdate},
+    script::{ScriptError, ScriptOutcome},
+    skill::Skill,
+};
+#[cfg(feature = "serde")]
+use crate::{
+    ability::{ClassAbilities, TargetType},
+    combat::CombatOutcome,
+    dialogue::Npc,
+    progression::ClassProgression,
+};
+
+/// A game in progress. Everything done through a [`Game`] is reported to its subscribers as a
+/// [`GameEvent`]; changes made directly through [`Game::party_mut`] or [`Game::state_mut`] are
+/// not.
+#[derive(Debug, Default)]
+pub struct Game {
+    party: Option<Party>,
+    state: GameState,
+    location: Option<Location>,
+    conversation: Option<Conversation>,
+    fight: Option<Vec<String>>,
+    events: EventBus,
+}
+impl Game {
+    /// Start a game with no party yet, e.g. from a loaded [`GameState`].
+    #[must_use]
+    pub fn new(state: GameState) -> Self {
+        Self {
+            state,
+            ..Self::default()
+        }
+    }
+
+    /// Pick up a [saved game](SavedGame) where it left off, with its party back at its location.
+    /// The location is loaded again, but its on-enter script doesn't run and no turn passes.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GameError::Io`] if the saved location can't be loaded.
+    #[cfg(feature = "serde")]
+    pub fn resume(saved: SavedGame) -> Result<Self, GameError> {
+        let location = saved
+            .location
+            .as_deref()
+            .map(Location::try_load_location)
+            .transpose()
+            .map_err(GameError::Io)?;
+        Ok(Self {
+            party: saved.party,
+            state: saved.state,
+            location,
+            ..Self::default()
+        })
+    }
+
+    /// Everything needed to [resume](Game::resume) this game later: the story so far, the party
+    /// and where they are. A conversation in progress isn't kept.
+    #[must_use]
+    pub fn save(&self) -> SavedGame {
+        SavedGame {
+            state: self.state.clone(),
+            party: self.party.clone(),
+            location: self
+                .location
+                .as_ref()
+                .map(|location| String::from(location.id())),
+        }
+    }
+
+    /// Call `subscriber` with every event from now on.
+    pub fn subscribe<F>(&mut self, subscriber: F) -> SubscriberId
+    where
+        F: FnMut(&GameEvent) + 'static,
+    {
+        self.events.subscribe(subscriber)
+    }
+
+    /// Stop calling a subscriber. Returns `false` if it had already been removed.
+    pub fn unsubscribe(&mut self, id: SubscriberId) -> bool {
+        self.events.unsubscribe(id)
+    }
+
+    /// The party, once the first character has been created.
+    #[must_use]
+    pub const fn party(&self) -> Option<&Party> {
+        self.party.as_ref()
+    }
+
+    /// The party, mutably. Changes made through this aren't reported as events.
+    pub const fn party_mut(&mut self) -> Option<&mut Party> {
+        self.party.as_mut()
+    }
+
+    /// The story so far.
+    #[must_use]
+    pub const fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// The story so far, mutably. Changes made through this aren't reported as events.
+    pub const fn state_mut(&mut self) -> &mut GameState {
+        &mut self.state
+    }
+
+    /// The location the party is at, once they have entered one.
+    #[must_use]
+    pub const fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    /// The conversation the party is having, if they are [talking](Game::talk) to an NPC.
+    #[must_use]
+    pub const fn conversation(&self) -> Option<&Conversation> {
+        self.conversation.as_ref()
+    }
+
+    /// A context for evaluating [`Condition`](crate::condition::Condition)s against the party and
+    /// the story so far, once there is a party.
+    #[must_use]
+    pub fn condition_context(&self) -> Option<ConditionContext<'_>> {
+        self.party
+            .as_ref()
+            .map(|party| ConditionContext::new(party, &self.state))
+    }
+
+    /// Build a character and add them to the party, making them its leader if they are the first.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if the character can't be built or the party is
+    /// full.
+    pub fn create_character(&mut self, builder: &CharacterBuilder) -> Result<(), GameError> {
+        let character = builder.build().map_err(GameError::Build)?;
+        let name = String::from(character.name());
+        let gained = items_gained(&Inventory::default(), character.inventory());
+        match &mut self.party {
+            Some(party) => party.add(character).map_err(GameError::Party)?,
+            None => self.party = Some(Party::new(character)),
+        }
+        self.events.emit(&GameEvent::CharacterCreated { name });
+        self.emit_all(&gained);
+        Ok(())
+    }
+
+    /// Move the party to the location with the given ID, recording the visit, ending any
+    /// [conversation](Game::conversation), running its on-enter script and starting its
+    /// [quest](Location::quest). Once the script has run, moving takes a [turn](Game::pass_turn)
+    /// and every quest is [advanced](Game::update_quests); what the party's status effects did
+    /// comes first in the outcome's messages.
+    ///
+    /// If the script fails, the party is still at the location and its quest is started, but no
+    /// turn passes.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party, the location can't be loaded
+    /// or its script fails.
+    #[cfg(feature = "serde")]
+    pub fn enter_location(&mut self, location_id: &str) -> Result<ScriptOutcome, GameError> {
+        let location = Location::try_load_location(location_id).map_err(GameError::Io)?;
+        let party = self.party.as_mut().ok_or(GameError::NoParty)?;
+        self.state.journal_mut().record_visit(location_id);
+        self.events.emit(&GameEvent::LocationEntered {
+            location_id: String::from(location_id),
+        });
+
+        let before = party.inventory().clone();
+        let outcome = location.enter(party, &mut self.state);
+        let gained = items_gained(&before, party.inventory());
+        let quest = location.quest().map(String::from);
+        self.location = Some(location);
+        self.conversation = None;
+        self.emit_all(&gained);
+        let mut started = match quest {
+            Some(quest_id) => self.start_quest(&quest_id)?,
+            None => Vec::new(),
+        };
+        let mut outcome = outcome.map_err(GameError::Script)?;
+
+        let mut messages = self.pass_turn()?;
+        messages.append(&mut outcome.messages);
+        messages.append(&mut started);
+        messages.extend(self.update_quests()?);
+        outcome.messages = messages;
+        Ok(outcome)
+    }
+
+    /// End the party's turn outside of combat, ticking every standing member's status effects.
+    /// Returns what the effects did, to be shown to the player.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GameError::NoParty`] if there is no party.
+    pub fn pass_turn(&mut self) -> Result<Vec<String>, GameError> {
+        let party = self.party.as_mut().ok_or(GameError::NoParty)?;
+        let mut messages = Vec::new();
+        let mut events = Vec::new();
+        for member in party.members_mut().iter_mut().filter(|m| !m.is_down()) {
+            let tick = member.tick_status_effects();
+            let character = String::from(member.name());
+            for (kind, results) in &tick.damage_rolls {
+                messages.push(format!(
+                    "{character} is {kind}: took {} damage",
+                    results.total()
+                ));
+                events.push(GameEvent::DiceRolled {
+                    results: results.clone(),
+                });
+            }
+            if tick.total_damage() > 0 {
+                events.push(GameEvent::DamageTaken {
+                    character: character.clone(),
+                    amount: isize::try_from(tick.total_damage()).unwrap_or(isize::MAX),
+                });
+            }
+            for kind in &tick.expired {
+                messages.push(format!("{character} is no longer {kind}"));
+            }
+        }
+        self.emit_all(&events);
+        Ok(messages)
+    }
+
+    /// Make the choice with the given ID at the current location, running its on-choice script.
+    /// If the choice has an [ability](crate::location::Choice::ability), the party's leader must
+    /// be able to use it, and uses it once the script has succeeded, so nothing is spent if the
+    /// script fails. Afterwards its [quest](crate::location::Choice::quest) is started and every
+    /// quest is [advanced](Game::update_quests). If the script sends the party elsewhere, call
+    /// [`Game::enter_location`] with [`ScriptOutcome::go_to`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or location, the choice isn't
+    /// offered, the leader doesn't have or can't use its ability or its script fails.
+    pub fn choose(&mut self, choice_id: &str) -> Result<ScriptOutcome, GameError> {
+        let location = self.location.as_ref().ok_or(GameError::NoLocation)?;
+        let party = self.party.as_ref().ok_or(GameError::NoParty)?;
+        let context = ConditionContext::new(party, &self.state);
+        #[cfg_attr(not(feature = "serde"), allow(unused_variables))]
+        let choice = location
+            .choices_for(&context)
+            .find(|choice| choice.id() == choice_id)
+            .ok_or_else(|| GameError::NoSuchChoice(String::from(choice_id)))?;
+
+        #[cfg(feature = "serde")]
+        let quest = choice.quest().map(String::from);
+        #[cfg(feature = "serde")]
+        let ability = match choice.ability() {
+            Some(ability_id) => {
+                let leader = party.leader();
+                let abilities = ClassAbilities::try_load(leader.class()).map_err(GameError::Io)?;
+                let ability = abilities
+                    .get(ability_id)
+                    .cloned()
+                    .ok_or_else(|| GameError::NoSuchAbility(String::from(ability_id)))?;
+                leader
+                    .can_use_ability(&ability)
+                    .map_err(GameError::Ability)?;
+                if ability.target() != TargetType::Caster {
+                    return Err(GameError::Ability(AbilityError::NoTarget));
+                }
+                Some(ability)
+            }
+            None => None,
+        };
+
+        let location = self.location.as_ref().ok_or(GameError::NoLocation)?;
+        let party = self.party.as_mut().ok_or(GameError::NoParty)?;
+        let before = party.inventory().clone();
+        let outcome = location.choose(choice_id, party, &mut self.state);
+        let gained = items_gained(&before, party.inventory());
+        self.emit_all(&gained);
+        let mut outcome = outcome.map_err(GameError::Script)?;
+
+        let mut messages = Vec::new();
+        #[cfg(feature = "serde")]
+        if let Some(ability) = ability {
+            let leader = self
+                .party
+                .as_ref()
+                .ok_or(GameError::NoParty)?
+                .leader_index();
+            let used = self.use_ability(leader, &ability, None, None)?;
+            messages.push(describe_ability(self.member(leader)?, &ability, &used));
+        }
+        messages.append(&mut outcome.messages);
+        #[cfg(feature = "serde")]
+        {
+            if let Some(quest_id) = quest {
+                messages.extend(self.start_quest(&quest_id)?);
+            }
+            messages.extend(self.update_quests()?);
+        }
+        outcome.messages = messages;
+        Ok(outcome)
+    }
+
+    /// Leave the current location in the given direction, entering the location that lies there.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no location or no exit that way, or
+    /// entering the next location fails.
+    #[cfg(feature = "serde")]
+    pub fn go(&mut self, direction: &str) -> Result<ScriptOutcome, GameError> {
+        let location = self.location.as_ref().ok_or(GameError::NoLocation)?;
+        let next = location
+            .exit(direction)
+            .map(String::from)
+            .ok_or_else(|| GameError::NoSuchExit(String::from(direction)))?;
+        self.enter_location(&next)
+    }
+
+    /// The IDs of the items lying at the current location that the party hasn't taken yet.
+    #[must_use]
+    pub fn items_here(&self) -> Vec<&str> {
+        self.location
+            .iter()
+            .flat_map(|location| {
+                location
+                    .items()
+                    .iter()
+                    .filter(|item| !self.state.has_flag(&taken_flag(location, item)))
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Pick up an item lying at the current location, adding it to the party's inventory.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or location, or the item isn't
+    /// lying here.
+    pub fn take(&mut self, item_id: &str) -> Result<(), GameError> {
+        if !self.items_here().contains(&item_id) {
+            return Err(GameError::NoSuchItem(String::from(item_id)));
+        }
+        let location = self.location.as_ref().ok_or(GameError::NoLocation)?;
+        let flag = taken_flag(location, item_id);
+        self.give_item(item_id, 1)?;
+        self.state.set_flag(&flag);
+        Ok(())
+    }
+
+    /// Roll dice written like `4d6kh3` or `1d20 + 2`, recording the roll in the
+    /// [roll history](GameState::roll_history). A stat, as in `1d20 + Cunning`, adds the party
+    /// leader's value of it.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GameError::Dice`] if `dice` is the incorrect format.
+    pub fn roll(&mut self, dice: &str) -> Result<RollRecord, GameError> {
+        let roll: ScaledRoll = dice.parse().map_err(GameError::Dice)?;
+        let stats = self
+            .party
+            .as_ref()
+            .map(|party| party.leader().effective_stats())
+            .unwrap_or_default();
+        let (results, total) = roll.roll(&stats);
+        let record = RollRecord {
+            dice: roll.to_string(),
+            results: results.to_string(),
+            total,
+        };
+        self.events.emit(&GameEvent::DiceRolled { results });
+        self.state.record_roll(record.clone());
+        Ok(record)
+    }
+
+    /// Have the party member at `member` make a stat check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or no such member.
+    pub fn check(
+        &mut self,
+        member: usize,
+        stat: Stat,
+        difficulty: isize,
+    ) -> Result<CheckResult, GameError> {
+        self.make_check(member, |character| character.check(stat, difficulty))
+    }
+
+    /// Have the party member at `member` make a skill check.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or no such member.
+    pub fn skill_check(
+        &mut self,
+        member: usize,
+        skill: Skill,
+        difficulty: isize,
+    ) -> Result<CheckResult, GameError> {
+        self.make_check(member, |character| character.skill_check(skill, difficulty))
+    }
+
+    fn make_check<F>(&mut self, member: usize, roll: F) -> Result<CheckResult, GameError>
+    where
+        F: FnOnce(&Character) -> CheckResult,
+    {
+        let character = self.member(member)?;
+        let check = roll(character);
+        let name = String::from(character.name());
+        self.emit_check(name, &check);
+        Ok(check)
+    }
+
+    /// Deal damage to the party member at `member`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or no such member.
+    pub fn damage(&mut self, member: usize, amount: isize) -> Result<(), GameError> {
+        let character = self
+            .party
+            .as_mut()
+            .ok_or(GameError::NoParty)?
+            .members_mut()
+            .get_mut(member)
+            .ok_or(GameError::NoSuchMember(member))?;
+        character.take_damage(amount);
+        let character = String::from(character.name());
+        self.events
+            .emit(&GameEvent::DamageTaken { character, amount });
+        Ok(())
+    }
+
+    /// Add items to the party's inventory.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GameError::NoParty`] if there is no party.
+    pub fn give_item(&mut self, item: &str, count: usize) -> Result<(), GameError> {
+        let party = self.party.as_mut().ok_or(GameError::NoParty)?;
+        party.inventory_mut().add(item, count);
+        if count > 0 {
+            self.events.emit(&GameEvent::ItemGained {
+                item: String::from(item),
+                count,
+            });
+        }
+        Ok(())
+    }
+
+    /// Choose a response in a conversation; see [`Conversation::respond`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or the response can't be
+    /// chosen.
+    pub fn respond(
+        &mut self,
+        conversation: &mut Conversation,
+        index: usize,
+    ) -> Result<ResponseOutcome, GameError> {
+        let party = self.party.as_mut().ok_or(GameError::NoParty)?;
+        let before = party.inventory().clone();
+        let outcome = conversation
+            .respond(index, party, &mut self.state)
+            .map_err(GameError::Dialogue)?;
+        let leader = String::from(party.leader().name());
+        let gained = items_gained(&before, party.inventory());
+        if let Some(check) = &outcome.check {
+            self.emit_check(leader, check);
+        }
+        self.emit_all(&gained);
+        Ok(outcome)
+    }
+
+    /// Start talking to an NPC at the current location, replacing any
+    /// [conversation](Game::conversation) already going on. Respond with [`Game::say`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no location, the NPC isn't there, or the
+    /// NPC or their dialogue can't be loaded.
+    #[cfg(feature = "serde")]
+    pub fn talk(&mut self, npc_id: &str) -> Result<&Conversation, GameError> {
+        let location = self.location.as_ref().ok_or(GameError::NoLocation)?;
+        if !location.npcs().iter().any(|npc| npc == npc_id) {
+            return Err(GameError::NoSuchNpc(String::from(npc_id)));
+        }
+        let npc = Npc::try_load(npc_id).map_err(GameError::Io)?;
+        let conversation = Conversation::try_start(npc).map_err(GameError::Io)?;
+        Ok(self.conversation.insert(conversation))
+    }
+
+    /// Choose the response at `index` in the current [conversation](Game::conversation), as
+    /// [`Game::respond`] does. The conversation is forgotten once it is over, and a fight the
+    /// response starts waits to be [taken](Game::take_fight).
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if the party isn't talking to anyone or the response
+    /// can't be chosen.
+    pub fn say(&mut self, index: usize) -> Result<ResponseOutcome, GameError> {
+        let mut conversation = self
+            .conversation
+            .take()
+            .ok_or(GameError::Dialogue(DialogueError::Ended))?;
+        let outcome = self.respond(&mut conversation, index);
+        if !conversation.is_over() {
+            self.conversation = Some(conversation);
+        }
+        if let Ok(ResponseOutcome {
+            combat: Some(creatures),
+            ..
+        }) = &outcome
+        {
+            self.fight = Some(creatures.clone());
+        }
+        outcome
+    }
+
+    /// The IDs of the creatures a response [said](Game::say) in conversation started a fight
+    /// with, if it hasn't been fought yet. The frontend is responsible for running the
+    /// [`Combat`].
+    pub const fn take_fight(&mut self) -> Option<Vec<String>> {
+        self.fight.take()
+    }
+
+    /// Have the party member at `member` attack; see [`Combat::attack`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or no such member.
+    pub fn attack(
+        &mut self,
+        combat: &mut Combat,
+        member: usize,
+        target: usize,
+    ) -> Result<Option<Attack>, GameError> {
+        let attack = combat.attack(self.member(member)?, target);
+        if let Some(attack) = &attack {
+            self.events.emit(&GameEvent::DiceRolled {
+                results: attack.rolls.clone(),
+            });
+        }
+        Ok(attack)
+    }
+
+    /// Have the party member at `member` use an ability. In combat, it is used against the enemy
+    /// at index `target`, or every standing enemy if `target` is [`None`]; see
+    /// [`Combat::use_ability`]. Outside of combat there are no enemies, so only abilities
+    /// targeting the [`Caster`](crate::ability::TargetType::Caster) can be used.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or no such member, or the
+    /// member can't use the ability.
+    pub fn use_ability(
+        &mut self,
+        member: usize,
+        ability: &Ability,
+        combat: Option<&mut Combat>,
+        target: Option<usize>,
+    ) -> Result<AbilityOutcome, GameError> {
+        let hero = self
+            .party
+            .as_mut()
+            .ok_or(GameError::NoParty)?
+            .members_mut()
+            .get_mut(member)
+            .ok_or(GameError::NoSuchMember(member))?;
+        let outcome = match combat {
+            Some(combat) => combat.use_ability(hero, ability, target),
+            None => hero.use_ability(ability, &mut []),
+        }
+        .map_err(GameError::Ability)?;
+
+        let character = String::from(hero.name());
+        if let Some(results) = &outcome.rolls {
+            self.events.emit(&GameEvent::DiceRolled {
+                results: results.clone(),
+            });
+        }
+        self.events.emit(&GameEvent::AbilityUsed {
+            character,
+            ability: String::from(ability.name()),
+        });
+        Ok(outcome)
+    }
+
+    /// Have every standing enemy attack the party; see [`Combat::enemy_turn`].
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GameError::NoParty`] if there is no party.
+    pub fn enemy_turn(&mut self, combat: &mut Combat) -> Result<Vec<Attack>, GameError> {
+        let party = self.party.as_mut().ok_or(GameError::NoParty)?;
+        let attacks = combat.enemy_turn(party.members_mut());
+        for attack in &attacks {
+            self.events.emit(&GameEvent::DiceRolled {
+                results: attack.rolls.clone(),
+            });
+            self.events.emit(&GameEvent::DamageTaken {
+                character: attack.target.clone(),
+                amount: attack.damage,
+            });
+        }
+        Ok(attacks)
+    }
+
+    /// Finish a fight once it is no longer [ongoing](CombatOutcome::Ongoing). On victory, every
+    /// enemy is recorded as defeated in the [`Journal`], the party is
+    /// [awarded](Game::award_experience) the fight's experience and every quest is
+    /// [advanced](Game::update_quests). Returns the messages to show the player.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party, or a class progression or quest
+    /// can't be loaded.
+    #[cfg(feature = "serde")]
+    pub fn end_combat(&mut self, combat: &Combat) -> Result<Vec<String>, GameError> {
+        let party = self.party.as_ref().ok_or(GameError::NoParty)?;
+        if combat.outcome(party.members()) != CombatOutcome::Victory {
+            return Ok(Vec::new());
+        }
+        for enemy in combat.enemies() {
+            self.state.journal_mut().record_defeat(enemy.id());
+        }
+        let mut messages = vec![format!(
+            "Victory! Everyone earned {} XP.",
+            combat.experience()
+        )];
+        messages.extend(self.award_experience(combat.experience())?);
+        messages.extend(self.update_quests()?);
+        Ok(messages)
+    }
+
+    /// Give every party member the same amount of experience. Returns a message for each level
+    /// reached and ability unlocked; stat points are left for the player to spend.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party or a class progression can't
+    /// be loaded.
+    #[cfg(feature = "serde")]
+    pub fn award_experience(&mut self, experience: usize) -> Result<Vec<String>, GameError> {
+        let party = self.party.as_mut().ok_or(GameError::NoParty)?;
+        let mut messages = Vec::new();
+        for member in party.members_mut() {
+            let progression = ClassProgression::try_load(member.class()).map_err(GameError::Io)?;
+            for reward in member.gain_experience(experience, &progression) {
+                messages.push(format!("{} reached level {}!", member.name(), reward.level));
+                for unlock in &reward.unlocks {
+                    messages.push(format!("{} unlocked {unlock}!", member.name()));
+                }
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Start the quest with the given ID, writing its first stage in the [`Journal`]. Returns the
+    /// messages to show the player, which are empty if it had already been started.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GameError::Io`] if the quest can't be loaded.
+    #[cfg(feature = "serde")]
+    pub fn start_quest(&mut self, quest_id: &str) -> Result<Vec<String>, GameError> {
+        let quest = Quest::try_load(quest_id).map_err(GameError::Io)?;
+        if !self.state.start_quest(&quest) {
+            return Ok(Vec::new());
+        }
+        let mut messages = vec![format!("New quest: {}", quest.name())];
+        messages.extend(
+            quest
+                .stages()
+                .first()
+                .map(|stage| format!("Journal updated: {}", stage.description)),
+        );
+        Ok(messages)
+    }
+
+    /// Advance every quest in progress as far as it can go, [awarding](Game::award_experience)
+    /// the experience of each one finished. Returns the messages to show the player.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`GameError`] if there is no party, or a quest or class
+    /// progression can't be loaded.
+    #[cfg(feature = "serde")]
+    pub fn update_quests(&mut self) -> Result<Vec<String>, GameError> {
+        let quest_ids: Vec<String> = self
+            .state
+            .journal()
+            .quests()
+            .filter(|(_, log)| !log.is_completed())
+            .map(|(quest_id, _)| String::from(quest_id))
+            .collect();
+        let mut messages = Vec::new();
+        for quest_id in quest_ids {
+            let quest = Quest::try_load(&quest_id).map_err(GameError::Io)?;
+            for update in self.update_quest(&quest)? {
+                match update {
+                    QuestUpdate::StageCompleted { next_stage } => {
+                        messages.extend(
+                            quest
+                                .stages()
+                                .get(next_stage)
+                                .map(|stage| format!("Journal updated: {}", stage.description)),
+                        );
+                    }
+                    QuestUpdate::Completed { experience } => {
+                        messages.push(format!(
+                            "Quest complete: {}! Everyone earned {experience} XP.",
+                            quest.name()
+                        ));
+                        messages.extend(self.award_experience(experience)?);
+                    }
+                }
+            }
+        }
+        Ok(messages)
+    }
+
+    /// Advance a started quest; see [`GameState::update_quest`]. [`Game::update_quests`] does this
+    /// for every quest in progress.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`GameError::NoParty`] if there is no party.
+    pub fn update_quest(&mut self, quest: &Quest) -> Result<Vec<QuestUpdate>, GameError> {
+        let party = self.party.as_mut().ok_or(GameError::NoParty)?;
+        let before = party.inventory().clone();
+        let updates = self.state.update_quest(quest, party);
+        let gained = items_gained(&before, party.inventory());
+        self.emit_all(&gained);
+        Ok(updates)
+    }
+
+    /// The party member at `member`.
+    fn member(&self, member: usize) -> Result<&Character, GameError> {
+        self.party
+            .as_ref()
+            .ok_or(GameError::NoParty)?
+            .members()
+            .get(member)
+            .ok_or(GameError::NoSuchMember(member))
+    }
+
+    fn emit_check(&mut self, character: String, check: &CheckResult) {
+        self.events.emit(&GameEvent::DiceRolled {
+            results: check.dice().clone(),
+        });
+        self.events.emit(&GameEvent::CheckMade {
+            character,
+            check: check.clone(),
+        });
+    }
+
+    fn emit_all(&mut self, events: &[GameEvent]) {
+        for event in events {
+            self.events.emit(event);
+        }
+    }
+}
+
+/// What happened when a character used an ability, to be shown to the player.
+#[cfg(feature = "serde")]
+fn describe_ability(character: &Character, ability: &Ability, outcome: &AbilityOutcome) -> String {
+    match &outcome.rolls {
+        Some(rolls) => format!(
+            "{} used {} ({rolls}) for {}",
+            character.name(),
+            ability.name(),
+            outcome.amount
+        ),
+        None => format!("{} used {}", character.name(), ability.name()),
+    }
+}
+
+/// An [`GameEvent::ItemGained`] for every item there is more of in `after` than `before`.
+fn items_gained(before: &Inventory, after: &Inventory) -> Vec<GameEvent> {
+    after
+        .iter()
+        .filter(|&(item, count)| count > before.count(item))
+        .map(|(item, count)| GameEvent::ItemGained {
+            item: String::from(item),
+            count: count - before.count(item),
+        })
+        .collect()
+}
+
+/// The story flag set once an item lying at a location has been taken.
+fn taken_flag(location: &Location, item_id: &str) -> String {
+    format!("taken:{}:{item_id}", location.id())
+}
+
+/// The reasons an action can't be taken in a [`Game`].
+#[derive(Debug)]
+pub enum GameError {
+    /// No character has been created yet.
+    NoParty,
+    /// There is no party member at the given index.
+    NoSuchMember(usize),
+    /// The party hasn't entered a location yet.
+    NoLocation,
+    /// The current location doesn't offer a choice with the given ID.
+    NoSuchChoice(String),
+    /// The current location has no exit in the given direction.
+    NoSuchExit(String),
+    /// The item with the given ID isn't lying at the current location.
+    NoSuchItem(String),
+    /// The NPC with the given ID isn't at the current location.
+    NoSuchNpc(String),
+    /// The party's leader has no ability with the given ID.
+    NoSuchAbility(String),
+    /// The character couldn't be built.
+    Build(BuildError),
+    /// The party couldn't be changed.
+    Party(PartyError),
+    /// A file couldn't be loaded.
+    Io(io::Error),
+    /// A location script couldn't be loaded or failed.
+    Script(ScriptError),
+    /// A dialogue response couldn't be chosen.
+    Dialogue(DialogueError),
+    /// The dice weren't written correctly.
+    Dice(DiceError),
+    /// An ability couldn't be used.
+    Ability(AbilityError),
+}
+impl Display for GameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoParty => write!(f, "there is no party yet"),
+            Self::NoSuchMember(index) => write!(f, "there is no party member {index}"),
+            Self::NoLocation => write!(f, "the party isn't anywhere yet"),
+            Self::NoSuchChoice(choice) => write!(f, "\"{choice}\" isn't a choice here"),
+            Self::NoSuchExit(direction) => write!(f, "there is no way {direction} from here"),
+            Self::NoSuchItem(item) => write!(f, "there is no {item} here"),
+            Self::NoSuchNpc(npc) => write!(f, "{npc} isn't here"),
+            Self::NoSuchAbility(ability) => {
+                write!(f, "the party's leader has no {ability} ability")
+            }
+            Self::Build(err) => write!(f, "{err}"),
+            Self::Party(err) => write!(f, "{err}"),
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Script(err) => write!(f, "{err}"),
+            Self::Dialogue(err) => write!(f, "{err}"),
+            Self::Dice(err) => write!(f, "invalid dice: {err}"),
+            Self::Ability(err) => write!(f, "{err}"),
+        }
+    }
+}
+impl std::error::Error for GameError {}
+
+/// The most rolls kept in a [`GameState`]'s roll history.
+pub const ROLL_HISTORY_LENGTH: usize = 100;
+
+/// A roll the player made with [`Game::roll`], as kept in the roll history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollRecord {
+    /// The dice rolled, e.g. `1d20 + 2`.
+    pub dice: String,
+    /// Each die's result, as shown by
+    /// [`RolledDiceResults`](crate::dice::RolledDiceResults)' [`Display`].
+    pub results: String,
+    /// The final total, once any bonus has been added.
+    pub total: isize,
+}
+impl Display for RollRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.dice, self.results)?;
+        // Only rolls with a bonus have a total that differs from the dice
+        if self.dice.contains(['+', '-']) {
+            write!(f, ", total {}", self.total)?;
+        }
+        Ok(())
+    }
+}
+
+/// Everything the story remembers about what the party has done.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameState {
+    #[cfg_attr(feature = "serde", serde(default))]
+    flags: BTreeSet<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    dispositions: BTreeMap<String, isize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    journal: Journal,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rolls: Vec<RollRecord>,
+}
+impl GameState {
+    /// Load a saved game from the filesystem.
+    ///
+    /// # Errors
+    ///
+    /// This function returns errors typical to loading and deserializing a struct from the disk
+    /// (file path does not exist, deserialization failure, filesystem error, etc.).
+    #[cfg(feature = "fs-content")]
+    pub fn try_load<P: AsRef<Utf8Path>>(path: P) -> io::Result<Self> {
+        try_load_json(path)
+    }
+
+    /// Save the game to the filesystem, replacing any existing save at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the file cannot be created or written to.
+    #[cfg(feature = "fs-content")]
+    pub fn try_save<P: AsRef<Utf8Path>>(&self, path: P) -> io::Result<()> {
+        try_save_json(path, self)
+    }
+
+    /// The party's quest journal.
+    #[must_use]
+    pub const fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    /// The party's quest journal, mutably.
+    pub const fn journal_mut(&mut self) -> &mut Journal {
+        &mut self.journal
+    }
+
+    /// Whether the given story flag has been set.
+    #[must_use]
+    pub fn has_flag(&self, flag: &str) -> bool {
+        self.flags.contains(flag)
+    }
+
+    /// Set a story flag.
+    pub fn set_flag(&mut self, flag: &str) {
+        self.flags.insert(String::from(flag));
+    }
+
+    /// Clear a story flag.
+    pub fn clear_flag(&mut self, flag: &str) {
+        self.flags.remove(flag);
+    }
+
+    /// Every story flag that has been set, in alphabetical order.
+    pub fn flags(&self) -> impl Iterator<Item = &str> {
+        self.flags.iter().map(String::as_str)
+    }
+
+    /// How the NPC with the given ID feels about the party. Every NPC starts at 0; higher is
+    /// friendlier.
+    #[must_use]
+    pub fn disposition(&self, npc_id: &str) -> isize {
+        self.dispositions.get(npc_id).copied().unwrap_or_default()
+    }
+
+    /// Change how the NPC with the given ID feels about the party.
+    pub fn change_disposition(&mut self, npc_id: &str, amount: isize) {
+        *self.dispositions.entry(String::from(npc_id)).or_default() += amount;
+    }
+
+    /// The player's most recent rolls, oldest first. At most [`ROLL_HISTORY_LENGTH`] are kept.
+    #[must_use]
+    pub fn roll_history(&self) -> &[RollRecord] {
+        &self.rolls
+    }
+
+    /// Add a roll to the end of the roll history, forgetting the oldest if it is full.
+    pub fn record_roll(&mut self, roll: RollRecord) {
+        self.rolls.push(roll);
+        if self.rolls.len() > ROLL_HISTORY_LENGTH {
+            let excess = self.rolls.len() - ROLL_HISTORY_LENGTH;
+            self.rolls.drain(..excess);
+        }
+    }
+}
+
+/// A game as it is saved: the [`GameState`], along with the party and the ID of the location they
+/// are at. A save holding only a [`GameState`] loads as one with no party or location.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SavedGame {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    state: GameState,
+    #[cfg_attr(feature = "serde", serde(default))]
+    party: Option<Party>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    location: Option<String>,
+}
+impl SavedGame {
+    /// Load a saved game from the filesystem.
+    ///
+    /// # Errors
+    ///
+    /// This function returns errors typical to loading and deserializing a struct from the disk
+    /// (file path does not exist, deserialization failure, filesystem error, etc.).
+    #[cfg(feature = "fs-content")]
+    pub fn try_load<P: AsRef<Utf8Path>>(path: P) -> io::Result<Self> {
+        try_load_json(path)
+    }
+
+    /// Save the game to the filesystem, replacing any existing save at `path`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the file cannot be created or written to.
+    #[cfg(feature = "fs-content")]
+    pub fn try_save<P: AsRef<Utf8Path>>(&self, path: P) -> io::Result<()> {
+        try_save_json(path, self)
+    }
+
+    /// The story so far.
+    #[must_use]
+    pub const fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    /// The party, if one had been created.
+    #[must_use]
+    pub const fn party(&self) -> Option<&Party> {
+        self.party.as_ref()
+    }
+
+    /// The ID of the location the party was at, if they had entered one.
+    #[must_use]
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_deref()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    #[cfg(feature = "fs-content")]
+    use std::{cell::RefCell, rc::Rc};
+
+    #[cfg(feature = "fs-content")]
+    use camino::Utf8PathBuf;
+
+    use super::*;
+    #[cfg(feature = "fs-content")]
+    use crate::{
+        background::Background,
+        character::{Class, Race},
+        creation::{CreationMode, CreationRules},
+        status::{StatusEffect, StatusKind},
+    };
+
+    #[cfg(feature = "fs-content")]
+    fn builder(name: &str, background: Option<&str>) -> CharacterBuilder {
+        let mut builder =
+            CharacterBuilder::with_rules(CreationMode::Standard, CreationRules::default());
+        builder
+            .set_name(name)
+            .unwrap()
+            .set_class(Class::Trespasser)
+            .set_race(Race::GoblinoidFae)
+            .set_background(background.map(|id| Background::try_load(id).unwrap()));
+        builder.add_bonus_stat(Stat::Cunning).unwrap();
+        builder.add_bonus_stat(Stat::Bulk).unwrap();
+        builder
+    }
+
+    /// A game whose events are all recorded in the returned list.
+    #[cfg(feature = "fs-content")]
+    fn recorded_game() -> (Game, Rc<RefCell<Vec<GameEvent>>>) {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut game = Game::default();
+        let recorded = Rc::clone(&events);
+        game.subscribe(move |event| recorded.borrow_mut().push(event.clone()));
+        (game, events)
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn game_reports_events() {
+        let (mut game, events) = recorded_game();
+        assert!(game.roll("1d6").is_ok());
+        assert!(matches!(
+            game.enter_location("testplace"),
+            Err(GameError::NoParty)
+        ));
+        assert!(matches!(game.choose("leave"), Err(GameError::NoLocation)));
+
+        game.create_character(&builder("Smuggs", Some("bog_smuggler")))
+            .unwrap();
+        game.create_character(&builder("Sneaks", None)).unwrap();
+        assert_eq!(game.party().unwrap().members().len(), 2);
+        assert!(matches!(
+            game.create_character(&CharacterBuilder::with_rules(
+                CreationMode::Standard,
+                CreationRules::default()
+            )),
+            Err(GameError::Build(_))
+        ));
+
+        let entered = game.enter_location("testplace").unwrap();
+        assert!(!entered.messages.is_empty());
+        assert!(game.state().journal().has_visited("testplace"));
+        assert!(matches!(
+            game.choose("read_omens"),
+            Err(GameError::NoSuchChoice(_))
+        ));
+        // Uses up the rusty key
+        assert!(game.choose("leave").unwrap().go_to.is_none());
+
+        let check = game.skill_check(1, Skill::Stealth, 10).unwrap();
+        game.damage(1, 3).unwrap();
+        assert!(matches!(game.damage(5, 3), Err(GameError::NoSuchMember(5))));
+        game.give_item("frog_charm", 2).unwrap();
+
+        let events = events.borrow();
+        assert!(matches!(events[0], GameEvent::DiceRolled { .. }));
+        assert_eq!(
+            events[1..5],
+            [
+                GameEvent::CharacterCreated {
+                    name: String::from("Smuggs")
+                },
+                GameEvent::ItemGained {
+                    item: String::from("hooded_lantern"),
+                    count: 1
+                },
+                GameEvent::ItemGained {
+                    item: String::from("rope"),
+                    count: 1
+                },
+                GameEvent::CharacterCreated {
+                    name: String::from("Sneaks")
+                },
+            ]
+        );
+        assert_eq!(
+            events[5..8],
+            [
+                GameEvent::LocationEntered {
+                    location_id: String::from("testplace")
+                },
+                // From the on-enter script
+                GameEvent::ItemGained {
+                    item: String::from("rusty_key"),
+                    count: 1
+                },
+                GameEvent::DiceRolled {
+                    results: check.dice().clone()
+                },
+            ]
+        );
+        assert_eq!(
+            events[8..],
+            [
+                GameEvent::CheckMade {
+                    character: String::from("Sneaks"),
+                    check
+                },
+                GameEvent::DamageTaken {
+                    character: String::from("Sneaks"),
+                    amount: 3
+                },
+                GameEvent::ItemGained {
+                    item: String::from("frog_charm"),
+                    count: 2
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn abilities_can_be_used_outside_of_combat() {
+        let (mut game, events) = recorded_game();
+        let mut soothsayer = builder("Seer", None);
+        soothsayer.set_class(Class::Soothsayer);
+        game.create_character(&soothsayer).unwrap();
+        game.enter_location("testplace").unwrap();
+        game.damage(0, 3).unwrap();
+
+        let tended = game.choose("tend_wounds").unwrap();
+        assert!(tended.messages[0].starts_with("Seer used Mend ("));
+        let leader = game.party().unwrap().leader();
+        assert!(leader.health() > leader.max_health() - 3);
+        assert!(events.borrow().contains(&GameEvent::AbilityUsed {
+            character: String::from("Seer"),
+            ability: String::from("Mend")
+        }));
+        assert!(matches!(
+            game.choose("tend_wounds"),
+            Err(GameError::Ability(AbilityError::OnCooldown(_)))
+        ));
+        let err = game.choose("call_the_queen").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the party's leader has no summon_queen ability"
+        );
+
+        let abilities = ClassAbilities::try_load(Class::Soothsayer).unwrap();
+        assert!(matches!(
+            game.use_ability(0, abilities.get("fireball").unwrap(), None, None),
+            Err(GameError::Ability(AbilityError::NoTarget))
+        ));
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn status_effects_tick_while_exploring() {
+        let (mut game, events) = recorded_game();
+        game.create_character(&builder("Smuggs", None)).unwrap();
+        let leader = game.party_mut().unwrap().leader_mut();
+        leader.apply_status_effect(StatusEffect::new(StatusKind::Poisoned, 1));
+        let health = leader.health();
+
+        let entered = game.enter_location("testplace").unwrap();
+        let damage = health - game.party().unwrap().leader().health();
+        assert!((1..=4).contains(&damage));
+        assert_eq!(
+            entered.messages[..2],
+            [
+                format!("Smuggs is Poisoned: took {damage} damage"),
+                String::from("Smuggs is no longer Poisoned"),
+            ]
+        );
+        // The turn passes once the on-enter script has handed over the rusty key
+        let events = events.borrow();
+        assert!(matches!(events[2], GameEvent::ItemGained { .. }));
+        assert!(matches!(events[3], GameEvent::DiceRolled { .. }));
+        assert_eq!(
+            events[4],
+            GameEvent::DamageTaken {
+                character: String::from("Smuggs"),
+                amount: damage
+            }
+        );
+
+        assert!(game.pass_turn().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn quests_advance_as_the_party_plays() {
+        let mut game = Game::default();
+        game.create_character(&builder("Smuggs", None)).unwrap();
+        game.enter_location("testplace").unwrap();
+
+        // The corridor starts the quest, whose first stage is already done
+        let entered = game.enter_location("testcorridor").unwrap();
+        assert_eq!(
+            entered.messages,
+            [
+                "New quest: Mossback's Secret",
+                "Journal updated: Find your way out of the rusty laboratory.",
+                "Journal updated: Old Mossback knows more about the bog than he lets on. Ask him \
+                 about it, and earn his trust.",
+            ]
+        );
+        assert!(
+            game.enter_location("testcorridor")
+                .unwrap()
+                .messages
+                .is_empty()
+        );
+
+        game.state_mut().set_flag("asked_about_bog");
+        game.give_item("frog_charm", 1).unwrap();
+        let advanced = game.update_quests().unwrap();
+        assert_eq!(advanced.len(), 1);
+        assert!(advanced[0].contains("A bog lurker"));
+
+        let mut combat = Combat::new(vec![
+            crate::combat::Creature::try_load("bog_lurker").unwrap(),
+        ]);
+        assert!(game.end_combat(&combat).unwrap().is_empty());
+        while combat.outcome(game.party().unwrap().members()) == CombatOutcome::Ongoing {
+            game.attack(&mut combat, 0, 0).unwrap();
+        }
+        let ended = game.end_combat(&combat).unwrap();
+        assert_eq!(
+            ended[0],
+            format!("Victory! Everyone earned {} XP.", combat.experience())
+        );
+        assert!(ended.contains(&String::from(
+            "Quest complete: Mossback's Secret! Everyone earned 150 XP."
+        )));
+        assert_eq!(game.state().journal().defeated("bog_lurker"), 1);
+        assert!(
+            game.state()
+                .journal()
+                .quest("mossbacks_secret")
+                .unwrap()
+                .is_completed()
+        );
+        let party = game.party().unwrap();
+        assert_eq!(party.leader().experience(), combat.experience() + 150);
+        assert!(party.inventory().contains("bogbane"));
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn enemy_attacks_are_reported() {
+        let (mut game, events) = recorded_game();
+        game.create_character(&builder("Smuggs", None)).unwrap();
+        let mut combat = Combat::new(vec![
+            crate::combat::Creature::try_load("swamp_rat").unwrap(),
+        ]);
+        let attacks = game.enemy_turn(&mut combat).unwrap();
+        assert_eq!(attacks.len(), 1);
+        assert_eq!(
+            events.borrow()[1..],
+            [
+                GameEvent::DiceRolled {
+                    results: attacks[0].rolls.clone()
+                },
+                GameEvent::DamageTaken {
+                    character: String::from("Smuggs"),
+                    amount: attacks[0].damage
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_and_dispositions() {
+        let mut state = GameState::default();
+        assert!(!state.has_flag("met_mossback"));
+        state.set_flag("met_mossback");
+        state.set_flag("found_key");
+        assert!(state.has_flag("met_mossback"));
+        state.clear_flag("found_key");
+        assert_eq!(state.flags().collect::<Vec<_>>(), ["met_mossback"]);
+
+        assert_eq!(state.disposition("mossback"), 0);
+        state.change_disposition("mossback", 2);
+        state.change_disposition("mossback", -5);
+        assert_eq!(state.disposition("mossback"), -3);
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn save_and_load() {
+        let mut state = GameState::default();
+        state.set_flag("asked_about_bog");
+        state.change_disposition("old_mossback", 1);
+        state.journal_mut().record_visit("testplace");
+        state.journal_mut().record_defeat("swamp_rat");
+        state.record_roll(RollRecord {
+            dice: String::from("2d6 + 1"),
+            results: String::from("3+4=7"),
+            total: 8,
+        });
+
+        let mut path = Utf8PathBuf::try_from(std::env::temp_dir()).unwrap();
+        path.push(format!("swampqueen-game-test-{}.json", std::process::id()));
+        state.try_save(&path).unwrap();
+        let loaded = GameState::try_load(&path).unwrap();
+        // A save from before parties were saved still loads
+        let saved = SavedGame::try_load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(loaded, state);
+        assert_eq!(saved.state(), &state);
+        assert!(saved.party().is_none());
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn resume_where_the_party_left_off() {
+        let mut game = Game::default();
+        game.create_character(&builder("Smuggs", Some("bog_smuggler")))
+            .unwrap();
+        game.create_character(&builder("Sneaks", None)).unwrap();
+        game.enter_location("testplace").unwrap();
+        game.go("north").unwrap();
+        game.damage(1, 3).unwrap();
+        game.give_item("frog_charm", 2).unwrap();
+
+        let mut path = Utf8PathBuf::try_from(std::env::temp_dir()).unwrap();
+        path.push(format!(
+            "swampqueen-resume-test-{}.json",
+            std::process::id()
+        ));
+        game.save().try_save(&path).unwrap();
+        let saved = SavedGame::try_load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(saved.location(), Some("testcorridor"));
+
+        let resumed = Game::resume(saved).unwrap();
+        assert_eq!(resumed.location().unwrap().id(), "testcorridor");
+        assert_eq!(resumed.party(), game.party());
+        assert_eq!(resumed.state(), game.state());
+        assert!(resumed.state().journal().has_visited("testcorridor"));
+    }
+
+    #[test]
+    fn roll_history_is_capped() {
+        let mut game = Game::default();
+        for _ in 0..=ROLL_HISTORY_LENGTH {
+            game.roll("1d6 + 2").unwrap();
+        }
+        let history = game.state().roll_history();
+        assert_eq!(history.len(), ROLL_HISTORY_LENGTH);
+        assert_eq!(history[0].dice, "1d6 + 2");
+        assert!(
+            history[0]
+                .to_string()
+                .ends_with(&format!(", total {}", history[0].total))
+        );
+    }
+}
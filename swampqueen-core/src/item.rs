@@ -0,0 +1,120 @@
+//! Items and the [`Inventory`] that holds them. Items are referred to by their ID.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A collection of items, each with a count.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Inventory {
+    items: BTreeMap<String, usize>,
+}
+impl Inventory {
+    /// Add `count` of the item with the given ID.
+    pub fn add(&mut self, item_id: &str, count: usize) {
+        if count > 0 {
+            *self.items.entry(String::from(item_id)).or_default() += count;
+        }
+    }
+
+    /// Remove `count` of the item with the given ID. Returns `false` (removing nothing) if there
+    /// aren't that many.
+    pub fn remove(&mut self, item_id: &str, count: usize) -> bool {
+        let Some(held) = self.items.get_mut(item_id) else {
+            return count == 0;
+        };
+        if *held < count {
+            return false;
+        }
+        *held -= count;
+        if *held == 0 {
+            self.items.remove(item_id);
+        }
+        true
+    }
+
+    /// How many of the item with the given ID are held.
+    #[must_use]
+    pub fn count(&self, item_id: &str) -> usize {
+        self.items.get(item_id).copied().unwrap_or_default()
+    }
+
+    /// Whether at least one of the item with the given ID is held.
+    #[must_use]
+    pub fn contains(&self, item_id: &str) -> bool {
+        self.count(item_id) > 0
+    }
+
+    /// Move everything in `other` into this inventory.
+    pub fn merge(&mut self, other: Self) {
+        for (item_id, count) in other.items {
+            self.add(&item_id, count);
+        }
+    }
+
+    /// Iterate over the ID and count of every held item, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, usize)> {
+        self.items.iter().map(|(id, &count)| (id.as_str(), count))
+    }
+
+    /// Whether nothing is held.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+impl Display for Inventory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return write!(f, "(empty)");
+        }
+        let mut first = true;
+        for (item_id, count) in self.iter() {
+            if !first {
+                write!(f, ", ")?;
+            }
+            first = false;
+            if count > 1 {
+                write!(f, "{item_id} x{count}")?;
+            } else {
+                write!(f, "{item_id}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove_items() {
+        let mut inventory = Inventory::default();
+        inventory.add("lantern", 1);
+        inventory.add("rope", 2);
+        inventory.add("rope", 1);
+        inventory.add("nothing", 0);
+        assert_eq!(inventory.count("rope"), 3);
+        assert!(!inventory.contains("nothing"));
+
+        assert!(!inventory.remove("rope", 4));
+        assert!(inventory.remove("rope", 3));
+        assert!(!inventory.contains("rope"));
+        assert!(!inventory.remove("rope", 1));
+        assert_eq!(inventory.to_string(), "lantern");
+    }
+
+    #[test]
+    fn merge_inventories() {
+        let mut first = Inventory::default();
+        first.add("eel", 1);
+        let mut second = Inventory::default();
+        second.add("eel", 2);
+        second.add("moss", 1);
+        first.merge(second);
+        assert_eq!(first.to_string(), "eel x3, moss");
+    }
+}
@@ -1,4 +1,22 @@
 //! Library crate for [swampqueen](https://github.com/OrigamiEnderdragon/swampqueen).
+//!
+//! Everything beyond the rules themselves is behind a default feature, so that tools needing only
+//! [`dice`] or [`character`] can turn them off, as can builds with no terminal or filesystem, such
+//! as in a browser:
+//!
+//! * `serde` - Serialization of the game's types, loading JSON [content], and the typed
+//!   [`command`]s that explore it.
+//! * `fs-content` - Load content from, and save games to, the filesystem. Without it, content must
+//!   be [provided](content::provide) as strings or embedded. Requires `serde`.
+//! * `embedded-content` (not default) - Build the default adventure's content into the binary, so
+//!   it can be played without the content directory. Files on disk are still used first. Requires
+//!   `serde`.
+//! * `tui-prompts` - Terminal prompts in [`ui`], and, with `serde`, the [`play`] flow that uses
+//!   them.
+//!
+//! CI tests the default features, each of `serde`, `embedded-content` and `tui-prompts` on its
+//! own, none at all, and the default features with `embedded-content`. Tests that load content
+//! from the test files only run with `fs-content`.
 #![warn(missing_docs)]
 #![warn(missing_debug_implementations)]
 #![warn(rust_2018_idioms)]
@@ -9,8 +27,34 @@
 #![warn(clippy::todo)]
 #![warn(clippy::dbg_macro)]
 
+pub mod ability;
+pub mod adventure;
+pub mod background;
 pub mod character;
+pub mod check;
+pub mod combat;
+#[cfg(feature = "serde")]
+pub mod command;
+pub mod condition;
+pub mod content;
+pub mod creation;
+pub mod dialogue;
 pub mod dice;
+pub mod event;
+#[cfg(feature = "fs-content")]
 mod files;
+pub mod game;
+pub mod item;
 pub mod location;
+pub mod party;
+#[cfg(all(feature = "serde", feature = "tui-prompts"))]
+pub mod play;
+pub mod progression;
+pub mod quest;
+pub mod script;
+pub mod skill;
+pub mod status;
+#[cfg(all(feature = "fs-content", feature = "tui-prompts"))]
+pub mod transcript;
+#[cfg(feature = "tui-prompts")]
 pub mod ui;
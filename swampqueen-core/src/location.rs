@@ -1,66 +1,285 @@
 //! All functionality related to in-game locations. This module is centred around the [`Location`]
 //! type, which stores all data related to that location.
 
-use std::{collections::HashMap, io, sync::LazyLock};
+use std::collections::{BTreeMap, HashMap};
 
-use camino::Utf8PathBuf;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::files::try_load_json;
-
-// TODO this path is only for testing purposes; eventually it will need to be determined based on
-// config and the like
-static LOCATION_DIR: LazyLock<Utf8PathBuf> = LazyLock::new(|| {
-    let mut path = Utf8PathBuf::new();
-    path.push(env!("CARGO_MANIFEST_DIR"));
-    path.push("testfiles/locations/");
-    path
-});
+#[cfg(feature = "serde")]
+use crate::content::{ContentKind, load_json};
+use crate::{
+    condition::{Condition, ConditionContext},
+    game::GameState,
+    party::Party,
+    script::{Script, ScriptError, ScriptOutcome},
+};
 
 /// A given location within the game.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Location {
     id: String,
     name: String,
-    text: HashMap<String, Vec<String>>,
+    text: HashMap<String, Vec<Paragraph>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    choices: Vec<Choice>,
+    /// The IDs of neighbouring locations, by the direction they lie in.
+    #[cfg_attr(feature = "serde", serde(default))]
+    exits: BTreeMap<String, String>,
+    /// The IDs of items lying here for the party to take.
+    #[cfg_attr(feature = "serde", serde(default))]
+    items: Vec<String>,
+    /// The IDs of the NPCs here for the party to talk to.
+    #[cfg_attr(feature = "serde", serde(default))]
+    npcs: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    scripts: LocationScripts,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quest: Option<String>,
 }
 impl Location {
-    /// Load a [`Location`] from the filesystem.
+    /// Load a [`Location`] from the game's [content](crate::content).
     ///
     /// # Arguments
     ///
-    /// * `location_id` - A [`std::str`] corresponding to the ID of the desired location. The ID
-    ///   must match the name of the location `.json` file on the disk.
+    /// * `location_id` - A [`std::str`] corresponding to the ID of the desired location. In a
+    ///   content directory, this is the name of the location's `.json` file.
     ///
     /// # Errors
     ///
-    /// This function returns errors typical to loading and deserializing a struct from the disk
-    /// (file path does not exist, deserialization failure, filesystem error, etc.).
-    pub fn try_load_location(location_id: &str) -> io::Result<Self> {
-        let path = Self::get_location_path(location_id);
-        try_load_json(path)
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load_location(location_id: &str) -> std::io::Result<Self> {
+        load_json(ContentKind::Location, location_id)
+    }
+
+    /// The ID of this location.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The name shown to the player.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The key of every list of text at this location, such as `intro`, in alphabetical order.
+    pub fn text_keys(&self) -> impl Iterator<Item = &str> {
+        let mut keys: Vec<&str> = self.text.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        keys.into_iter()
+    }
+
+    /// The directions the party can leave in, in alphabetical order.
+    pub fn exits(&self) -> impl Iterator<Item = &str> {
+        self.exits.keys().map(String::as_str)
     }
 
-    /// Get a paragraph of text from the list with the given `key`.
+    /// The ID of the location in the given direction, if there is one.
+    #[must_use]
+    pub fn exit(&self, direction: &str) -> Option<&str> {
+        self.exits.get(direction).map(String::as_str)
+    }
+
+    /// The IDs of the items that start out lying here, whether or not they've been taken.
+    #[must_use]
+    pub fn items(&self) -> &[String] {
+        &self.items
+    }
+
+    /// The IDs of the NPCs here, whom the party can [talk to](crate::game::Game::talk).
+    #[must_use]
+    pub fn npcs(&self) -> &[String] {
+        &self.npcs
+    }
+
+    /// The ID of the quest started when the party enters, if any; see
+    /// [`Game::enter_location`](crate::game::Game::enter_location).
+    #[must_use]
+    pub fn quest(&self) -> Option<&str> {
+        self.quest.as_deref()
+    }
+
+    /// Get a paragraph of text from the list with the given `key`, whether or not any character
+    /// would see it.
     pub fn paragraph(&self, key: &str, paragraph_index: usize) -> Option<&str> {
         self.text
             .get(key)
             .and_then(|paragraphs| paragraphs.get(paragraph_index))
-            .map(String::as_str)
+            .map(Paragraph::text)
+    }
+
+    /// Get every paragraph of text from the list with the given `key` whose condition holds, in
+    /// order.
+    pub fn paragraphs_for<'a>(
+        &'a self,
+        key: &str,
+        context: &'a ConditionContext<'a>,
+    ) -> impl Iterator<Item = &'a str> {
+        self.text
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter(|paragraph| paragraph.is_shown(context))
+            .map(Paragraph::text)
+    }
+
+    /// Every choice whose condition holds at this location, in order.
+    pub fn choices_for<'a>(
+        &'a self,
+        context: &'a ConditionContext<'a>,
+    ) -> impl Iterator<Item = &'a Choice> {
+        self.choices
+            .iter()
+            .filter(|choice| choice.requires.evaluate(context))
+    }
+
+    /// Run this location's on-enter script, if it has one.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`ScriptError`] if the script can't be loaded or fails.
+    pub fn enter(
+        &self,
+        party: &mut Party,
+        state: &mut GameState,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        Self::run_script(self.scripts.on_enter.as_deref(), party, state, None)
     }
 
-    /// Helper function to construct the [`Location`] path from the given ID.
-    fn get_location_path(location_id: &str) -> Utf8PathBuf {
-        let mut path: Utf8PathBuf = LOCATION_DIR.clone();
-        path.push(format!("{location_id}.json"));
-        path
+    /// Run this location's on-choice script, if it has one, for the choice with the given ID.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`ScriptError`] if the script can't be loaded or fails.
+    pub fn choose(
+        &self,
+        choice_id: &str,
+        party: &mut Party,
+        state: &mut GameState,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        Self::run_script(
+            self.scripts.on_choice.as_deref(),
+            party,
+            state,
+            Some(choice_id),
+        )
     }
+
+    fn run_script(
+        script_id: Option<&str>,
+        party: &mut Party,
+        state: &mut GameState,
+        choice: Option<&str>,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        match script_id {
+            Some(script_id) => Script::try_load(script_id)?.run(party, state, choice),
+            None => Ok(ScriptOutcome::default()),
+        }
+    }
+}
+
+/// The IDs of the [`Script`]s run on a [`Location`]'s events.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LocationScripts {
+    /// Run when the party enters the location.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub on_enter: Option<String>,
+    /// Run when the party makes a [`Choice`], with its ID as `choice`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub on_choice: Option<String>,
 }
 
-#[cfg(test)]
+/// A paragraph of location text: either plain text everyone sees, or text only shown when a
+/// [`Condition`] holds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+#[cfg_attr(not(feature = "serde"), allow(dead_code))]
+enum Paragraph {
+    Plain(String),
+    Conditional { text: String, requires: Condition },
+}
+impl Paragraph {
+    fn text(&self) -> &str {
+        match self {
+            Self::Plain(text) | Self::Conditional { text, .. } => text,
+        }
+    }
+
+    fn is_shown(&self, context: &ConditionContext<'_>) -> bool {
+        match self {
+            Self::Plain(_) => true,
+            Self::Conditional { requires, .. } => requires.evaluate(context),
+        }
+    }
+}
+
+/// Something the player can choose to do at a [`Location`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Choice {
+    id: String,
+    text: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    requires: Condition,
+    #[cfg_attr(feature = "serde", serde(default))]
+    ability: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    quest: Option<String>,
+}
+impl Choice {
+    /// The ID of this choice, unique within its location.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The text shown to the player.
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// What must hold for this choice to be offered.
+    #[must_use]
+    pub const fn condition(&self) -> &Condition {
+        &self.requires
+    }
+
+    /// The ID of an ability of the party leader's class that the leader uses when this choice is
+    /// made, if any; see [`Game::choose`](crate::game::Game::choose).
+    #[must_use]
+    pub fn ability(&self) -> Option<&str> {
+        self.ability.as_deref()
+    }
+
+    /// The ID of the quest started when this choice is made, if any.
+    #[must_use]
+    pub fn quest(&self) -> Option<&str> {
+        self.quest.as_deref()
+    }
+}
+impl From<&Choice> for String {
+    fn from(value: &Choice) -> Self {
+        value.text.clone()
+    }
+}
+
+#[cfg(all(test, feature = "fs-content"))]
 #[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
+    use crate::{
+        character::{Character, Class, Race, Stat},
+        game::GameState,
+        party::Party,
+    };
 
     const TESTPLACE_ID: &str = "testplace";
     const TESTPLACE_NAME: &str = "Test Place";
@@ -77,9 +296,18 @@ mod tests {
         let intro_text = location.text.get("intro").unwrap();
         assert_eq!(location.id, TESTPLACE_ID);
         assert_eq!(location.name, TESTPLACE_NAME);
-        assert_eq!(intro_text[0], TESTPLACE_P0);
-        assert_eq!(intro_text[1], TESTPLACE_P1);
-        assert_eq!(intro_text[2], TESTPLACE_P2);
+        assert_eq!(intro_text[0].text(), TESTPLACE_P0);
+        assert_eq!(intro_text[1].text(), TESTPLACE_P1);
+        assert_eq!(intro_text[4].text(), TESTPLACE_P2);
+        assert_eq!(location.choices.len(), 5);
+        assert_eq!(location.exits().collect::<Vec<_>>(), ["north"]);
+        assert_eq!(location.exit("north"), Some("testcorridor"));
+        assert_eq!(location.exit("south"), None);
+        assert_eq!(location.items(), ["frayed_cable"]);
+        assert_eq!(
+            location.text_keys().collect::<Vec<_>>(),
+            ["intro", "lights", "loudspeaker"]
+        );
     }
 
     #[test]
@@ -88,6 +316,102 @@ mod tests {
         let testplace: Location = serde_json::from_str(&file_str).unwrap();
         assert_eq!(testplace.paragraph("intro", 0).unwrap(), TESTPLACE_P0);
         assert_eq!(testplace.paragraph("intro", 1).unwrap(), TESTPLACE_P1);
-        assert_eq!(testplace.paragraph("intro", 2).unwrap(), TESTPLACE_P2);
+        assert_eq!(testplace.paragraph("intro", 4).unwrap(), TESTPLACE_P2);
+    }
+
+    #[test]
+    fn paragraphs_depend_on_race() {
+        let testplace = Location::try_load_location(TESTPLACE_ID).unwrap();
+        let state = GameState::default();
+        let fae = Party::new(Character::new(
+            "Buzz",
+            Class::Warden,
+            Race::InsectoidFae,
+            Stat::Cunning,
+            Stat::TheSight,
+        ));
+        let goblin = Party::new(Character::new(
+            "Grub",
+            Class::Warden,
+            Race::GoblinoidFae,
+            Stat::Cunning,
+            Stat::TheSight,
+        ));
+
+        let fae = ConditionContext::new(&fae, &state);
+        let goblin = ConditionContext::new(&goblin, &state);
+        let fae_intro: Vec<&str> = testplace.paragraphs_for("intro", &fae).collect();
+        assert_eq!(fae_intro.len(), 4);
+        assert!(fae_intro[2].contains("antennae"));
+        assert_eq!(fae_intro[3], TESTPLACE_P2);
+
+        let goblin_intro: Vec<&str> = testplace.paragraphs_for("intro", &goblin).collect();
+        assert_eq!(goblin_intro, [TESTPLACE_P0, TESTPLACE_P1, TESTPLACE_P2]);
+        assert_eq!(testplace.paragraphs_for("outro", &goblin).count(), 0);
+    }
+
+    #[test]
+    fn choices_depend_on_class_and_stats() {
+        let testplace = Location::try_load_location(TESTPLACE_ID).unwrap();
+        let state = GameState::default();
+        let bastion = Party::new(Character::new(
+            "Brick",
+            Class::Bastion,
+            Race::AlligatorFolk,
+            Stat::Bulk,
+            Stat::Backbone,
+        ));
+        let soothsayer = Party::new(Character::new(
+            "Seer",
+            Class::Soothsayer,
+            Race::InsectoidFae,
+            Stat::TheSight,
+            Stat::Cunning,
+        ));
+
+        let ids = |party| {
+            let context = ConditionContext::new(party, &state);
+            testplace
+                .choices_for(&context)
+                .map(Choice::id)
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(ids(&bastion), ["leave", "smash_speaker"]);
+        assert_eq!(
+            ids(&soothsayer),
+            ["leave", "read_omens", "tend_wounds", "call_the_queen"]
+        );
+    }
+
+    #[test]
+    fn scripts_run_on_events() {
+        let testplace = Location::try_load_location(TESTPLACE_ID).unwrap();
+        let mut party = Party::new(Character::new(
+            "Scripty",
+            Class::Hunter,
+            Race::GoblinoidFae,
+            Stat::Bulk,
+            Stat::Backbone,
+        ));
+        let mut state = GameState::default();
+
+        let first = testplace.enter(&mut party, &mut state).unwrap();
+        assert!(first.messages[0].contains("rusty key"));
+        let second = testplace.enter(&mut party, &mut state).unwrap();
+        assert!(second.messages[0].contains("Scripty"));
+        assert_eq!(party.inventory().count("rusty_key"), 1);
+
+        let smash = testplace
+            .choose("smash_speaker", &mut party, &mut state)
+            .unwrap();
+        assert!(state.has_flag("speaker_smashed"));
+        assert_eq!(smash.go_to, None);
+
+        // The first attempt uses up the key; the second gets lost
+        let leave = testplace.choose("leave", &mut party, &mut state).unwrap();
+        assert_eq!(leave.go_to, None);
+        let leave = testplace.choose("leave", &mut party, &mut state).unwrap();
+        assert_eq!(leave.go_to.as_deref(), Some(TESTPLACE_ID));
     }
 }
@@ -0,0 +1,329 @@
+//! Parties: the group of [`Character`]s adventuring together. A [`Party`] has a leader, shares one
+//! [`Inventory`] between its members and keeps them in marching order, which is the order they are
+//! passed to [`Combat`](crate::combat::Combat) in.
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{character::Character, check::CheckResult, item::Inventory};
+
+/// The most [`Character`]s a [`Party`] can hold.
+pub const MAX_PARTY_SIZE: usize = 4;
+
+/// A group of adventurers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Party {
+    members: Vec<Character>,
+    leader: usize,
+    inventory: Inventory,
+}
+impl Party {
+    /// Create a party containing only its leader.
+    #[must_use]
+    pub fn new(leader: Character) -> Self {
+        let mut party = Self {
+            members: Vec::new(),
+            leader: 0,
+            inventory: Inventory::default(),
+        };
+        party.join(leader);
+        party
+    }
+
+    /// Move a character into the party, adding their items to the shared inventory.
+    fn join(&mut self, mut character: Character) {
+        self.inventory
+            .merge(std::mem::take(character.inventory_mut()));
+        self.members.push(character);
+    }
+
+    /// Add a character to the back of the marching order.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`PartyError::Full`] (handing the character back) if the party
+    /// already has [`MAX_PARTY_SIZE`] members.
+    pub fn add(&mut self, character: Character) -> Result<(), PartyError> {
+        if self.is_full() {
+            return Err(PartyError::Full(Box::new(character)));
+        }
+        self.join(character);
+        Ok(())
+    }
+
+    /// Remove the member at `index` from the party. Their items stay in the shared inventory. The
+    /// leader cannot be removed; choose a new leader first.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`PartyError`] if there is no such member or they lead the party.
+    pub fn remove(&mut self, index: usize) -> Result<Character, PartyError> {
+        if index >= self.members.len() {
+            return Err(PartyError::NoSuchMember(index));
+        }
+        if index == self.leader {
+            return Err(PartyError::RemovingLeader);
+        }
+        if index < self.leader {
+            self.leader -= 1;
+        }
+        Ok(self.members.remove(index))
+    }
+
+    /// Whether the party has [`MAX_PARTY_SIZE`] members.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.members.len() >= MAX_PARTY_SIZE
+    }
+
+    /// Every member, in marching order.
+    #[must_use]
+    pub fn members(&self) -> &[Character] {
+        &self.members
+    }
+
+    /// Every member, in marching order, mutably. Pass this to [`Combat`](crate::combat::Combat).
+    pub fn members_mut(&mut self) -> &mut [Character] {
+        &mut self.members
+    }
+
+    /// The party's leader.
+    #[must_use]
+    pub fn leader(&self) -> &Character {
+        &self.members[self.leader]
+    }
+
+    /// The leader's index in the marching order.
+    #[must_use]
+    pub const fn leader_index(&self) -> usize {
+        self.leader
+    }
+
+    /// The party's leader, mutably.
+    pub fn leader_mut(&mut self) -> &mut Character {
+        &mut self.members[self.leader]
+    }
+
+    /// Make the member at `index` the leader. Returns `false` if there is no such member.
+    pub fn set_leader(&mut self, index: usize) -> bool {
+        if index >= self.members.len() {
+            return false;
+        }
+        self.leader = index;
+        true
+    }
+
+    /// Swap the places of two members in the marching order. Returns `false` if either index is
+    /// out of range.
+    pub fn swap(&mut self, a: usize, b: usize) -> bool {
+        if a >= self.members.len() || b >= self.members.len() {
+            return false;
+        }
+        self.members.swap(a, b);
+        if self.leader == a {
+            self.leader = b;
+        } else if self.leader == b {
+            self.leader = a;
+        }
+        true
+    }
+
+    /// The items shared by the whole party.
+    #[must_use]
+    pub const fn inventory(&self) -> &Inventory {
+        &self.inventory
+    }
+
+    /// The items shared by the whole party, mutably.
+    pub const fn inventory_mut(&mut self) -> &mut Inventory {
+        &mut self.inventory
+    }
+
+    /// Have every standing member make a check, keeping only the best result. Returns [`None`] if
+    /// everyone is down.
+    pub fn best_check<F>(&self, roll: F) -> Option<(&Character, CheckResult)>
+    where
+        F: Fn(&Character) -> CheckResult,
+    {
+        self.standing()
+            .map(|member| (member, roll(member)))
+            .max_by_key(|(_, check)| check.total())
+    }
+
+    /// Have every standing member make a check. The party passes if at least half of them do.
+    pub fn group_check<F>(&self, roll: F) -> GroupCheck<'_>
+    where
+        F: Fn(&Character) -> CheckResult,
+    {
+        GroupCheck {
+            results: self
+                .standing()
+                .map(|member| (member, roll(member)))
+                .collect(),
+        }
+    }
+
+    /// Every member who isn't down, in marching order.
+    fn standing(&self) -> impl Iterator<Item = &Character> {
+        self.members.iter().filter(|member| !member.is_down())
+    }
+}
+
+/// The result of a [`Party::group_check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupCheck<'a> {
+    /// Each standing member's check, in marching order.
+    pub results: Vec<(&'a Character, CheckResult)>,
+}
+impl GroupCheck<'_> {
+    /// The number of members who passed.
+    #[must_use]
+    pub fn passes(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|(_, check)| check.passed())
+            .count()
+    }
+
+    /// Whether at least half of the members passed.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        !self.results.is_empty() && self.passes() * 2 >= self.results.len()
+    }
+}
+impl Display for GroupCheck<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (member, check) in &self.results {
+            writeln!(f, "{}: {check}", member.name())?;
+        }
+        write!(
+            f,
+            "{}/{} passed: {}",
+            self.passes(),
+            self.results.len(),
+            if self.passed() { "passed" } else { "failed" }
+        )
+    }
+}
+
+/// The reasons a [`Party`] cannot be changed as requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartyError {
+    /// The party already has [`MAX_PARTY_SIZE`] members; the character who couldn't join is
+    /// handed back.
+    Full(Box<Character>),
+    /// There is no member at the given index.
+    NoSuchMember(usize),
+    /// The leader cannot leave the party.
+    RemovingLeader,
+}
+impl Display for PartyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Full(character) => write!(
+                f,
+                "the party already has {MAX_PARTY_SIZE} members, so {} can't join",
+                character.name()
+            ),
+            Self::NoSuchMember(index) => write!(f, "there is no party member {index}"),
+            Self::RemovingLeader => write!(f, "the party's leader can't leave"),
+        }
+    }
+}
+impl std::error::Error for PartyError {}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::character::{Class, Race, Stat};
+    #[cfg(feature = "fs-content")]
+    use crate::{
+        background::Background,
+        character::CharacterBuilder,
+        creation::{CreationMode, CreationRules},
+    };
+
+    fn member(name: &str, class: Class) -> Character {
+        Character::new(name, class, Race::AlligatorFolk, Stat::Bulk, Stat::Backbone)
+    }
+
+    #[test]
+    fn party_size_and_leader() {
+        let mut party = Party::new(member("Ada", Class::Hunter));
+        for name in ["Bo", "Cy", "Di"] {
+            party.add(member(name, Class::Warden)).unwrap();
+        }
+        assert!(party.is_full());
+        assert!(matches!(
+            party.add(member("Ed", Class::Bastion)),
+            Err(PartyError::Full(character)) if character.name() == "Ed"
+        ));
+
+        assert_eq!(party.leader().name(), "Ada");
+        assert_eq!(party.remove(0), Err(PartyError::RemovingLeader));
+        assert!(party.set_leader(2));
+        assert_eq!(party.remove(0).unwrap().name(), "Ada");
+        assert_eq!(party.leader().name(), "Cy");
+        assert_eq!(party.remove(7), Err(PartyError::NoSuchMember(7)));
+
+        assert!(party.swap(0, 1));
+        let order: Vec<&str> = party.members().iter().map(Character::name).collect();
+        assert_eq!(order, ["Cy", "Bo", "Di"]);
+        assert_eq!(party.leader().name(), "Cy");
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn inventories_are_shared() {
+        let mut builder =
+            CharacterBuilder::with_rules(CreationMode::Standard, CreationRules::default());
+        builder
+            .set_name("Smuggs")
+            .unwrap()
+            .set_class(Class::Trespasser)
+            .set_race(Race::GoblinoidFae)
+            .set_background(Some(Background::try_load("bog_smuggler").unwrap()));
+        builder.add_bonus_stat(Stat::Cunning).unwrap();
+        builder.add_bonus_stat(Stat::Bulk).unwrap();
+        let smuggler = builder.build().unwrap();
+
+        let mut party = Party::new(member("Ada", Class::Hunter));
+        party.add(smuggler).unwrap();
+        assert!(party.inventory().contains("hooded_lantern"));
+        assert!(party.members()[1].inventory().is_empty());
+    }
+
+    #[test]
+    fn party_checks() {
+        let mut party = Party::new(member("Ada", Class::Hunter));
+        party.add(member("Bo", Class::Bastion)).unwrap();
+        party.add(member("Cy", Class::Warden)).unwrap();
+
+        let best = party
+            .best_check(|member| member.check(Stat::Bulk, 10))
+            .unwrap();
+        assert_eq!(best.1.difficulty(), 10);
+        assert!(party.members().contains(best.0));
+        let group = party.group_check(|member| member.check(Stat::Bulk, 10));
+        assert_eq!(group.results.len(), 3);
+
+        // Impossible and trivial checks
+        assert!(!party.group_check(|m| m.check(Stat::Bulk, 100)).passed());
+        assert!(party.group_check(|m| m.check(Stat::Bulk, -100)).passed());
+
+        party.members_mut()[1].take_damage(100);
+        assert_eq!(
+            party.group_check(|m| m.check(Stat::Bulk, 10)).results.len(),
+            2
+        );
+        for member in party.members_mut() {
+            member.take_damage(100);
+        }
+        assert!(party.best_check(|m| m.check(Stat::Bulk, 10)).is_none());
+        assert!(!party.group_check(|m| m.check(Stat::Bulk, -100)).passed());
+    }
+}
@@ -0,0 +1,509 @@
+//! Playing an adventure through a [`Frontend`]: creating the party, exploring from the
+//! adventure's first location by menu, then by typed [commands](crate::command). This is the flow
+//! the desktop client runs in a terminal, and the one [transcripts](crate::transcript) replay in
+//! tests.
+
+use std::{fmt::Display, io};
+
+use cli_prompts::prompts::AbortReason;
+
+use crate::{
+    ability::ClassAbilities,
+    adventure::Adventure,
+    background::Background,
+    character::{
+        BuildError, BuildStep, CharacterBuilder, Class, Race, Stat, StatValues, validate_name,
+    },
+    combat::{Combat, CombatOutcome, Creature},
+    command::{Command, Vocabulary, execute, introduce, parse},
+    creation::CreationMode,
+    game::{Game, GameError},
+    party::Party,
+    script::ScriptOutcome,
+    ui::{Frontend, level_up},
+};
+
+/// The option offered in every creation prompt for going back a step.
+const BACK: &str = "<- Back";
+
+/// The option offered at every location for ending exploration.
+const MOVE_ON: &str = "Move on";
+
+/// Play an adventure from the start: create a party, explore until the player moves on, then take
+/// typed commands until the player quits.
+///
+/// # Errors
+///
+/// This function returns a [`PlayError`] if the player stops answering or the game can't go on.
+pub fn play<F: Frontend>(frontend: &mut F, adventure: &Adventure) -> Result<Game, PlayError> {
+    let mut game = Game::default();
+    recruit_party(frontend, &mut game, adventure)?;
+    explore(frontend, &mut game, adventure.start_location())?;
+    command_prompt(frontend, &mut game)?;
+    Ok(game)
+}
+
+/// Create characters for the adventure until the party is full or the player sets off.
+///
+/// # Errors
+///
+/// This function returns a [`PlayError`] if the player stops answering or a character can't
+/// join the party.
+pub fn recruit_party<F: Frontend>(
+    frontend: &mut F,
+    game: &mut Game,
+    adventure: &Adventure,
+) -> Result<(), PlayError> {
+    game.create_character(&create_character(frontend, adventure)?)?;
+    while !game.party().is_some_and(Party::is_full) {
+        let options = [
+            String::from("Recruit another adventurer"),
+            String::from("Set off"),
+        ];
+        if frontend.select("Your party is ready?", &options)? != 0 {
+            break;
+        }
+        game.create_character(&create_character(frontend, adventure)?)?;
+    }
+
+    if let Some(party) = game.party() {
+        for member in party.members() {
+            frontend.show_character(member);
+        }
+        frontend.show_text(&format!("Shared inventory: {}", party.inventory()));
+    }
+    Ok(())
+}
+
+/// Run the character creation flow for the given adventure, one [`BuildStep`] at a time.
+///
+/// # Errors
+///
+/// This function returns a [`PlayError`] if the player stops answering or the adventure's
+/// backgrounds can't be loaded.
+pub fn create_character<F: Frontend>(
+    frontend: &mut F,
+    adventure: &Adventure,
+) -> Result<CharacterBuilder, PlayError> {
+    let mut builder = CharacterBuilder::new(adventure);
+    let backgrounds = Background::try_load_all()?;
+
+    while let Some(step) = builder.next_step() {
+        match step {
+            BuildStep::Name => {
+                let name: String = frontend.input_with("Character name", |input| {
+                    validate_name(input)
+                        .map(|()| String::from(input))
+                        .map_err(|e| e.to_string())
+                })?;
+                builder.set_name(&name)?;
+            }
+            BuildStep::Class => {
+                match select_or_back(frontend, "Please select your class", &Class::ALL)? {
+                    Some(i) => {
+                        builder.set_class(Class::ALL[i]);
+                    }
+                    None => {
+                        builder.undo();
+                    }
+                }
+            }
+            BuildStep::Race => {
+                match select_or_back(frontend, "Please select your race", &Race::ALL)? {
+                    Some(i) => {
+                        builder.set_race(Race::ALL[i]);
+                    }
+                    None => {
+                        builder.undo();
+                    }
+                }
+            }
+            BuildStep::Background => {
+                let mut labels: Vec<String> = backgrounds.iter().map(String::from).collect();
+                labels.push(String::from("None"));
+                match select_or_back(frontend, "Please select your background", &labels)? {
+                    Some(i) => {
+                        builder.set_background(backgrounds.get(i).cloned());
+                    }
+                    None => {
+                        builder.undo();
+                    }
+                }
+            }
+            BuildStep::BonusStat => {
+                match select_or_back(frontend, "Please select a bonus stat", &Stat::ALL)? {
+                    Some(i) => {
+                        if let Err(e) = builder.add_bonus_stat(Stat::ALL[i]) {
+                            frontend.show_text(&e.to_string());
+                        }
+                    }
+                    None => {
+                        builder.undo();
+                    }
+                }
+            }
+            BuildStep::PointBuy => buy_stats(frontend, &mut builder)?,
+            BuildStep::RollStats => roll_stats(frontend, &mut builder)?,
+        }
+    }
+
+    Ok(builder)
+}
+
+/// Ask how many points to spend on each stat in turn, within the adventure's point-buy budget.
+fn buy_stats<F: Frontend>(
+    frontend: &mut F,
+    builder: &mut CharacterBuilder,
+) -> Result<(), PlayError> {
+    let CreationMode::PointBuy {
+        budget,
+        max_per_stat,
+    } = *builder.mode()
+    else {
+        unreachable!("point buy is only offered in point-buy mode");
+    };
+    let mut purchased = StatValues::default();
+    for stat in Stat::ALL {
+        let remaining = budget - purchased.total();
+        let prompt = format!("{stat} (0-{max_per_stat}, {remaining} point(s) left)");
+        let value = frontend.input_with(&prompt, |input| match input.parse::<isize>() {
+            Ok(value) if !(0..=max_per_stat).contains(&value) => {
+                Err(format!("Must be between 0 and {max_per_stat}"))
+            }
+            Ok(value) if value > remaining => {
+                Err(format!("You only have {remaining} point(s) left"))
+            }
+            Ok(value) => Ok(value),
+            Err(_) => Err("Please enter a number".into()),
+        })?;
+        purchased.set(stat, value);
+    }
+    builder.set_purchased_stats(purchased)?;
+    Ok(())
+}
+
+/// Roll stats, letting the player reroll them until they keep a roll or run out of rerolls.
+fn roll_stats<F: Frontend>(
+    frontend: &mut F,
+    builder: &mut CharacterBuilder,
+) -> Result<(), PlayError> {
+    loop {
+        let rolled = builder.roll_stats()?;
+        frontend.show_text(&rolled.stats().to_string());
+        if rolled.rerolls_left() == 0 {
+            return Ok(());
+        }
+        let options = [
+            String::from("Keep these stats"),
+            format!("Reroll ({} left)", rolled.rerolls_left()),
+        ];
+        if frontend.select("Keep or reroll?", &options)? == 0 {
+            return Ok(());
+        }
+        builder.reroll_stats();
+    }
+}
+
+/// Select one of `options` by index, or [`None`] if the player chose to go back.
+fn select_or_back<F, T>(
+    frontend: &mut F,
+    prompt: &str,
+    options: &[T],
+) -> Result<Option<usize>, AbortReason>
+where
+    F: Frontend,
+    for<'a> &'a T: Into<String>,
+{
+    let mut labels: Vec<String> = options.iter().map(Into::into).collect();
+    labels.push(String::from(BACK));
+    let chosen = frontend.select(prompt, &labels)?;
+    Ok((chosen < options.len()).then_some(chosen))
+}
+
+/// Enter a location and let the player make choices there, following the party wherever the
+/// location scripts send it, until the player moves on.
+///
+/// # Errors
+///
+/// This function returns a [`PlayError`] if the player stops answering, or a location can't be
+/// loaded or its scripts fail.
+pub fn explore<F: Frontend>(
+    frontend: &mut F,
+    game: &mut Game,
+    location_id: &str,
+) -> Result<(), PlayError> {
+    let mut outcome = enter(frontend, game, location_id)?;
+    loop {
+        if let Some(location_id) = outcome.go_to {
+            outcome = enter(frontend, game, &location_id)?;
+            continue;
+        }
+
+        let (Some(location), Some(context)) = (game.location(), game.condition_context()) else {
+            return Err(GameError::NoLocation.into());
+        };
+        let (ids, mut labels): (Vec<String>, Vec<String>) = location
+            .choices_for(&context)
+            .map(|choice| (String::from(choice.id()), String::from(choice)))
+            .unzip();
+        labels.push(String::from(MOVE_ON));
+        let Some(choice_id) = ids.get(frontend.select("Choose an action", &labels)?) else {
+            return Ok(());
+        };
+        outcome = game.choose(choice_id)?;
+        show_messages(frontend, &outcome);
+    }
+}
+
+/// Enter a location, showing its introduction and anything its script says.
+fn enter<F: Frontend>(
+    frontend: &mut F,
+    game: &mut Game,
+    location_id: &str,
+) -> Result<ScriptOutcome, PlayError> {
+    let outcome = game.enter_location(location_id)?;
+    for line in introduce(game) {
+        frontend.show_text(&line);
+    }
+    show_messages(frontend, &outcome);
+    Ok(outcome)
+}
+
+/// Carry on a [resumed](Game::resume) game: remind the player where the party is, then take
+/// typed commands until the player quits.
+///
+/// # Errors
+///
+/// This function returns a [`PlayError`] if the player stops answering.
+pub fn resume<F: Frontend>(frontend: &mut F, game: &mut Game) -> Result<(), PlayError> {
+    for line in introduce(game) {
+        frontend.show_text(&line);
+    }
+    command_prompt(frontend, game)
+}
+
+/// Let the player type [commands](crate::command) until they quit, asking again whenever a
+/// command can't be understood. A fight started in conversation is [fought](fight) straight
+/// away, and the prompt ends if the party is defeated.
+///
+/// # Errors
+///
+/// This function returns a [`PlayError`] if the player stops answering, or a fight's creatures
+/// can't be loaded.
+pub fn command_prompt<F: Frontend>(frontend: &mut F, game: &mut Game) -> Result<(), PlayError> {
+    frontend.show_text("Type \"help\" for a list of commands.");
+    loop {
+        let vocabulary = Vocabulary::new(game);
+        let command = frontend.input_with("What now?", |line| {
+            parse(line, &vocabulary).map_err(|err| err.to_string())
+        })?;
+        if command == Command::Quit {
+            return Ok(());
+        }
+        match execute(game, &command) {
+            Ok(lines) => {
+                for line in lines {
+                    frontend.show_text(&line);
+                }
+            }
+            Err(err) => frontend.show_text(&err.to_string()),
+        }
+        if let Some(creature_ids) = game.take_fight() {
+            let enemies = creature_ids
+                .iter()
+                .map(|id| Creature::try_load(id))
+                .collect::<Result<_, _>>()?;
+            if fight(frontend, game, enemies)? == CombatOutcome::Defeat {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Fight the given enemies until one side is beaten, asking what each standing party member
+/// does every round. On victory, experience is awarded, quests are advanced and the party
+/// spends any stat points they earned.
+///
+/// # Errors
+///
+/// This function returns a [`PlayError`] if the player stops answering, there is no party, or
+/// a class's abilities or a quest can't be loaded.
+pub fn fight<F: Frontend>(
+    frontend: &mut F,
+    game: &mut Game,
+    enemies: Vec<Creature>,
+) -> Result<CombatOutcome, PlayError> {
+    let mut combat = Combat::new(enemies);
+    let outcome = loop {
+        let outcome = combat.outcome(game.party().ok_or(GameError::NoParty)?.members());
+        if outcome != CombatOutcome::Ongoing {
+            break outcome;
+        }
+
+        frontend.show_text(&format!("--- Round {} ---", combat.round()));
+        for member in 0..game.party().ok_or(GameError::NoParty)?.members().len() {
+            let hero = &game.party().ok_or(GameError::NoParty)?.members()[member];
+            if hero.is_down() {
+                continue;
+            }
+            let Some((target, enemy)) = combat.standing_enemies().next() else {
+                break;
+            };
+            frontend.show_text(&format!(
+                "{} faces the {}.",
+                hero.name(),
+                String::from(enemy)
+            ));
+
+            let abilities = ClassAbilities::try_load(hero.class())?;
+            let usable: Vec<_> = abilities
+                .iter()
+                .filter(|ability| hero.can_use_ability(ability).is_ok())
+                .collect();
+            let mut actions = vec![String::from("Attack")];
+            actions.extend(usable.iter().map(|&ability| String::from(ability)));
+            let prompt = format!("What will {} do?", hero.name());
+            let action = frontend.select(&prompt, &actions)?;
+
+            if let Some(&ability) = action.checked_sub(1).and_then(|i| usable.get(i)) {
+                let used = game.use_ability(member, ability, Some(&mut combat), Some(target))?;
+                frontend.show_text(&match used.rolls {
+                    Some(rolls) => format!("{}! ({rolls}) for {}", ability.name(), used.amount),
+                    None => format!("{}!", ability.name()),
+                });
+            } else if let Some(attack) = game.attack(&mut combat, member, target)? {
+                frontend.show_text(&format!(
+                    "{} hits the {} for {} ({}).",
+                    attack.attacker, attack.target, attack.damage, attack.rolls
+                ));
+            }
+        }
+
+        for attack in game.enemy_turn(&mut combat)? {
+            frontend.show_text(&format!(
+                "The {} hits {} for {} ({}).",
+                attack.attacker, attack.target, attack.damage, attack.rolls
+            ));
+        }
+        let heroes = game.party_mut().ok_or(GameError::NoParty)?.members_mut();
+        for (name, tick) in combat.end_round(heroes).ticks {
+            for (kind, damage) in &tick.damage_rolls {
+                frontend.show_text(&format!("{name} is {kind}: took {damage} damage"));
+            }
+        }
+    };
+
+    if outcome == CombatOutcome::Victory {
+        for line in game.end_combat(&combat)? {
+            frontend.show_text(&line);
+        }
+        for hero in game.party_mut().ok_or(GameError::NoParty)?.members_mut() {
+            level_up(frontend, hero)?;
+        }
+    } else {
+        frontend.show_text("Your party has been defeated by the swamp...");
+    }
+    for hero in game.party().ok_or(GameError::NoParty)?.members() {
+        frontend.show_text(&format!(
+            "{}: HP {}/{}",
+            hero.name(),
+            hero.health(),
+            hero.max_health()
+        ));
+    }
+    Ok(outcome)
+}
+
+fn show_messages<F: Frontend>(frontend: &mut F, outcome: &ScriptOutcome) {
+    for message in &outcome.messages {
+        frontend.show_text(message);
+    }
+}
+
+/// The reasons playing through a [`Frontend`] can stop early.
+#[derive(Debug)]
+pub enum PlayError {
+    /// The player stopped answering.
+    Abort(AbortReason),
+    /// The game couldn't go on.
+    Game(GameError),
+    /// The character couldn't be built.
+    Build(BuildError),
+    /// A file couldn't be loaded.
+    Io(io::Error),
+}
+impl Display for PlayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Abort(AbortReason::Interrupt) => write!(f, "the player stopped answering"),
+            Self::Abort(AbortReason::Error(err)) | Self::Io(err) => write!(f, "{err}"),
+            Self::Game(err) => write!(f, "{err}"),
+            Self::Build(err) => write!(f, "{err}"),
+        }
+    }
+}
+impl std::error::Error for PlayError {}
+impl From<AbortReason> for PlayError {
+    fn from(value: AbortReason) -> Self {
+        Self::Abort(value)
+    }
+}
+impl From<GameError> for PlayError {
+    fn from(value: GameError) -> Self {
+        Self::Game(value)
+    }
+}
+impl From<BuildError> for PlayError {
+    fn from(value: BuildError) -> Self {
+        Self::Build(value)
+    }
+}
+impl From<io::Error> for PlayError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+#[cfg(all(test, feature = "fs-content"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::{creation::CreationRules, ui::ScriptedFrontend};
+
+    #[test]
+    fn fights_started_in_conversation_are_fought() {
+        let mut builder =
+            CharacterBuilder::with_rules(CreationMode::Standard, CreationRules::default());
+        builder
+            .set_name("Smuggs")
+            .unwrap()
+            .set_class(Class::Bastion)
+            .set_race(Race::GoblinoidFae)
+            .set_background(None);
+        builder.add_bonus_stat(Stat::Bulk).unwrap();
+        builder.add_bonus_stat(Stat::Backbone).unwrap();
+        let mut game = Game::default();
+        game.create_character(&builder).unwrap();
+        game.enter_location("testcorridor").unwrap();
+
+        // Insult his hat, then attack (and raise the first stat) until the fight is over
+        let mut answers = vec!["talk to mossback", "3"];
+        answers.extend(["1"; 200]);
+        let mut frontend = ScriptedFrontend::new(answers);
+        let result = command_prompt(&mut frontend, &mut game);
+
+        let output = frontend.output();
+        assert!(output.contains(&String::from("A fight breaks out with the bog lurker!")));
+        assert!(output.contains(&String::from("--- Round 1 ---")));
+        assert!(game.take_fight().is_none());
+        if output
+            .iter()
+            .any(|line| line.contains("defeated by the swamp"))
+        {
+            assert!(result.is_ok());
+        } else {
+            assert_eq!(game.state().journal().defeated("bog_lurker"), 1);
+            assert!(matches!(result, Err(PlayError::Abort(_))));
+        }
+    }
+}
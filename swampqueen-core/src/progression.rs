@@ -0,0 +1,99 @@
+//! Experience and levelling. Each [`Class`] has its own [`ClassProgression`] table, loaded from
+//! data, describing how much experience each level requires and what reaching it grants.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::{
+    character::Class,
+    content::{ContentKind, load_json},
+};
+
+/// The level every character starts at.
+pub const STARTING_LEVEL: usize = 1;
+
+/// What a character receives upon reaching a given level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LevelReward {
+    /// The level this reward is for.
+    pub level: usize,
+    /// The total experience needed to reach this level.
+    pub experience: usize,
+    /// The number of stats the character may raise by one.
+    pub stat_points: usize,
+    /// Any class features unlocked at this level.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unlocks: Vec<String>,
+}
+
+/// The levelling table for a single [`Class`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClassProgression {
+    progression: Vec<LevelReward>,
+}
+impl ClassProgression {
+    /// Load the [`ClassProgression`] for the given [`Class`] from the game's
+    /// [content](crate::content).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load(class: Class) -> std::io::Result<Self> {
+        load_json(ContentKind::Class, class.id())
+    }
+
+    /// The reward for reaching the given level, if that level exists.
+    #[must_use]
+    pub fn reward(&self, level: usize) -> Option<&LevelReward> {
+        self.progression.iter().find(|reward| reward.level == level)
+    }
+
+    /// The total experience needed to reach the level after `level`, or [`None`] if `level` is the
+    /// highest level.
+    #[must_use]
+    pub fn experience_for_next_level(&self, level: usize) -> Option<usize> {
+        self.reward(level + 1).map(|reward| reward.experience)
+    }
+
+    /// The highest level a character of this class can reach.
+    #[must_use]
+    pub fn max_level(&self) -> usize {
+        self.progression
+            .iter()
+            .map(|reward| reward.level)
+            .max()
+            .unwrap_or(STARTING_LEVEL)
+    }
+}
+
+#[cfg(all(test, feature = "fs-content"))]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_all_classes() {
+        for class in Class::ALL {
+            let progression = ClassProgression::try_load(class).unwrap();
+            assert_eq!(progression.max_level(), 5);
+            assert!(progression.reward(STARTING_LEVEL).is_none());
+        }
+    }
+
+    #[test]
+    fn next_level_experience() {
+        let progression = ClassProgression::try_load(Class::Soothsayer).unwrap();
+        assert_eq!(progression.experience_for_next_level(1), Some(100));
+        assert_eq!(progression.experience_for_next_level(4), Some(700));
+        assert_eq!(progression.experience_for_next_level(5), None);
+        assert_eq!(
+            progression.reward(3).unwrap().unlocks,
+            vec![String::from("Omen Reading")]
+        );
+    }
+}
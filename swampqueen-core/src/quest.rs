@@ -0,0 +1,397 @@
+//! Quests and the [`Journal`] that tracks them. A [`Quest`] is a series of stages, each finished by
+//! completing all of its [`Objective`]s; finishing the last stage grants the quest's rewards.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "serde")]
+use crate::content::{ContentKind, load_json};
+use crate::{game::GameState, party::Party};
+
+/// A quest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Quest {
+    id: String,
+    name: String,
+    stages: Vec<QuestStage>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    rewards: QuestRewards,
+}
+impl Quest {
+    /// Load a [`Quest`] from the game's [content](crate::content).
+    ///
+    /// # Arguments
+    ///
+    /// * `quest_id` - A [`std::str`] corresponding to the ID of the desired quest. In a content
+    ///   directory, this is the name of the quest's `.json` file.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if no [content source](crate::content::ContentSource) has it,
+    /// or it can't be read or deserialized.
+    #[cfg(feature = "serde")]
+    pub fn try_load(quest_id: &str) -> std::io::Result<Self> {
+        load_json(ContentKind::Quest, quest_id)
+    }
+
+    /// The ID of this quest.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The name shown to the player.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The quest's stages, in order.
+    #[must_use]
+    pub fn stages(&self) -> &[QuestStage] {
+        &self.stages
+    }
+
+    /// What finishing the quest grants.
+    #[must_use]
+    pub const fn rewards(&self) -> &QuestRewards {
+        &self.rewards
+    }
+}
+
+/// One stage of a [`Quest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuestStage {
+    /// The journal entry written when the stage begins.
+    pub description: String,
+    /// Everything that must be done to finish the stage.
+    pub objectives: Vec<Objective>,
+}
+
+/// Something that must be done to finish a [`QuestStage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type"))]
+pub enum Objective {
+    /// Visit the [`Location`](crate::location::Location) with the given ID.
+    VisitLocation {
+        /// The location's ID.
+        location: String,
+    },
+    /// Hold at least `count` of an item in the party's inventory.
+    ObtainItem {
+        /// The item's ID.
+        item: String,
+        /// How many are needed.
+        #[cfg_attr(feature = "serde", serde(default = "one"))]
+        count: usize,
+    },
+    /// Defeat at least `count` of a creature over the course of the game.
+    DefeatCreature {
+        /// The creature's ID.
+        creature: String,
+        /// How many must be defeated.
+        #[cfg_attr(feature = "serde", serde(default = "one"))]
+        count: usize,
+    },
+    /// Set a story flag.
+    SetFlag {
+        /// The flag.
+        flag: String,
+    },
+}
+impl Objective {
+    /// Whether this objective has been completed.
+    #[must_use]
+    pub fn is_complete(&self, party: &Party, state: &GameState) -> bool {
+        let journal = state.journal();
+        match self {
+            Self::VisitLocation { location } => journal.has_visited(location),
+            Self::ObtainItem { item, count } => party.inventory().count(item) >= *count,
+            Self::DefeatCreature { creature, count } => journal.defeated(creature) >= *count,
+            Self::SetFlag { flag } => state.has_flag(flag),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+const fn one() -> usize {
+    1
+}
+
+/// What finishing a [`Quest`] grants.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuestRewards {
+    /// Experience for every party member. This is reported by [`GameState::update_quest`] for the
+    /// frontend to award, like [`Combat::experience`](crate::combat::Combat::experience).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub experience: usize,
+    /// The IDs of items added to the party's inventory.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub items: Vec<String>,
+    /// Story flags set.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub flags: Vec<String>,
+}
+
+/// A change in a quest's progress reported by [`GameState::update_quest`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuestUpdate {
+    /// A stage was finished and the next one began.
+    StageCompleted {
+        /// The index of the stage that began.
+        next_stage: usize,
+    },
+    /// The last stage was finished. The item and flag rewards have already been applied.
+    Completed {
+        /// The experience every party member should be awarded.
+        experience: usize,
+    },
+}
+
+/// The journal's record of a single quest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QuestLog {
+    name: String,
+    stage: usize,
+    entries: Vec<String>,
+    completed: bool,
+}
+impl QuestLog {
+    /// The quest's name.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The index of the stage the party is on.
+    #[must_use]
+    pub const fn stage(&self) -> usize {
+        self.stage
+    }
+
+    /// The journal entries for every stage reached so far, in order.
+    #[must_use]
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Whether the quest has been finished.
+    #[must_use]
+    pub const fn is_completed(&self) -> bool {
+        self.completed
+    }
+}
+
+/// The party's journal: every quest they have started, and the places and fights quests care
+/// about.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Journal {
+    #[cfg_attr(feature = "serde", serde(default))]
+    quests: BTreeMap<String, QuestLog>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    visited: BTreeSet<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    defeated: BTreeMap<String, usize>,
+}
+impl Journal {
+    /// The record of the quest with the given ID, if it has been started.
+    #[must_use]
+    pub fn quest(&self, quest_id: &str) -> Option<&QuestLog> {
+        self.quests.get(quest_id)
+    }
+
+    /// Every quest started, by ID.
+    pub fn quests(&self) -> impl Iterator<Item = (&str, &QuestLog)> {
+        self.quests.iter().map(|(id, log)| (id.as_str(), log))
+    }
+
+    /// Whether the party has been to the location with the given ID.
+    #[must_use]
+    pub fn has_visited(&self, location_id: &str) -> bool {
+        self.visited.contains(location_id)
+    }
+
+    /// How many of the creature with the given ID the party has defeated.
+    #[must_use]
+    pub fn defeated(&self, creature_id: &str) -> usize {
+        self.defeated.get(creature_id).copied().unwrap_or_default()
+    }
+
+    /// Record that the party has been to a location.
+    pub fn record_visit(&mut self, location_id: &str) {
+        self.visited.insert(String::from(location_id));
+    }
+
+    /// Record that the party has defeated a creature.
+    pub fn record_defeat(&mut self, creature_id: &str) {
+        *self.defeated.entry(String::from(creature_id)).or_default() += 1;
+    }
+}
+impl Display for Journal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.quests.is_empty() {
+            return write!(f, "The journal is empty.");
+        }
+        let mut first = true;
+        for log in self.quests.values() {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            write!(f, "== {} ==", log.name)?;
+            if log.completed {
+                write!(f, " (completed)")?;
+            }
+            for entry in &log.entries {
+                write!(f, "\n- {entry}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GameState {
+    /// Start a quest, writing its first stage in the journal. Returns `false` if it had already
+    /// been started.
+    pub fn start_quest(&mut self, quest: &Quest) -> bool {
+        if self.journal().quest(quest.id()).is_some() {
+            return false;
+        }
+        let entries = quest
+            .stages
+            .first()
+            .map(|stage| vec![stage.description.clone()])
+            .unwrap_or_default();
+        self.journal_mut().quests.insert(
+            String::from(quest.id()),
+            QuestLog {
+                name: quest.name.clone(),
+                stage: 0,
+                entries,
+                completed: quest.stages.is_empty(),
+            },
+        );
+        true
+    }
+
+    /// Advance a started quest through every stage whose objectives are all complete, applying
+    /// its rewards if it is finished. Returns each change in order.
+    pub fn update_quest(&mut self, quest: &Quest, party: &mut Party) -> Vec<QuestUpdate> {
+        let mut updates = Vec::new();
+        while let Some(log) = self.journal().quest(quest.id()) {
+            if log.completed {
+                break;
+            }
+            let Some(stage) = quest.stages.get(log.stage) else {
+                break;
+            };
+            if !stage
+                .objectives
+                .iter()
+                .all(|objective| objective.is_complete(party, self))
+            {
+                break;
+            }
+
+            let next_stage = log.stage + 1;
+            let next = quest.stages.get(next_stage);
+            let Some(log) = self.journal_mut().quests.get_mut(quest.id()) else {
+                break;
+            };
+            log.stage = next_stage;
+            if let Some(next) = next {
+                log.entries.push(next.description.clone());
+                updates.push(QuestUpdate::StageCompleted { next_stage });
+            } else {
+                log.completed = true;
+                for item in &quest.rewards.items {
+                    party.inventory_mut().add(item, 1);
+                }
+                for flag in &quest.rewards.flags {
+                    self.set_flag(flag);
+                }
+                updates.push(QuestUpdate::Completed {
+                    experience: quest.rewards.experience,
+                });
+            }
+        }
+        updates
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "fs-content")]
+    use crate::character::{Character, Class, Race, Stat};
+
+    #[cfg(feature = "fs-content")]
+    fn party() -> Party {
+        Party::new(Character::new(
+            "Questing Quill",
+            Class::Hunter,
+            Race::InsectoidFae,
+            Stat::Bulk,
+            Stat::Backbone,
+        ))
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn quest_progresses_through_stages() {
+        let quest = Quest::try_load("mossbacks_secret").unwrap();
+        let mut party = party();
+        let mut state = GameState::default();
+
+        assert!(state.update_quest(&quest, &mut party).is_empty());
+        assert!(state.start_quest(&quest));
+        assert!(!state.start_quest(&quest));
+        assert!(state.update_quest(&quest, &mut party).is_empty());
+
+        state.journal_mut().record_visit("testplace");
+        state.set_flag("asked_about_bog");
+        party.inventory_mut().add("frog_charm", 1);
+        assert_eq!(
+            state.update_quest(&quest, &mut party),
+            [
+                QuestUpdate::StageCompleted { next_stage: 1 },
+                QuestUpdate::StageCompleted { next_stage: 2 }
+            ]
+        );
+        assert_eq!(
+            state.journal().quest("mossbacks_secret").unwrap().stage(),
+            2
+        );
+
+        state.journal_mut().record_defeat("bog_lurker");
+        assert_eq!(
+            state.update_quest(&quest, &mut party),
+            [QuestUpdate::Completed { experience: 150 }]
+        );
+        assert!(state.update_quest(&quest, &mut party).is_empty());
+        assert!(party.inventory().contains("bogbane"));
+        assert!(state.has_flag("mossback_quest_done"));
+
+        let log = state.journal().quest("mossbacks_secret").unwrap();
+        assert!(log.is_completed());
+        assert_eq!(log.entries().len(), 3);
+        assert!(state.journal().to_string().contains("(completed)"));
+    }
+
+    #[test]
+    fn empty_journal() {
+        assert_eq!(Journal::default().to_string(), "The journal is empty.");
+    }
+}
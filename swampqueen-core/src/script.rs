@@ -0,0 +1,419 @@
+//! Scripts attached to [`Location`](crate::location::Location) events, for logic beyond what a
+//! [`Condition`](crate::condition::Condition) can express. Scripts are written in
+//! [Rhai](https://rhai.rs) and run in a sandbox: they can't touch the filesystem, load modules or
+//! run forever, and can only see the game through the functions below.
+//!
+//! | Function                     | Does                                                   |
+//! |------------------------------|--------------------------------------------------------|
+//! | `stat(name)`                 | The leader's stat, e.g. `stat("the_sight")`            |
+//! | `skill(name)`                | The leader's skill modifier, e.g. `skill("stealth")`   |
+//! | `player_name()`              | The leader's name                                      |
+//! | `player_race()`              | The leader's race, e.g. `"InsectoidFae"`               |
+//! | `player_class()`             | The leader's class, e.g. `"Soothsayer"`                |
+//! | `player_level()`             | The leader's level                                     |
+//! | `roll(dice)`                 | Roll dice and total them, e.g. `roll("4d6kh3")`        |
+//! | `has_flag(flag)`             | Whether a story flag has been set                      |
+//! | `set_flag(flag)`             | Set a story flag                                       |
+//! | `clear_flag(flag)`           | Clear a story flag                                     |
+//! | `item_count(item)`           | How many of an item the party holds                    |
+//! | `give_item(item[, count])`   | Add items to the party's inventory                     |
+//! | `take_item(item[, count])`   | Remove items, returning `false` if there aren't enough |
+//! | `go_to(location)`            | Move the party to another location afterwards          |
+//! | `say(text)`, `print(text)`   | Show text to the player                                |
+//!
+//! On-choice scripts also have a `choice` constant holding the ID of the chosen
+//! [`Choice`](crate::location::Choice); in on-enter scripts it is `()`.
+//!
+//! A script can roll at most 10,000 dice each time it runs, and no more than
+//! [`MAX_DICE`](crate::dice::MAX_DICE) at once.
+
+use std::{cell::RefCell, fmt::Display, io, rc::Rc};
+
+use rhai::{
+    AST, Dynamic, Engine, EvalAltResult, INT, Scope, module_resolvers::DummyModuleResolver,
+};
+
+use crate::{
+    character::Character,
+    condition::{skill_named, stat_named},
+    content::{ContentKind, load_text},
+    dice::parse_roll_many_str,
+    game::GameState,
+    item::Inventory,
+    party::Party,
+};
+
+/// The most operations a script may run before it is stopped, so a runaway loop can't hang the
+/// game.
+const MAX_OPERATIONS: u64 = 100_000;
+
+/// The most dice a script may roll in one run. Rolling happens outside of Rhai, so it isn't
+/// counted towards [`MAX_OPERATIONS`].
+const MAX_DICE_ROLLED: usize = 10_000;
+
+/// A compiled script.
+#[derive(Debug, Clone)]
+pub struct Script {
+    ast: AST,
+}
+impl Script {
+    /// Load and compile a [`Script`] from the game's [content](crate::content).
+    ///
+    /// # Arguments
+    ///
+    /// * `script_id` - A [`std::str`] corresponding to the ID of the desired script. In a content
+    ///   directory, this is the name of the script's `.rhai` file.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ScriptError::Io`] if no
+    /// [content source](crate::content::ContentSource) has it or it can't be read, or
+    /// [`ScriptError::Compile`] if it isn't a valid script.
+    pub fn try_load(script_id: &str) -> Result<Self, ScriptError> {
+        Self::compile(&load_text(ContentKind::Script, script_id).map_err(ScriptError::Io)?)
+    }
+
+    /// Compile a [`Script`] from its source.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ScriptError::Compile`] if the source isn't a valid script.
+    pub fn compile(source: &str) -> Result<Self, ScriptError> {
+        sandboxed_engine()
+            .compile(source)
+            .map(|ast| Self { ast })
+            .map_err(|err| ScriptError::Compile(err.to_string()))
+    }
+
+    /// Run the script. The party's shared inventory and the game state are changed as the script
+    /// goes; if it fails partway through, changes made before the failure are kept.
+    ///
+    /// # Arguments
+    ///
+    /// * `choice` - The ID of the choice made, for on-choice scripts.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`ScriptError::Runtime`] if the script fails, e.g. by asking for a
+    /// stat that doesn't exist or running for too long.
+    pub fn run(
+        &self,
+        party: &mut Party,
+        state: &mut GameState,
+        choice: Option<&str>,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        let sandbox = Rc::new(RefCell::new(Sandbox {
+            state: std::mem::take(state),
+            inventory: std::mem::take(party.inventory_mut()),
+            outcome: ScriptOutcome::default(),
+            dice_left: MAX_DICE_ROLLED,
+        }));
+        let engine = api_engine(&sandbox, Rc::new(party.leader().clone()));
+
+        let mut scope = Scope::new();
+        scope.push_constant(
+            "choice",
+            choice.map_or(Dynamic::UNIT, |choice| Dynamic::from(String::from(choice))),
+        );
+        let result = engine.run_ast_with_scope(&mut scope, &self.ast);
+        drop(engine);
+
+        let sandbox = sandbox.take();
+        *state = sandbox.state;
+        *party.inventory_mut() = sandbox.inventory;
+        result
+            .map(|()| sandbox.outcome)
+            .map_err(|err| ScriptError::Runtime(err.to_string()))
+    }
+}
+
+/// What a script asked the frontend to do once it finished.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ScriptOutcome {
+    /// Text to show the player, in order.
+    pub messages: Vec<String>,
+    /// The ID of the location to move the party to, if any.
+    pub go_to: Option<String>,
+}
+
+/// The reasons a script cannot be loaded or run.
+#[derive(Debug)]
+pub enum ScriptError {
+    /// The script file couldn't be read.
+    Io(io::Error),
+    /// The script isn't valid Rhai.
+    Compile(String),
+    /// The script failed while running.
+    Runtime(String),
+}
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "couldn't read script: {err}"),
+            Self::Compile(err) => write!(f, "couldn't compile script: {err}"),
+            Self::Runtime(err) => write!(f, "script failed: {err}"),
+        }
+    }
+}
+impl std::error::Error for ScriptError {}
+
+/// Everything a running script can change, moved out of the party and game state for the length
+/// of the run.
+#[derive(Debug, Default)]
+struct Sandbox {
+    state: GameState,
+    inventory: Inventory,
+    outcome: ScriptOutcome,
+    dice_left: usize,
+}
+
+/// An engine with no access to the outside world and limits on how much work a script can do.
+fn sandboxed_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine
+        .set_module_resolver(DummyModuleResolver::new())
+        .set_max_operations(MAX_OPERATIONS)
+        .set_max_call_levels(32)
+        .set_max_expr_depths(64, 32)
+        .set_max_string_size(10_000)
+        .set_max_array_size(1_000)
+        .set_max_map_size(1_000)
+        .disable_symbol("eval");
+    engine
+}
+
+/// A sandboxed engine with the game's API registered.
+fn api_engine(sandbox: &Rc<RefCell<Sandbox>>, leader: Rc<Character>) -> Engine {
+    let mut engine = sandboxed_engine();
+
+    let s = Rc::clone(sandbox);
+    engine.on_print(move |text| s.borrow_mut().outcome.messages.push(String::from(text)));
+    let s = Rc::clone(sandbox);
+    engine.register_fn("say", move |text: &str| {
+        s.borrow_mut().outcome.messages.push(String::from(text));
+    });
+
+    let l = Rc::clone(&leader);
+    engine.register_fn(
+        "stat",
+        move |name: &str| -> Result<INT, Box<EvalAltResult>> {
+            let stat = stat_named(name).ok_or_else(|| format!("unknown stat `{name}`"))?;
+            Ok(to_int(l.effective_stats().get(stat)))
+        },
+    );
+    let l = Rc::clone(&leader);
+    engine.register_fn(
+        "skill",
+        move |name: &str| -> Result<INT, Box<EvalAltResult>> {
+            let skill = skill_named(name).ok_or_else(|| format!("unknown skill `{name}`"))?;
+            Ok(to_int(l.skill_modifier(skill)))
+        },
+    );
+    let l = Rc::clone(&leader);
+    engine.register_fn("player_name", move || String::from(l.name()));
+    let l = Rc::clone(&leader);
+    engine.register_fn("player_race", move || format!("{:?}", l.race()));
+    let l = Rc::clone(&leader);
+    engine.register_fn("player_class", move || format!("{:?}", l.class()));
+    engine.register_fn("player_level", move || {
+        INT::try_from(leader.level()).unwrap_or(INT::MAX)
+    });
+
+    let s = Rc::clone(sandbox);
+    engine.register_fn(
+        "roll",
+        move |dice: &str| -> Result<INT, Box<EvalAltResult>> {
+            let request = parse_roll_many_str(dice).map_err(|err| err.to_string())?;
+            let mut sandbox = s.borrow_mut();
+            sandbox.dice_left = sandbox
+                .dice_left
+                .checked_sub(request.num_dice())
+                .ok_or("too many dice rolled")?;
+            Ok(INT::try_from(request.roll().total()).unwrap_or(INT::MAX))
+        },
+    );
+
+    let s = Rc::clone(sandbox);
+    engine.register_fn("has_flag", move |flag: &str| {
+        s.borrow().state.has_flag(flag)
+    });
+    let s = Rc::clone(sandbox);
+    engine.register_fn("set_flag", move |flag: &str| {
+        s.borrow_mut().state.set_flag(flag);
+    });
+    let s = Rc::clone(sandbox);
+    engine.register_fn("clear_flag", move |flag: &str| {
+        s.borrow_mut().state.clear_flag(flag);
+    });
+
+    let s = Rc::clone(sandbox);
+    engine.register_fn("item_count", move |item: &str| {
+        INT::try_from(s.borrow().inventory.count(item)).unwrap_or(INT::MAX)
+    });
+    let s = Rc::clone(sandbox);
+    engine.register_fn("give_item", move |item: &str| {
+        s.borrow_mut().inventory.add(item, 1);
+    });
+    let s = Rc::clone(sandbox);
+    engine.register_fn(
+        "give_item",
+        move |item: &str, count: INT| -> Result<(), Box<EvalAltResult>> {
+            s.borrow_mut().inventory.add(item, to_count(count)?);
+            Ok(())
+        },
+    );
+    let s = Rc::clone(sandbox);
+    engine.register_fn("take_item", move |item: &str| {
+        s.borrow_mut().inventory.remove(item, 1)
+    });
+    let s = Rc::clone(sandbox);
+    engine.register_fn(
+        "take_item",
+        move |item: &str, count: INT| -> Result<bool, Box<EvalAltResult>> {
+            Ok(s.borrow_mut().inventory.remove(item, to_count(count)?))
+        },
+    );
+
+    let s = Rc::clone(sandbox);
+    engine.register_fn("go_to", move |location: &str| {
+        s.borrow_mut().outcome.go_to = Some(String::from(location));
+    });
+
+    engine
+}
+
+fn to_int(value: isize) -> INT {
+    INT::try_from(value).unwrap_or(INT::MAX)
+}
+
+fn to_count(count: INT) -> Result<usize, Box<EvalAltResult>> {
+    usize::try_from(count).map_err(|_| format!("invalid item count {count}").into())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::character::{Class, Race, Stat};
+
+    fn party() -> Party {
+        Party::new(Character::new(
+            "Scripted Sal",
+            Class::Soothsayer,
+            Race::InsectoidFae,
+            Stat::TheSight,
+            Stat::Cunning,
+        ))
+    }
+
+    fn run(source: &str, party: &mut Party, state: &mut GameState) -> ScriptOutcome {
+        Script::compile(source)
+            .unwrap()
+            .run(party, state, None)
+            .unwrap()
+    }
+
+    #[test]
+    fn scripts_read_and_change_the_game() {
+        let mut party = party();
+        let mut state = GameState::default();
+        party.inventory_mut().add("reed_flute", 2);
+
+        let outcome = run(
+            r#"
+            say(player_name() + " the " + player_race() + " " + player_class());
+            if stat("the_sight") >= 5 && skill("lore") > stat("the_sight") {
+                set_flag("sees_omens");
+            }
+            if take_item("reed_flute", 2) {
+                give_item("broken_flute");
+            }
+            print(item_count("broken_flute"));
+            let r = roll("2d6");
+            if r >= 2 && r <= 12 && player_level() == 1 {
+                go_to("swamp_edge");
+            }
+            "#,
+            &mut party,
+            &mut state,
+        );
+        assert_eq!(
+            outcome.messages,
+            ["Scripted Sal the InsectoidFae Soothsayer", "1"]
+        );
+        assert_eq!(outcome.go_to.as_deref(), Some("swamp_edge"));
+        assert!(state.has_flag("sees_omens"));
+        assert_eq!(party.inventory().count("reed_flute"), 0);
+        assert!(party.inventory().contains("broken_flute"));
+    }
+
+    #[test]
+    fn choice_constant() {
+        let script = Script::compile(
+            r#"if choice == () { say("entered") } else { say("chose " + choice) }"#,
+        )
+        .unwrap();
+        let mut party = party();
+        let mut state = GameState::default();
+        let entered = script.run(&mut party, &mut state, None).unwrap();
+        assert_eq!(entered.messages, ["entered"]);
+        let chose = script.run(&mut party, &mut state, Some("leave")).unwrap();
+        assert_eq!(chose.messages, ["chose leave"]);
+        assert!(matches!(
+            Script::compile(r#"choice = "other";"#)
+                .unwrap()
+                .run(&mut party, &mut state, None),
+            Err(ScriptError::Runtime(_))
+        ));
+    }
+
+    #[test]
+    fn changes_are_kept_on_failure() {
+        let mut party = party();
+        let mut state = GameState::default();
+        let result = Script::compile(r#"set_flag("before"); stat("charisma"); set_flag("after");"#)
+            .unwrap()
+            .run(&mut party, &mut state, None);
+        assert!(matches!(result, Err(ScriptError::Runtime(err)) if err.contains("charisma")));
+        assert!(state.has_flag("before"));
+        assert!(!state.has_flag("after"));
+    }
+
+    #[test]
+    fn scripts_are_sandboxed() {
+        let mut party = party();
+        let mut state = GameState::default();
+        assert!(matches!(
+            Script::compile("fn (").map(|_| ()),
+            Err(ScriptError::Compile(_))
+        ));
+        assert!(matches!(
+            Script::compile(r#"eval("1")"#).map(|_| ()),
+            Err(ScriptError::Compile(_))
+        ));
+        for source in [
+            "loop {}",
+            r#"import "secrets" as s;"#,
+            "fn f(x) { f(x + 1) } f(0);",
+            r#"give_item("frog_charm", -1);"#,
+            r#"roll("lots of dice");"#,
+            r#"roll("999999999999d6");"#,
+            r#"loop { roll("1000d1000"); }"#,
+        ] {
+            let result = Script::compile(source)
+                .unwrap()
+                .run(&mut party, &mut state, None);
+            assert!(matches!(result, Err(ScriptError::Runtime(_))), "{source}");
+        }
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn load_script() {
+        assert!(Script::try_load("testplace_enter").is_ok());
+        assert!(matches!(
+            Script::try_load("no_such_script"),
+            Err(ScriptError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,198 @@
+//! Skills: narrower areas of expertise, each falling under one of the five [`Stat`]s. A skill
+//! check rolls the [`CHECK_DIE`](crate::check::CHECK_DIE), adding the parent stat and the bonus
+//! for the character's [`Proficiency`] in the skill.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::character::Stat;
+
+/// A named skill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Skill {
+    /// Opening locks without the key.
+    Lockpicking,
+    /// Moving without being noticed.
+    Stealth,
+    /// Talking others round.
+    Persuasion,
+    /// Reading people's intentions.
+    Insight,
+    /// Frightening others into compliance.
+    Intimidation,
+    /// Climbing, swimming and wading.
+    Athletics,
+    /// Living off the swamp.
+    Survival,
+    /// Pushing on through exhaustion and sickness.
+    Endurance,
+    /// Knowing which plants heal and which kill.
+    Herbalism,
+    /// Knowing the old stories, and what they are really about.
+    Lore,
+}
+impl Skill {
+    /// Every skill, grouped by parent stat.
+    pub const ALL: [Self; 10] = [
+        Self::Lockpicking,
+        Self::Stealth,
+        Self::Persuasion,
+        Self::Insight,
+        Self::Intimidation,
+        Self::Athletics,
+        Self::Survival,
+        Self::Endurance,
+        Self::Herbalism,
+        Self::Lore,
+    ];
+
+    /// The stat this skill falls under.
+    #[must_use]
+    pub const fn stat(self) -> Stat {
+        match self {
+            Self::Lockpicking | Self::Stealth => Stat::Slipperiness,
+            Self::Persuasion | Self::Insight => Stat::Cunning,
+            Self::Intimidation | Self::Athletics => Stat::Bulk,
+            Self::Survival | Self::Endurance => Stat::Backbone,
+            Self::Herbalism | Self::Lore => Stat::TheSight,
+        }
+    }
+}
+impl From<&Skill> for String {
+    fn from(value: &Skill) -> Self {
+        format!("{value:?}")
+    }
+}
+impl From<Skill> for String {
+    fn from(value: Skill) -> Self {
+        (&value).into()
+    }
+}
+impl Display for Skill {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from(self))
+    }
+}
+
+/// How well a character knows a [`Skill`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Proficiency {
+    /// No training at all.
+    #[default]
+    Untrained,
+    /// Granted once.
+    Trained,
+    /// Granted twice.
+    Expert,
+    /// Granted three or more times.
+    Master,
+}
+impl Proficiency {
+    /// The bonus added to checks of a skill with this proficiency.
+    #[must_use]
+    pub const fn bonus(self) -> isize {
+        match self {
+            Self::Untrained => 0,
+            Self::Trained => 2,
+            Self::Expert => 4,
+            Self::Master => 6,
+        }
+    }
+
+    /// The next proficiency up, staying at [`Proficiency::Master`].
+    #[must_use]
+    pub const fn raised(self) -> Self {
+        match self {
+            Self::Untrained => Self::Trained,
+            Self::Trained => Self::Expert,
+            Self::Expert | Self::Master => Self::Master,
+        }
+    }
+}
+impl Display for Proficiency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// A character's proficiency in every skill.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Proficiencies {
+    skills: BTreeMap<Skill, Proficiency>,
+}
+impl Proficiencies {
+    /// Raise the proficiency in a skill by one level. Each source granting the same skill (class,
+    /// race, background) makes the character better at it.
+    pub fn train(&mut self, skill: Skill) {
+        let proficiency = self.skills.entry(skill).or_default();
+        *proficiency = proficiency.raised();
+    }
+
+    /// The proficiency in a skill.
+    #[must_use]
+    pub fn get(&self, skill: Skill) -> Proficiency {
+        self.skills.get(&skill).copied().unwrap_or_default()
+    }
+
+    /// Every skill with at least [`Proficiency::Trained`], in the order of [`Skill::ALL`].
+    pub fn iter(&self) -> impl Iterator<Item = (Skill, Proficiency)> {
+        self.skills
+            .iter()
+            .map(|(&skill, &proficiency)| (skill, proficiency))
+    }
+
+    /// Whether the character is untrained in every skill.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+    }
+}
+impl Display for Proficiencies {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut first = true;
+        for (skill, proficiency) in self.iter() {
+            if !first {
+                write!(f, ", ")?;
+            }
+            first = false;
+            write!(f, "{skill} ({proficiency})")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_stat_has_skills() {
+        for stat in Stat::ALL {
+            assert!(Skill::ALL.iter().any(|skill| skill.stat() == stat));
+        }
+    }
+
+    #[test]
+    fn training_raises_proficiency() {
+        let mut proficiencies = Proficiencies::default();
+        assert_eq!(proficiencies.get(Skill::Lore), Proficiency::Untrained);
+        proficiencies.train(Skill::Lore);
+        assert_eq!(proficiencies.get(Skill::Lore), Proficiency::Trained);
+        for _ in 0..5 {
+            proficiencies.train(Skill::Lore);
+        }
+        assert_eq!(proficiencies.get(Skill::Lore), Proficiency::Master);
+        assert_eq!(proficiencies.get(Skill::Lore).bonus(), 6);
+
+        proficiencies.train(Skill::Stealth);
+        assert_eq!(
+            proficiencies.to_string(),
+            "Stealth (Trained), Lore (Master)"
+        );
+    }
+}
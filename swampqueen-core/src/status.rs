@@ -0,0 +1,374 @@
+//! Status effects that linger on a [`Character`](crate::character::Character) or creature for a
+//! number of turns. The principal type of this module is [`StatusEffects`], the collection of
+//! effects currently active on a single entity.
+
+use std::fmt::Display;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    character::{Stat, StatValues},
+    dice::{RollManyRequest, RolledDiceResults},
+};
+
+/// The different kinds of status effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StatusKind {
+    /// Deals `1d4` damage per stack at the end of every turn.
+    Poisoned,
+    /// Stuck in the mud; slows down movement.
+    BoggedDown,
+    /// Favoured by the swamp.
+    Blessed,
+    /// Shaken to the core.
+    Terrified,
+}
+impl StatusKind {
+    /// How applying this effect to an entity that already has it behaves.
+    #[must_use]
+    pub const fn stacking(self) -> Stacking {
+        match self {
+            Self::Poisoned => Stacking::Intensify { max_stacks: 5 },
+            Self::BoggedDown | Self::Terrified => Stacking::Refresh,
+            Self::Blessed => Stacking::Extend,
+        }
+    }
+
+    /// The stat modifiers granted by a single stack of this effect.
+    #[must_use]
+    pub fn modifiers(self) -> StatValues {
+        let mut modifiers = StatValues::default();
+        match self {
+            Self::Poisoned => modifiers.add_to_stat(Stat::Bulk, -1),
+            Self::BoggedDown => modifiers.add_to_stat(Stat::Slipperiness, -2),
+            Self::Blessed => {
+                modifiers.add_to_stat(Stat::Backbone, 1);
+                modifiers.add_to_stat(Stat::TheSight, 1);
+            }
+            Self::Terrified => {
+                modifiers.add_to_stat(Stat::Backbone, -2);
+                modifiers.add_to_stat(Stat::Cunning, -1);
+            }
+        }
+        modifiers
+    }
+
+    /// The dice rolled as damage for each stack of this effect at the end of every turn, if any.
+    #[must_use]
+    pub const fn damage_per_stack(self) -> Option<RollManyRequest> {
+        match self {
+            Self::Poisoned => Some(RollManyRequest::new(1, 4)),
+            Self::BoggedDown | Self::Blessed | Self::Terrified => None,
+        }
+    }
+}
+impl From<&StatusKind> for String {
+    fn from(value: &StatusKind) -> Self {
+        match value {
+            StatusKind::Poisoned => String::from("Poisoned"),
+            StatusKind::BoggedDown => String::from("Bogged Down"),
+            StatusKind::Blessed => String::from("Blessed"),
+            StatusKind::Terrified => String::from("Terrified"),
+        }
+    }
+}
+impl From<StatusKind> for String {
+    fn from(value: StatusKind) -> Self {
+        (&value).into()
+    }
+}
+impl Display for StatusKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", String::from(self))
+    }
+}
+
+/// The rule used when an effect is applied to an entity that already has an effect of the same
+/// [`StatusKind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stacking {
+    /// Keep a single stack, using whichever duration is longer.
+    Refresh,
+    /// Keep a single stack, adding the new duration onto the remaining one.
+    Extend,
+    /// Add the new stacks (up to `max_stacks`) and use whichever duration is longer.
+    Intensify {
+        /// The maximum number of stacks this effect can reach.
+        max_stacks: usize,
+    },
+}
+impl Stacking {
+    /// The most stacks an effect stacking this way can have.
+    #[must_use]
+    pub const fn max_stacks(self) -> usize {
+        match self {
+            Self::Refresh | Self::Extend => 1,
+            Self::Intensify { max_stacks } => max_stacks,
+        }
+    }
+}
+
+/// A single status effect, along with how long it lasts and how many times it is stacked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatusEffect {
+    kind: StatusKind,
+    turns_remaining: usize,
+    stacks: usize,
+}
+impl StatusEffect {
+    /// Create a single stack of the given effect lasting for `turns` turns.
+    #[must_use]
+    pub const fn new(kind: StatusKind, turns: usize) -> Self {
+        Self {
+            kind,
+            turns_remaining: turns,
+            stacks: 1,
+        }
+    }
+
+    /// Set the number of stacks of this effect.
+    #[must_use]
+    pub const fn with_stacks(mut self, stacks: usize) -> Self {
+        self.stacks = stacks;
+        self
+    }
+
+    /// The kind of this effect.
+    #[must_use]
+    pub const fn kind(&self) -> StatusKind {
+        self.kind
+    }
+
+    /// The number of turns before this effect wears off.
+    #[must_use]
+    pub const fn turns_remaining(&self) -> usize {
+        self.turns_remaining
+    }
+
+    /// The number of times this effect is stacked.
+    #[must_use]
+    pub const fn stacks(&self) -> usize {
+        self.stacks
+    }
+
+    /// Combine another application of the same effect into this one, following the rules given by
+    /// [`StatusKind::stacking`].
+    fn merge(&mut self, other: Self) {
+        match self.kind.stacking() {
+            Stacking::Refresh => {
+                self.turns_remaining = self.turns_remaining.max(other.turns_remaining);
+            }
+            Stacking::Extend => self.turns_remaining += other.turns_remaining,
+            Stacking::Intensify { max_stacks } => {
+                self.stacks = (self.stacks + other.stacks).min(max_stacks);
+                self.turns_remaining = self.turns_remaining.max(other.turns_remaining);
+            }
+        }
+    }
+}
+impl Display for StatusEffect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.stacks > 1 {
+            write!(
+                f,
+                "{} x{} ({} turns)",
+                self.kind, self.stacks, self.turns_remaining
+            )
+        } else {
+            write!(f, "{} ({} turns)", self.kind, self.turns_remaining)
+        }
+    }
+}
+
+/// What happened when the active effects were ticked at the end of a turn.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StatusTick {
+    /// Every damage roll made by an effect this turn, alongside the effect that caused it.
+    pub damage_rolls: Vec<(StatusKind, RolledDiceResults)>,
+    /// The effects that wore off this turn.
+    pub expired: Vec<StatusKind>,
+}
+impl StatusTick {
+    /// The total damage dealt by all effects this turn.
+    #[must_use]
+    pub fn total_damage(&self) -> usize {
+        self.damage_rolls
+            .iter()
+            .map(|(_, results)| results.total())
+            .sum()
+    }
+}
+
+/// All the status effects active on a single entity.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatusEffects {
+    effects: Vec<StatusEffect>,
+}
+impl StatusEffects {
+    /// Apply an effect, stacking it with any existing effect of the same kind. An effect never
+    /// has more than its [`Stacking::max_stacks`].
+    pub fn apply(&mut self, mut effect: StatusEffect) {
+        if effect.turns_remaining == 0 || effect.stacks == 0 {
+            return;
+        }
+        effect.stacks = effect.stacks.min(effect.kind.stacking().max_stacks());
+        match self.effects.iter_mut().find(|e| e.kind == effect.kind) {
+            Some(existing) => existing.merge(effect),
+            None => self.effects.push(effect),
+        }
+    }
+
+    /// Remove every effect of the given kind, returning whether there was one to remove.
+    pub fn remove(&mut self, kind: StatusKind) -> bool {
+        let before = self.effects.len();
+        self.effects.retain(|e| e.kind != kind);
+        self.effects.len() != before
+    }
+
+    /// Get the active effect of the given kind, if any.
+    #[must_use]
+    pub fn get(&self, kind: StatusKind) -> Option<&StatusEffect> {
+        self.effects.iter().find(|e| e.kind == kind)
+    }
+
+    /// Iterate over all active effects, in the order they were first applied.
+    pub fn iter(&self) -> impl Iterator<Item = &StatusEffect> {
+        self.effects.iter()
+    }
+
+    /// Whether there are no active effects.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// The combined stat modifiers of all active effects, to be applied on top of an entity's
+    /// [`StatValues`].
+    #[must_use]
+    pub fn modifiers(&self) -> StatValues {
+        let mut total = StatValues::default();
+        for effect in &self.effects {
+            for _ in 0..effect.stacks {
+                total += effect.kind.modifiers();
+            }
+        }
+        total
+    }
+
+    /// Run the end-of-turn triggers of every active effect, then count down their durations,
+    /// removing any that have worn off.
+    pub fn tick(&mut self) -> StatusTick {
+        let mut tick = StatusTick::default();
+        for effect in &mut self.effects {
+            if let Some(request) = effect.kind.damage_per_stack() {
+                for _ in 0..effect.stacks {
+                    tick.damage_rolls.push((effect.kind, request.roll()));
+                }
+            }
+            effect.turns_remaining = effect.turns_remaining.saturating_sub(1);
+            if effect.turns_remaining == 0 {
+                tick.expired.push(effect.kind);
+            }
+        }
+        self.effects.retain(|e| e.turns_remaining > 0);
+        tick
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refresh_keeps_longest_duration() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffect::new(StatusKind::BoggedDown, 3));
+        effects.apply(StatusEffect::new(StatusKind::BoggedDown, 2));
+        let bogged = effects.get(StatusKind::BoggedDown).unwrap();
+        assert_eq!(bogged.turns_remaining(), 3);
+        assert_eq!(bogged.stacks(), 1);
+    }
+
+    #[test]
+    fn extend_adds_durations() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffect::new(StatusKind::Blessed, 3));
+        effects.apply(StatusEffect::new(StatusKind::Blessed, 2));
+        assert_eq!(
+            effects.get(StatusKind::Blessed).unwrap().turns_remaining(),
+            5
+        );
+    }
+
+    #[test]
+    fn intensify_caps_stacks() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffect::new(StatusKind::Poisoned, 2).with_stacks(4));
+        effects.apply(StatusEffect::new(StatusKind::Poisoned, 4).with_stacks(4));
+        let poison = effects.get(StatusKind::Poisoned).unwrap();
+        assert_eq!(poison.stacks(), 5);
+        assert_eq!(poison.turns_remaining(), 4);
+    }
+
+    #[test]
+    fn first_application_caps_stacks() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffect::new(StatusKind::Poisoned, 2).with_stacks(10));
+        effects.apply(StatusEffect::new(StatusKind::BoggedDown, 2).with_stacks(3));
+        effects.apply(StatusEffect::new(StatusKind::Blessed, 2).with_stacks(3));
+        effects.apply(StatusEffect::new(StatusKind::Blessed, 2).with_stacks(3));
+        assert_eq!(effects.get(StatusKind::Poisoned).unwrap().stacks(), 5);
+        assert_eq!(effects.get(StatusKind::BoggedDown).unwrap().stacks(), 1);
+        assert_eq!(effects.get(StatusKind::Blessed).unwrap().stacks(), 1);
+    }
+
+    #[test]
+    fn modifiers_scale_with_stacks() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffect::new(StatusKind::Poisoned, 2).with_stacks(3));
+        effects.apply(StatusEffect::new(StatusKind::Blessed, 2));
+        let modifiers = effects.modifiers();
+        assert_eq!(modifiers.get(Stat::Bulk), -3);
+        assert_eq!(modifiers.get(Stat::Backbone), 1);
+        assert_eq!(modifiers.get(Stat::TheSight), 1);
+        assert_eq!(modifiers.get(Stat::Cunning), 0);
+    }
+
+    #[test]
+    fn poison_ticks_and_expires() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffect::new(StatusKind::Poisoned, 2).with_stacks(2));
+
+        let first = effects.tick();
+        assert_eq!(first.damage_rolls.len(), 2);
+        assert!((2..=8).contains(&first.total_damage()));
+        assert!(first.expired.is_empty());
+
+        let second = effects.tick();
+        assert_eq!(second.expired, vec![StatusKind::Poisoned]);
+        assert!(effects.is_empty());
+    }
+
+    #[test]
+    fn zero_duration_is_ignored() {
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusEffect::new(StatusKind::Terrified, 0));
+        assert!(effects.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn loaded_zero_duration_expires() {
+        let mut effects: StatusEffects = serde_json::from_str(
+            r#"{"effects": [{"kind": "BoggedDown", "turns_remaining": 0, "stacks": 1}]}"#,
+        )
+        .unwrap();
+        assert_eq!(effects.tick().expired, vec![StatusKind::BoggedDown]);
+        assert!(effects.is_empty());
+    }
+}
@@ -0,0 +1,318 @@
+//! Transcripts: recorded playthroughs of an adventure, replayed through a [`ScriptedFrontend`]
+//! with seeded dice to check that the game still says what it should.
+//!
+//! A transcript is a text file with one entry per line:
+//!
+//! | Line                | Meaning                                                         |
+//! |---------------------|-----------------------------------------------------------------|
+//! | `@adventure <id>`   | The adventure to play. Required.                                |
+//! | `@seed <number>`    | The seed for [`seed_dice`]. Defaults to `0`.                    |
+//! | `> <answer>`        | The next answer to a prompt: typed text, or an option's number or text. |
+//! | `# <comment>`       | Ignored, as are blank lines.                                    |
+//! | anything else       | Text expected in the output, after the previous expected text.  |
+//!
+//! Answers are given in order whenever the game asks for one, so expected text can be written
+//! between them to keep the transcript readable, but it is only checked against the output once
+//! the whole playthrough has finished.
+
+use std::{fmt::Display, io, str::FromStr, sync::LazyLock};
+
+use camino::Utf8PathBuf;
+
+use crate::{
+    adventure::Adventure,
+    dice::seed_dice,
+    files::{try_list_files, try_load_text},
+    play::{PlayError, play},
+    ui::ScriptedFrontend,
+};
+
+// TODO this path is only for testing purposes; eventually it will need to be determined based on
+// config and the like
+static TRANSCRIPT_DIR: LazyLock<Utf8PathBuf> = LazyLock::new(|| {
+    let mut path = Utf8PathBuf::new();
+    path.push(env!("CARGO_MANIFEST_DIR"));
+    path.push("testfiles/transcripts/");
+    path
+});
+
+/// How many lines of output to show around a missing expectation.
+const CONTEXT_LINES: usize = 10;
+
+/// A recorded playthrough: the answers to give and the text expected in return.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    adventure: String,
+    seed: u64,
+    answers: Vec<String>,
+    expected: Vec<String>,
+}
+impl Transcript {
+    /// Load a [`Transcript`] from the filesystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `transcript_id` - A [`std::str`] corresponding to the ID of the desired transcript. The
+    ///   ID must match the name of the `.transcript` file on the disk.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`TranscriptError`] if the file can't be read or parsed.
+    pub fn try_load(transcript_id: &str) -> Result<Self, TranscriptError> {
+        let mut path: Utf8PathBuf = TRANSCRIPT_DIR.clone();
+        path.push(format!("{transcript_id}.transcript"));
+        try_load_text(path)?.parse()
+    }
+
+    /// List the IDs of every transcript on the filesystem, in alphabetical order.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the transcript directory cannot be read.
+    pub fn try_list() -> io::Result<Vec<String>> {
+        try_list_files(&*TRANSCRIPT_DIR, "transcript")
+    }
+
+    /// The ID of the adventure played.
+    #[must_use]
+    pub fn adventure(&self) -> &str {
+        &self.adventure
+    }
+
+    /// Play the adventure with this transcript's answers and seed, returning everything the game
+    /// showed, one entry per line.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`TranscriptError`] if the adventure can't be loaded, the game
+    /// stops early (e.g. because it asked for more answers than were given), or answers are
+    /// left over at the end.
+    pub fn replay(&self) -> Result<Vec<String>, TranscriptError> {
+        let adventure = Adventure::try_load(&self.adventure)?;
+        let mut frontend = ScriptedFrontend::new(self.answers.iter().cloned());
+
+        seed_dice(Some(self.seed));
+        let result = play(&mut frontend, &adventure);
+        seed_dice(None);
+
+        let output = frontend.output().to_vec();
+        if let Err(error) = result {
+            return Err(TranscriptError::Play { error, output });
+        }
+        let unused: Vec<String> = frontend.remaining().map(String::from).collect();
+        if !unused.is_empty() {
+            return Err(TranscriptError::UnusedAnswers { unused, output });
+        }
+        Ok(output)
+    }
+
+    /// [Replay](Transcript::replay) the transcript and check that every expected line appears in
+    /// the output, in order.
+    ///
+    /// # Errors
+    ///
+    /// This function returns a [`TranscriptError`] if the replay fails or some expected text is
+    /// missing.
+    pub fn verify(&self) -> Result<(), TranscriptError> {
+        let output = self.replay()?;
+        let mut from = 0;
+        for expected in &self.expected {
+            match output[from..]
+                .iter()
+                .position(|line| line.contains(expected.as_str()))
+            {
+                Some(offset) => from += offset + 1,
+                None => {
+                    return Err(TranscriptError::Missing {
+                        expected: expected.clone(),
+                        after: from,
+                        output,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+impl FromStr for Transcript {
+    type Err = TranscriptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut adventure = None;
+        let mut seed = 0;
+        let mut answers = Vec::new();
+        let mut expected = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let parse_error = |message: &str| TranscriptError::Parse {
+                line: i + 1,
+                message: String::from(message),
+            };
+            let line = line.trim_end();
+            if let Some(answer) = line.strip_prefix('>') {
+                answers.push(String::from(answer.trim()));
+            } else if let Some(directive) = line.strip_prefix('@') {
+                match directive.split_once(' ') {
+                    Some(("adventure", id)) => adventure = Some(String::from(id.trim())),
+                    Some(("seed", value)) => {
+                        seed = value
+                            .trim()
+                            .parse()
+                            .map_err(|_| parse_error("the seed must be a whole number"))?;
+                    }
+                    _ => return Err(parse_error("unknown directive")),
+                }
+            } else if !line.trim().is_empty() && !line.starts_with('#') {
+                expected.push(String::from(line.trim()));
+            }
+        }
+
+        Ok(Self {
+            adventure: adventure.ok_or(TranscriptError::Parse {
+                line: 0,
+                message: String::from("no @adventure given"),
+            })?,
+            seed,
+            answers,
+            expected,
+        })
+    }
+}
+
+/// The reasons a [`Transcript`] can't be loaded, replayed or verified.
+#[derive(Debug)]
+pub enum TranscriptError {
+    /// A file couldn't be loaded.
+    Io(io::Error),
+    /// The transcript isn't written correctly.
+    Parse {
+        /// The 1-based line number, or 0 if the problem isn't on any one line.
+        line: usize,
+        /// What is wrong.
+        message: String,
+    },
+    /// The game stopped before the playthrough finished.
+    Play {
+        /// Why it stopped.
+        error: PlayError,
+        /// Everything shown before it stopped.
+        output: Vec<String>,
+    },
+    /// The playthrough finished without using every answer.
+    UnusedAnswers {
+        /// The answers left over.
+        unused: Vec<String>,
+        /// Everything shown.
+        output: Vec<String>,
+    },
+    /// Some expected text wasn't in the output.
+    Missing {
+        /// The text expected.
+        expected: String,
+        /// The index of the output line after the previous expected text.
+        after: usize,
+        /// Everything shown.
+        output: Vec<String>,
+    },
+}
+impl Display for TranscriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "{err}"),
+            Self::Parse { line, message } => write!(f, "line {line}: {message}"),
+            Self::Play { error, output } => {
+                write!(f, "the game stopped early: {error}")?;
+                write_lines(f, output, output.len().saturating_sub(CONTEXT_LINES))
+            }
+            Self::UnusedAnswers { unused, output } => {
+                write!(f, "answers left over: {unused:?}")?;
+                write_lines(f, output, output.len().saturating_sub(CONTEXT_LINES))
+            }
+            Self::Missing {
+                expected,
+                after,
+                output,
+            } => {
+                write!(f, "expected {expected:?} after output line {after}")?;
+                write_lines(f, output, *after)
+            }
+        }
+    }
+}
+impl std::error::Error for TranscriptError {}
+impl From<io::Error> for TranscriptError {
+    fn from(value: io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+/// Write a few numbered lines of output, starting at `from`.
+fn write_lines(
+    f: &mut std::fmt::Formatter<'_>,
+    output: &[String],
+    from: usize,
+) -> std::fmt::Result {
+    writeln!(f, ", but the output was:")?;
+    for (i, line) in output.iter().enumerate().skip(from).take(CONTEXT_LINES) {
+        writeln!(f, "{i:>4} | {line}")?;
+    }
+    if from + CONTEXT_LINES < output.len() {
+        writeln!(f, "     | ...")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_transcript() {
+        let transcript: Transcript = "# A comment\n@adventure testadventure\n@seed 7\n\n> Smuggs\n\
+                                      Expected text  \n"
+            .parse()
+            .unwrap();
+        assert_eq!(transcript.adventure(), "testadventure");
+        assert_eq!(transcript.seed, 7);
+        assert_eq!(transcript.answers, ["Smuggs"]);
+        assert_eq!(transcript.expected, ["Expected text"]);
+
+        assert!(matches!(
+            "> Smuggs".parse::<Transcript>(),
+            Err(TranscriptError::Parse { line: 0, .. })
+        ));
+        assert!(matches!(
+            "@adventure testadventure\n@seed lots".parse::<Transcript>(),
+            Err(TranscriptError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn every_transcript_passes() {
+        let transcripts = Transcript::try_list().unwrap();
+        assert!(!transcripts.is_empty());
+        for id in transcripts {
+            if let Err(err) = Transcript::try_load(&id).unwrap().verify() {
+                panic!("transcript {id} failed: {err}");
+            }
+        }
+    }
+
+    #[test]
+    fn mismatches_are_reported() {
+        let mut transcript = Transcript::try_load(&Transcript::try_list().unwrap()[0]).unwrap();
+        transcript
+            .expected
+            .push(String::from("The swamp swallows you whole."));
+        let err = transcript.verify().unwrap_err();
+        assert!(matches!(err, TranscriptError::Missing { .. }));
+        assert!(err.to_string().contains("The swamp swallows you whole."));
+
+        transcript.answers.pop();
+        assert!(matches!(
+            transcript.replay(),
+            Err(TranscriptError::Play { .. })
+        ));
+    }
+}
@@ -1,4 +1,22 @@
 //! User interface functionality.
+//!
+//! [`input`] and [`select`] use one of two backends: arrow-key [`cli_prompts`] prompts, or plain
+//! numbered menus read line by line from stdin for pipes, scripts, dumb terminals and screen
+//! readers. Line mode is used automatically when stdin or stdout isn't a terminal, and can be
+//! chosen explicitly with [`set_backend`].
+//!
+//! Game logic that talks to the player should go through a [`Frontend`] instead, so that it can be
+//! played in a terminal with [`CliFrontend`] or driven by a [`ScriptedFrontend`] in tests.
+
+use std::{
+    collections::VecDeque,
+    env,
+    io::{self, BufRead, IsTerminal, Write},
+    sync::{
+        LazyLock,
+        atomic::{AtomicBool, Ordering},
+    },
+};
 
 use cli_prompts::{
     DisplayPrompt,
@@ -6,6 +24,48 @@ use cli_prompts::{
     style::{Formatting, InputStyle},
 };
 
+use crate::character::{Character, Stat};
+
+/// Whether [`input`] and [`select`] use line mode.
+static LINE_MODE: LazyLock<AtomicBool> =
+    LazyLock::new(|| AtomicBool::new(Backend::detect() == Backend::Lines));
+
+/// How prompts are shown to the player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    /// Interactive prompts chosen with the arrow keys.
+    Prompts,
+    /// Numbered menus and plain line input.
+    Lines,
+}
+impl Backend {
+    /// [`Backend::Lines`] if stdin or stdout isn't a terminal or the terminal is dumb, otherwise
+    /// [`Backend::Prompts`].
+    #[must_use]
+    pub fn detect() -> Self {
+        let dumb = env::var("TERM").is_ok_and(|term| term == "dumb");
+        if dumb || !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+            Self::Lines
+        } else {
+            Self::Prompts
+        }
+    }
+}
+
+/// The backend [`input`] and [`select`] currently use.
+pub fn backend() -> Backend {
+    if LINE_MODE.load(Ordering::Relaxed) {
+        Backend::Lines
+    } else {
+        Backend::Prompts
+    }
+}
+
+/// Choose the backend [`input`] and [`select`] use, overriding [`Backend::detect`].
+pub fn set_backend(backend: Backend) {
+    LINE_MODE.store(backend == Backend::Lines, Ordering::Relaxed);
+}
+
 /// Text input prompt. Performs the validation and transformation function, continuing the prompt
 /// until a valid input is given.
 ///
@@ -16,6 +76,9 @@ pub fn input<F, T>(prompt: &str, f: F) -> Result<T, AbortReason>
 where
     F: Fn(&str) -> Result<T, String>,
 {
+    if backend() == Backend::Lines {
+        return line_input(&mut io::stdin().lock(), &mut io::stdout().lock(), prompt, f);
+    }
     Input::new(prompt, f)
         .style(InputStyle::default().default_value_formatting(Formatting::default().bold()))
         .display()
@@ -31,6 +94,394 @@ where
     S: Clone,
     for<'a> &'a S: Into<String>,
 {
+    if backend() == Backend::Lines {
+        return line_select(
+            &mut io::stdin().lock(),
+            &mut io::stdout().lock(),
+            prompt,
+            options,
+        );
+    }
     let prompt = Selection::new(prompt, options.iter());
     prompt.display().cloned()
 }
+
+/// Line mode [`input`]: write the prompt, then read lines from `reader` until one passes
+/// validation, writing the reason after each one that doesn't.
+///
+/// # Errors
+///
+/// This function returns [`AbortReason::Interrupt`] if `reader` runs out of lines, or
+/// [`AbortReason::Error`] if reading or writing fails.
+pub fn line_input<R, W, F, T>(
+    reader: &mut R,
+    writer: &mut W,
+    prompt: &str,
+    f: F,
+) -> Result<T, AbortReason>
+where
+    R: BufRead,
+    W: Write,
+    F: Fn(&str) -> Result<T, String>,
+{
+    loop {
+        write!(writer, "{prompt}: ").map_err(AbortReason::Error)?;
+        writer.flush().map_err(AbortReason::Error)?;
+        let line = read_line(reader)?;
+        match f(&line) {
+            Ok(value) => return Ok(value),
+            Err(message) => writeln!(writer, "{message}").map_err(AbortReason::Error)?,
+        }
+    }
+}
+
+/// Line mode [`select`]: write the options as a numbered menu, then read lines from `reader`
+/// until one is either an option's number or its text.
+///
+/// # Errors
+///
+/// This function returns [`AbortReason::Interrupt`] if `reader` runs out of lines, or
+/// [`AbortReason::Error`] if reading or writing fails.
+pub fn line_select<R, W, S>(
+    reader: &mut R,
+    writer: &mut W,
+    prompt: &str,
+    options: &[S],
+) -> Result<S, AbortReason>
+where
+    R: BufRead,
+    W: Write,
+    S: Clone,
+    for<'a> &'a S: Into<String>,
+{
+    let labels: Vec<String> = options.iter().map(Into::into).collect();
+    writeln!(writer, "{prompt}").map_err(AbortReason::Error)?;
+    for (i, label) in labels.iter().enumerate() {
+        writeln!(writer, "  {}) {label}", i + 1).map_err(AbortReason::Error)?;
+    }
+    line_input(reader, writer, "Enter a number", |line| {
+        find_option(&labels, line)
+            .map(|i| options[i].clone())
+            .ok_or_else(|| format!("Please enter a number from 1 to {}", labels.len()))
+    })
+}
+
+/// The index of the option an answer picks: either its 1-based number or its text, ignoring case.
+fn find_option(labels: &[String], answer: &str) -> Option<usize> {
+    match answer.parse::<usize>() {
+        Ok(number) => number.checked_sub(1).filter(|&i| i < labels.len()),
+        Err(_) => labels
+            .iter()
+            .position(|label| label.eq_ignore_ascii_case(answer)),
+    }
+}
+
+/// Read one line, without its line ending or surrounding whitespace.
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String, AbortReason> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).map_err(AbortReason::Error)? == 0 {
+        return Err(AbortReason::Interrupt);
+    }
+    Ok(String::from(line.trim()))
+}
+
+/// Everything the game needs from whatever the player is using to play it.
+pub trait Frontend {
+    /// Show the player some text.
+    fn show_text(&mut self, text: &str);
+
+    /// Ask the player to type a line of text, asking again until `validate` accepts it.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`AbortReason`] when the input is unexpectedly terminated.
+    fn input(
+        &mut self,
+        prompt: &str,
+        validate: &dyn Fn(&str) -> Result<(), String>,
+    ) -> Result<String, AbortReason>;
+
+    /// Ask the player to pick one of `options`, returning its index.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`AbortReason`] when the selection is unexpectedly terminated.
+    fn select(&mut self, prompt: &str, options: &[String]) -> Result<usize, AbortReason>;
+
+    /// Show the player a character sheet.
+    fn show_character(&mut self, character: &Character) {
+        self.show_text(&character.to_string());
+    }
+
+    /// [`Frontend::input`], transforming the accepted text with `f` as [`input`] does.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`AbortReason`] when the input is unexpectedly terminated.
+    fn input_with<F, T>(&mut self, prompt: &str, f: F) -> Result<T, AbortReason>
+    where
+        Self: Sized,
+        F: Fn(&str) -> Result<T, String>,
+    {
+        loop {
+            let line = self.input(prompt, &|line| f(line).map(|_| ()))?;
+            // Checked by the validator, but a frontend could still return something else
+            if let Ok(value) = f(&line) {
+                return Ok(value);
+            }
+        }
+    }
+
+    /// [`Frontend::select`], returning the chosen option itself as [`select`] does.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an [`AbortReason`] when the selection is unexpectedly terminated.
+    fn select_from<S>(&mut self, prompt: &str, options: &[S]) -> Result<S, AbortReason>
+    where
+        Self: Sized,
+        S: Clone,
+        for<'a> &'a S: Into<String>,
+    {
+        let labels: Vec<String> = options.iter().map(Into::into).collect();
+        loop {
+            if let Some(option) = options.get(self.select(prompt, &labels)?) {
+                return Ok(option.clone());
+            }
+        }
+    }
+}
+
+/// A [`Frontend`] on stdin and stdout, using [`input`] and [`select`] and so whichever
+/// [`Backend`] is chosen.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CliFrontend;
+impl Frontend for CliFrontend {
+    fn show_text(&mut self, text: &str) {
+        println!("{text}");
+    }
+
+    fn input(
+        &mut self,
+        prompt: &str,
+        validate: &dyn Fn(&str) -> Result<(), String>,
+    ) -> Result<String, AbortReason> {
+        input(prompt, |line| validate(line).map(|()| String::from(line)))
+    }
+
+    fn select(&mut self, prompt: &str, options: &[String]) -> Result<usize, AbortReason> {
+        let numbered: Vec<Numbered<'_>> = options
+            .iter()
+            .enumerate()
+            .map(|(index, label)| Numbered { index, label })
+            .collect();
+        Ok(select(prompt, &numbered)?.index)
+    }
+}
+
+/// An option for [`select`] that remembers where it was in the list, so that options with the
+/// same text can be told apart.
+#[derive(Debug, Clone, Copy)]
+struct Numbered<'a> {
+    index: usize,
+    label: &'a str,
+}
+impl From<&Numbered<'_>> for String {
+    fn from(value: &Numbered<'_>) -> Self {
+        Self::from(value.label)
+    }
+}
+
+/// A [`Frontend`] that answers every prompt from a list of answers given up front, recording
+/// everything it is shown. Made for testing.
+///
+/// Selections are answered with either the option's 1-based number or its text, and an answer
+/// that matches neither is an error. Typed answers that are rejected are recorded along with the
+/// reason, and the next answer is tried.
+#[derive(Debug, Default, Clone)]
+pub struct ScriptedFrontend {
+    answers: VecDeque<String>,
+    output: Vec<String>,
+}
+impl ScriptedFrontend {
+    /// Create a frontend that gives these answers, in order.
+    pub fn new<I, S>(answers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            answers: answers.into_iter().map(Into::into).collect(),
+            output: Vec::new(),
+        }
+    }
+
+    /// Everything shown so far, including prompts and rejected answers, one entry per line.
+    #[must_use]
+    pub fn output(&self) -> &[String] {
+        &self.output
+    }
+
+    /// The answers that haven't been given yet.
+    pub fn remaining(&self) -> impl Iterator<Item = &str> {
+        self.answers.iter().map(String::as_str)
+    }
+
+    /// The next answer, recorded after the prompt.
+    fn answer(&mut self, prompt: &str) -> Result<String, AbortReason> {
+        let answer = self.answers.pop_front().ok_or(AbortReason::Interrupt)?;
+        self.output.push(format!("{prompt}: {answer}"));
+        Ok(answer)
+    }
+}
+impl Frontend for ScriptedFrontend {
+    fn show_text(&mut self, text: &str) {
+        self.output.extend(text.lines().map(String::from));
+    }
+
+    fn input(
+        &mut self,
+        prompt: &str,
+        validate: &dyn Fn(&str) -> Result<(), String>,
+    ) -> Result<String, AbortReason> {
+        loop {
+            let answer = self.answer(prompt)?;
+            match validate(&answer) {
+                Ok(()) => return Ok(answer),
+                Err(message) => self.output.push(message),
+            }
+        }
+    }
+
+    fn select(&mut self, prompt: &str, options: &[String]) -> Result<usize, AbortReason> {
+        self.output.push(String::from(prompt));
+        for (i, option) in options.iter().enumerate() {
+            self.output.push(format!("  {}) {option}", i + 1));
+        }
+        let answer = self.answer("Enter a number")?;
+        find_option(options, &answer).ok_or_else(|| {
+            AbortReason::Error(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("no option {answer:?} in {options:?}"),
+            ))
+        })
+    }
+}
+
+/// Prompt the user to spend all of the given [`Character`]'s unspent stat points, one
+/// selection at a time.
+///
+/// # Errors
+///
+/// This function returns an [`AbortReason`] when the selection is unexpectedly terminated. Any
+/// stat points spent before that point remain spent.
+pub fn level_up<F>(frontend: &mut F, character: &mut Character) -> Result<(), AbortReason>
+where
+    F: Frontend,
+{
+    while character.stat_points() > 0 {
+        let prompt = format!(
+            "Choose a stat to raise ({} point(s) remaining)",
+            character.stat_points()
+        );
+        let stat = frontend.select_from(&prompt, &Stat::ALL)?;
+        character.raise_stat(stat);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "fs-content")]
+    use crate::{
+        character::{Class, Race, validate_name},
+        progression::ClassProgression,
+    };
+
+    #[test]
+    fn line_input_retries_until_valid() {
+        let mut reader = "\n  abc \n12\n".as_bytes();
+        let mut output = Vec::new();
+        let value = line_input(&mut reader, &mut output, "Age", |line| {
+            line.parse::<usize>()
+                .map_err(|_| String::from("Not a number"))
+        })
+        .unwrap();
+        assert_eq!(value, 12);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Age: Not a number\nAge: Not a number\nAge: "
+        );
+
+        let mut reader = "".as_bytes();
+        let result = line_input(&mut reader, &mut Vec::new(), "Age", |_| Ok(()));
+        assert!(matches!(result, Err(AbortReason::Interrupt)));
+    }
+
+    #[test]
+    fn line_select_by_number_or_name() {
+        let mut reader = "0\n9\nbulk\n".as_bytes();
+        let mut output = Vec::new();
+        let stat = line_select(&mut reader, &mut output, "Pick a stat", &Stat::ALL).unwrap();
+        assert_eq!(stat, Stat::Bulk);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("Pick a stat\n  1) "));
+        assert!(output.contains("Please enter a number from 1 to"));
+
+        let mut reader = "2\n".as_bytes();
+        let stat = line_select(&mut reader, &mut Vec::new(), "Pick a stat", &Stat::ALL).unwrap();
+        assert_eq!(stat, Stat::ALL[1]);
+    }
+
+    #[cfg(feature = "fs-content")]
+    #[test]
+    fn scripted_frontend_levels_up() {
+        let mut frontend = ScriptedFrontend::new(["", "Ed", "Bulk", "1", "unused"]);
+        let name: String = frontend
+            .input_with("Character name", |name| {
+                validate_name(name)
+                    .map(|()| String::from(name))
+                    .map_err(|e| e.to_string())
+            })
+            .unwrap();
+        let mut character = Character::new(
+            &name,
+            Class::Warden,
+            Race::AlligatorFolk,
+            Stat::Cunning,
+            Stat::TheSight,
+        );
+        let progression = ClassProgression::try_load(Class::Warden).unwrap();
+        character.gain_experience(300, &progression);
+        assert_eq!(character.stat_points(), 2);
+
+        let before = character.base_stats().clone();
+        level_up(&mut frontend, &mut character).unwrap();
+        assert_eq!(character.stat_points(), 0);
+        assert_eq!(
+            character.base_stats().get(Stat::Bulk),
+            before.get(Stat::Bulk) + 1
+        );
+        assert_eq!(
+            character.base_stats().get(Stat::ALL[0]),
+            before.get(Stat::ALL[0]) + 1
+        );
+        assert_eq!(frontend.remaining().collect::<Vec<_>>(), ["unused"]);
+
+        frontend.show_character(&character);
+        let output = frontend.output();
+        assert_eq!(output[0], "Character name: ");
+        assert_eq!(output[2], "Character name: Ed");
+        assert!(output.iter().any(|line| line.contains("Ed")));
+        assert!(matches!(
+            frontend.select("Again?", &[String::from("Yes")]),
+            Err(AbortReason::Error(_))
+        ));
+        assert!(matches!(
+            frontend.select("Again?", &[String::from("Yes")]),
+            Err(AbortReason::Interrupt)
+        ));
+    }
+}
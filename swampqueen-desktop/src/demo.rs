@@ -0,0 +1,241 @@
+//! A tour of the game's features, played through in a terminal one after another.
+
+use std::{cell::RefCell, fmt::Display, rc::Rc};
+
+use camino::Utf8PathBuf;
+use color_eyre::{Result, eyre::OptionExt};
+use swampqueen_core::{
+    adventure::Adventure,
+    character::{Resource, Stat},
+    combat::Creature,
+    dialogue::{Conversation, Npc},
+    dice::{roll_die, roll_many_from_str},
+    event::GameEvent,
+    game::{Game, GameError, GameState},
+    play::{PlayError, command_prompt, explore, fight, recruit_party},
+    progression::ClassProgression,
+    skill::Skill,
+    status::{StatusEffect, StatusKind},
+    ui::{CliFrontend, level_up, select},
+};
+
+/// Show off each part of the game in turn, from dice to combat.
+pub(crate) fn run() -> Result<()> {
+    println!("Let's roll some dice!");
+    // Roll dice. Store the RESULT of "roll_die" in a variable called "our_result".
+    let our_result = roll_die(6);
+    // ...print the result.
+    println!("{our_result}");
+
+    println!("I cast... LAME FIREBALL!!!!!");
+    println!("It onwy does 3d6 damage :c");
+
+    let die_1 = roll_die(6);
+    let die_2 = roll_die(6);
+    let die_3 = roll_die(6);
+
+    let damage = die_1 + die_2 + die_3;
+    println!("I rolled a {die_1}, a {die_2}, and a {die_3}...");
+    println!("I do {damage} damage!!!!!!");
+
+    println!("...");
+    println!("Actually...");
+    println!("I CAST REAL FIREBALL!!!!!!!!");
+    let fireball_result = roll_many_from_str("8d6")?;
+    println!("{fireball_result}");
+
+    println!("\n=========Character Creation Test=========\n");
+
+    let adventure_id =
+        select("Please select an adventure", &Adventure::try_list()?).map_err(PlayError::Abort)?;
+    let adventure = Adventure::try_load(&adventure_id)?;
+
+    let mut game = Game::default();
+    let tally = Rc::new(RefCell::new(EventTally::default()));
+    let recorder = Rc::clone(&tally);
+    game.subscribe(move |event| recorder.borrow_mut().record(event));
+
+    recruit_party(&mut CliFrontend, &mut game, &adventure)?;
+
+    println!("\n=========Quest Test=========\n");
+    game.start_quest("mossbacks_secret")?;
+    println!("{}", game.state().journal());
+
+    println!("\n=========Location Test=========\n");
+    explore(&mut CliFrontend, &mut game, adventure.start_location())?;
+
+    println!("\n=========Command Test=========\n");
+    command_prompt(&mut CliFrontend, &mut game)?;
+
+    println!("\n=========Save Test=========\n");
+    let mut save_path = Utf8PathBuf::try_from(std::env::temp_dir())?;
+    save_path.push("swampqueen-desktop-save.json");
+    game.state().try_save(&save_path)?;
+    let loaded = GameState::try_load(&save_path)?;
+    println!("Saved to {save_path}. Rolls remembered:");
+    for roll in loaded.roll_history() {
+        println!("  {roll}");
+    }
+
+    println!("\n=========Status Effect Test=========\n");
+    let character = game.party_mut().ok_or(GameError::NoParty)?.leader_mut();
+    character.apply_status_effect(StatusEffect::new(StatusKind::Poisoned, 3));
+    character.apply_status_effect(StatusEffect::new(StatusKind::BoggedDown, 2));
+    while !character.status_effects().is_empty() {
+        let tick = character.tick_status_effects();
+        for (kind, damage) in &tick.damage_rolls {
+            println!("{kind}: took {damage} damage");
+        }
+        for kind in &tick.expired {
+            println!("{kind} wore off");
+        }
+    }
+
+    println!("{character}");
+
+    println!("\n=========Level Up Test=========\n");
+    let progression = ClassProgression::try_load(character.class())?;
+    for reward in character.gain_experience(300, &progression) {
+        println!("You reached level {}!", reward.level);
+        for unlock in &reward.unlocks {
+            println!("You unlocked {unlock}!");
+        }
+    }
+    level_up(&mut CliFrontend, character).map_err(PlayError::Abort)?;
+
+    println!("{character}");
+
+    println!("\n=========Dialogue Test=========\n");
+    let mossback = Npc::try_load("old_mossback")?;
+    let mut conversation = Conversation::try_start(mossback)?;
+    while let Some(node) = conversation.node() {
+        for paragraph in node.text() {
+            println!("{paragraph}\n");
+        }
+        let (indices, responses): (Vec<usize>, Vec<String>) = conversation
+            .responses(game.party().ok_or(GameError::NoParty)?, game.state())
+            .map(|(i, response)| (i, String::from(response)))
+            .unzip();
+        let chosen: String =
+            select(conversation.npc().name(), &responses).map_err(PlayError::Abort)?;
+        let index = indices[responses
+            .iter()
+            .position(|r| *r == chosen)
+            .ok_or_eyre("no such response")?];
+        let outcome = game.respond(&mut conversation, index)?;
+        if let Some(check) = outcome.check {
+            println!("{check}");
+        }
+        if let Some(creatures) = outcome.combat {
+            let enemies = creatures
+                .iter()
+                .map(|id| Creature::try_load(id))
+                .collect::<Result<_, _>>()?;
+            fight(&mut CliFrontend, &mut game, enemies)?;
+        }
+    }
+    for line in game.update_quests()? {
+        println!("{line}");
+    }
+    spend_stat_points(&mut game)?;
+
+    println!("\n=========Combat Test=========\n");
+    fight(
+        &mut CliFrontend,
+        &mut game,
+        vec![
+            Creature::try_load("swamp_rat")?,
+            Creature::try_load("bog_lurker")?,
+        ],
+    )?;
+
+    println!("\n=========Rest & Check Test=========\n");
+    for member in game.party_mut().ok_or(GameError::NoParty)?.members_mut() {
+        member.rest();
+    }
+    let party = game.party().ok_or(GameError::NoParty)?;
+    let sneak = party.group_check(|member| member.check(Stat::Slipperiness, 15));
+    println!("Your party tries to slip past the sleeping heron...\n{sneak}");
+
+    let leader = party.leader_index();
+    let mut check = game.check(leader, Stat::Slipperiness, 15)?;
+    let character = game.party_mut().ok_or(GameError::NoParty)?.leader_mut();
+    println!("{} tries to slip past alone... {check}", character.name());
+    while !check.passed() && character.resource(Resource::Luck) > 0 {
+        let options = [
+            String::from("Spend a point of luck to reroll"),
+            String::from("Accept fate"),
+        ];
+        if select("Your foot squelches loudly!", &options).map_err(PlayError::Abort)? != options[0]
+        {
+            break;
+        }
+        if let Some(reroll) = character.reroll_check(&check) {
+            check = reroll;
+            println!("You try again... {check}");
+        }
+    }
+
+    let party = game.party().ok_or(GameError::NoParty)?;
+    if let Some((member, herbs)) =
+        party.best_check(|member| member.skill_check(Skill::Herbalism, 12))
+    {
+        println!("{} searches the bank for bogbane... {herbs}", member.name());
+    }
+
+    for member in party.members() {
+        println!("{member}");
+    }
+
+    println!("\n=========Journal=========\n");
+    println!("{}", game.state().journal());
+    println!("\nAlong the way: {}", tally.borrow());
+    Ok(())
+}
+
+/// Running totals of what happened during the game, shown at the end.
+#[derive(Debug, Default)]
+struct EventTally {
+    locations_entered: usize,
+    dice_rolled: usize,
+    checks_passed: usize,
+    checks_failed: usize,
+    damage_taken: isize,
+    items_gained: usize,
+}
+impl EventTally {
+    fn record(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::LocationEntered { .. } => self.locations_entered += 1,
+            GameEvent::DiceRolled { .. } => self.dice_rolled += 1,
+            GameEvent::CheckMade { check, .. } if check.passed() => self.checks_passed += 1,
+            GameEvent::CheckMade { .. } => self.checks_failed += 1,
+            GameEvent::DamageTaken { amount, .. } => self.damage_taken += amount,
+            GameEvent::ItemGained { count, .. } => self.items_gained += count,
+            GameEvent::CharacterCreated { .. } | GameEvent::AbilityUsed { .. } => {}
+        }
+    }
+}
+impl Display for EventTally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} location(s) explored, {} roll(s) made, {} check(s) passed and {} failed, \
+             {} damage taken and {} item(s) found",
+            self.locations_entered,
+            self.dice_rolled,
+            self.checks_passed,
+            self.checks_failed,
+            self.damage_taken,
+            self.items_gained
+        )
+    }
+}
+
+/// Let the player spend every hero's unspent stat points.
+fn spend_stat_points(game: &mut Game) -> Result<()> {
+    for hero in game.party_mut().ok_or(GameError::NoParty)?.members_mut() {
+        level_up(&mut CliFrontend, hero).map_err(PlayError::Abort)?;
+    }
+    Ok(())
+}
@@ -1,96 +1,239 @@
-use std::io::{self, Write};
+//! The desktop client for Swamp Queen, played in a terminal. Run with `--help` for the commands it
+//! takes; with none, it plays an adventure.
 
+use std::{fs, io};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use clap::{Args, Parser, Subcommand};
+use color_eyre::{
+    Result,
+    eyre::{WrapErr, bail},
+};
 use swampqueen_core::{
-    character::{Character, Class, Race, Stat},
-    dice::{roll_die, roll_many_from_str},
-    location::Location,
-    ui::{input, select},
+    adventure::Adventure,
+    content::{self, Directory},
+    dice::seed_dice,
+    game::{Game, SavedGame},
+    play::{PlayError, command_prompt, create_character, explore, recruit_party, resume},
+    ui::{Backend, CliFrontend, Frontend, select, set_backend},
 };
 
-fn main() {
-    println!("Let's roll some dice!");
-    // Roll dice. Store the RESULT of "roll_die" in a variable called "our_result".
-    let our_result = roll_die(6);
-    // ...print the result.
-    println!("{our_result}");
-
-    println!("I cast... LAME FIREBALL!!!!!");
-    println!("It onwy does 3d6 damage :c");
-
-    let die_1 = roll_die(6);
-    let die_2 = roll_die(6);
-    let die_3 = roll_die(6);
-
-    let damage = die_1 + die_2 + die_3;
-    println!("I rolled a {die_1}, a {die_2}, and a {die_3}...");
-    println!("I do {damage} damage!!!!!!");
-
-    println!("...");
-    println!("Actually...");
-    println!("I CAST REAL FIREBALL!!!!!!!!");
-    let fireball_result = roll_many_from_str("8d6").unwrap();
-    println!("{fireball_result}");
-
-    println!("\n=========Location Test=========\n");
-    let testplace = Location::try_load_location("testplace").unwrap();
-    println!("{}\n", testplace.paragraph("intro", 0).unwrap());
-    println!("{}\n", testplace.paragraph("intro", 1).unwrap());
-    println!("{}\n", testplace.paragraph("intro", 2).unwrap());
-
-    println!("\n=========Character Creation Test=========\n");
-
-    let chosen_name: String = input("Character name", |input| {
-        if input.is_empty() {
-            Err("You can't have an empty name!".into())
-        } else {
-            Ok(String::from(input))
+mod demo;
+mod validate;
+
+/// A text RPG set in the swamp.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Cli {
+    /// Use numbered menus instead of arrow-key prompts, for screen readers and scripted input.
+    #[arg(long, global = true)]
+    plain: bool,
+
+    /// The directory saved games are kept in.
+    #[arg(long, global = true, value_name = "DIR", default_value = "saves")]
+    save_dir: Utf8PathBuf,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// What to do, if not to [play](Command::Play).
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Play an adventure.
+    Play(PlayArgs),
+    /// Create a character and show their character sheet.
+    NewCharacter,
+    /// Roll dice, e.g. `roll 4d6kh3` or `roll 1d20 + 2`.
+    Roll {
+        /// The dice to roll.
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        dice: Vec<String>,
+    },
+    /// Check that every piece of content in a directory can be loaded.
+    Validate {
+        /// The content directory, with a folder for each kind of content.
+        dir: Utf8PathBuf,
+    },
+    /// List the saved games.
+    ListSaves,
+    /// Try out each part of the game in turn, from dice to combat.
+    Demo,
+}
+
+/// How to [play](Command::Play) an adventure.
+#[derive(Debug, Default, Args)]
+struct PlayArgs {
+    /// Continue the story saved in this slot, and save it there when the game ends.
+    #[arg(long, value_name = "SLOT", value_parser = parse_slot)]
+    save: Option<String>,
+
+    /// Seed the dice, so that the same choices lead to the same rolls.
+    #[arg(long, value_name = "N")]
+    seed: Option<u64>,
+
+    /// Load content from this directory before the built-in content.
+    #[arg(long, value_name = "DIR")]
+    content: Option<Utf8PathBuf>,
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+    let cli = Cli::parse();
+    if cli.plain {
+        set_backend(Backend::Lines);
+    }
+
+    match cli.command.unwrap_or(Command::Play(PlayArgs::default())) {
+        Command::Play(args) => play(args, &cli.save_dir),
+        Command::NewCharacter => new_character(),
+        Command::Roll { dice } => roll(&dice.join(" ")),
+        Command::Validate { dir } => validate::run(&dir),
+        Command::ListSaves => list_saves(&cli.save_dir),
+        Command::Demo => demo::run(),
+    }
+}
+
+/// Play an adventure from the start, or carry on the party's story from a save slot, saving it there
+/// when the game ends, even if it ends because the player stopped answering.
+fn play(args: PlayArgs, save_dir: &Utf8Path) -> Result<()> {
+    if let Some(dir) = args.content {
+        if !dir.is_dir() {
+            bail!("there is no content directory at {dir}");
+        }
+        content::add_source(Directory::new(dir));
+    }
+    seed_dice(args.seed);
+
+    let save_path = args.save.map(|slot| save_path(save_dir, &slot));
+    let saved = match &save_path {
+        Some(path) if path.exists() => SavedGame::try_load(path)
+            .wrap_err_with(|| format!("couldn't load the saved game at {path}"))?,
+        _ => SavedGame::default(),
+    };
+
+    let mut game = Game::resume(saved)?;
+    let played = if game.party().is_some() && game.location().is_some() {
+        resume(&mut CliFrontend, &mut game).map_err(Into::into)
+    } else {
+        play_new(&mut game)
+    };
+
+    // Save whatever happened before the game ended, however it ended
+    if let Some(path) = save_path
+        && game.party().is_some()
+    {
+        fs::create_dir_all(save_dir)?;
+        game.save()
+            .try_save(&path)
+            .wrap_err_with(|| format!("couldn't save the game to {path}"))?;
+        println!("Saved to {path}.");
+    }
+    played
+}
+
+/// Choose an adventure, create a party for it, then explore it from the start.
+fn play_new(game: &mut Game) -> Result<()> {
+    let adventure = choose_adventure()?;
+    recruit_party(&mut CliFrontend, game, &adventure)?;
+    explore(&mut CliFrontend, game, adventure.start_location())?;
+    command_prompt(&mut CliFrontend, game)?;
+    Ok(())
+}
+
+/// Create a character for an adventure and show them, without playing it.
+fn new_character() -> Result<()> {
+    let adventure = choose_adventure()?;
+    let character = create_character(&mut CliFrontend, &adventure)?.build()?;
+    CliFrontend.show_character(&character);
+    Ok(())
+}
+
+/// Roll dice, with the default stats for any the dice are scaled by.
+fn roll(dice: &str) -> Result<()> {
+    println!("{}", Game::default().roll(dice)?);
+    Ok(())
+}
+
+/// List the slots of the games saved in the save directory, in alphabetical order.
+fn list_saves(save_dir: &Utf8Path) -> Result<()> {
+    let entries = match save_dir.read_dir_utf8() {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            println!("There are no saved games in {save_dir}.");
+            return Ok(());
+        }
+        Err(err) => return Err(err).wrap_err_with(|| format!("couldn't read {save_dir}")),
+    };
+
+    let mut slots = Vec::new();
+    for entry in entries {
+        let path = entry?.into_path();
+        if path.extension() == Some("json")
+            && let Some(slot) = path.file_stem()
+        {
+            slots.push(String::from(slot));
+        }
+    }
+    slots.sort();
+
+    if slots.is_empty() {
+        println!("There are no saved games in {save_dir}.");
+    }
+    for slot in slots {
+        println!("{slot}");
+    }
+    Ok(())
+}
+
+/// Ask the player which adventure to play, and load it.
+fn choose_adventure() -> Result<Adventure> {
+    let adventure_id =
+        select("Please select an adventure", &Adventure::try_list()?).map_err(PlayError::Abort)?;
+    Ok(Adventure::try_load(&adventure_id)?)
+}
+
+/// Check that a save slot is a plain name, so that its save stays in the save directory.
+fn parse_slot(slot: &str) -> std::result::Result<String, String> {
+    if slot.is_empty() || slot == "." || slot == ".." || slot.contains(std::path::is_separator) {
+        return Err(String::from(
+            "save slots can't be empty, `.` or `..`, or contain path separators",
+        ));
+    }
+    Ok(String::from(slot))
+}
+
+/// The path to the game saved in the given slot.
+fn save_path(save_dir: &Utf8Path, slot: &str) -> Utf8PathBuf {
+    save_dir.join(format!("{slot}.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn parse_arguments() {
+        Cli::command().debug_assert();
+
+        let cli = Cli::parse_from(["swampqueen", "roll", "1d20", "-", "2"]);
+        assert!(
+            matches!(cli.command, Some(Command::Roll { dice }) if dice.join(" ") == "1d20 - 2")
+        );
+
+        let cli = Cli::parse_from(["swampqueen", "play", "--save", "smuggs", "--seed", "7"]);
+        let Some(Command::Play(args)) = cli.command else {
+            panic!("expected the play command");
+        };
+        assert_eq!(args.save.as_deref(), Some("smuggs"));
+        assert_eq!(args.seed, Some(7));
+        assert_eq!(cli.save_dir, "saves");
+
+        assert!(Cli::try_parse_from(["swampqueen", "roll"]).is_err());
+        for slot in ["", "..", "../../x", "saves/smuggs"] {
+            assert!(Cli::try_parse_from(["swampqueen", "play", "--save", slot]).is_err());
         }
-    })
-    .unwrap();
-
-    let class = select(
-        "Please select your class",
-        &[
-            Class::Hunter,
-            Class::Warden,
-            Class::Bastion,
-            Class::Soothsayer,
-            Class::Trespasser,
-        ],
-    )
-    .unwrap();
-
-    let race = select(
-        "Please select your race",
-        &[Race::InsectoidFae, Race::GoblinoidFae, Race::AlligatorFolk],
-    )
-    .unwrap();
-
-    let stat_1 = select(
-        "Please select your first bonus stat",
-        &[
-            Stat::Slipperiness,
-            Stat::Cunning,
-            Stat::Bulk,
-            Stat::Backbone,
-            Stat::TheSight,
-        ],
-    )
-    .unwrap();
-
-    let stat_2 = select(
-        "Please select your second bonus stat",
-        &[
-            Stat::Slipperiness,
-            Stat::Cunning,
-            Stat::Bulk,
-            Stat::Backbone,
-            Stat::TheSight,
-        ],
-    )
-    .unwrap();
-
-    let character = Character::new(&chosen_name, class, race, stat_1, stat_2);
-
-    println!("{character}");
+    }
 }
@@ -0,0 +1,121 @@
+//! Checking a directory of content for anything the game can't load.
+
+use camino::Utf8Path;
+use color_eyre::{
+    Result,
+    eyre::{OptionExt, WrapErr, bail},
+};
+use swampqueen_core::{
+    ability::ClassAbilities,
+    adventure::Adventure,
+    background::Background,
+    character::Class,
+    combat::Creature,
+    content::{ContentKind, ContentSource, Directory, parse_json},
+    dialogue::{Dialogue, Npc},
+    location::Location,
+    progression::ClassProgression,
+    quest::Quest,
+    script::Script,
+};
+
+/// Load every piece of content in the directory, reporting each one that can't be loaded.
+pub(crate) fn run(dir: &Utf8Path) -> Result<()> {
+    if !dir.is_dir() {
+        bail!("there is no content directory at {dir}");
+    }
+    let source = Directory::new(dir);
+
+    let mut checked = 0;
+    let mut failed = 0;
+    for kind in ContentKind::ALL {
+        for id in source.list(kind)? {
+            checked += 1;
+            if let Err(err) = check(&source, kind, &id) {
+                failed += 1;
+                println!("{kind}/{id}: {err:#}");
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{failed} of {checked} pieces of content in {dir} can't be loaded");
+    }
+    println!("All {checked} pieces of content in {dir} can be loaded.");
+    Ok(())
+}
+
+/// Load a piece of content from the directory alone as whatever the game would load it as, so
+/// that nothing it needs can come from any other content.
+fn check(source: &Directory, kind: ContentKind, id: &str) -> Result<()> {
+    let text = source
+        .load_text(kind, id)?
+        .ok_or_eyre("it isn't in the directory")?;
+    match kind {
+        ContentKind::Adventure => {
+            let adventure: Adventure = parse_json(&text)?;
+            source
+                .load_text(ContentKind::Location, adventure.start_location())?
+                .ok_or_eyre("there is no such location in the directory")
+                .wrap_err_with(|| format!("its start location `{}`", adventure.start_location()))?;
+        }
+        ContentKind::Background => {
+            parse_json::<Background>(&text)?;
+        }
+        ContentKind::Class => {
+            Class::ALL
+                .into_iter()
+                .find(|class| class.id() == id)
+                .ok_or_eyre("there is no class with this ID")?;
+            parse_json::<ClassAbilities>(&text)?;
+            parse_json::<ClassProgression>(&text)?;
+        }
+        ContentKind::Creature => {
+            parse_json::<Creature>(&text)?;
+        }
+        ContentKind::Dialogue => {
+            parse_json::<Dialogue>(&text)?;
+        }
+        ContentKind::Location => {
+            parse_json::<Location>(&text)?;
+        }
+        ContentKind::Npc => {
+            parse_json::<Npc>(&text)?;
+        }
+        ContentKind::Quest => {
+            parse_json::<Quest>(&text)?;
+        }
+        ContentKind::Script => {
+            Script::compile(&text)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use camino::Utf8PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn start_location_must_be_in_the_directory() -> Result<()> {
+        let mut dir = Utf8PathBuf::try_from(std::env::temp_dir())?;
+        dir.push("swampqueen-validate-test");
+        fs::create_dir_all(dir.join("adventures"))?;
+        // The built-in content has this location, but the directory doesn't
+        fs::write(
+            dir.join("adventures/lost.json"),
+            r#"{"id": "lost", "name": "Lost", "start_location": "testplace"}"#,
+        )?;
+
+        let source = Directory::new(&dir);
+        let err = check(&source, ContentKind::Adventure, "lost").unwrap_err();
+        assert!(format!("{err:#}").contains("its start location `testplace`"));
+        assert!(run(&dir).is_err());
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
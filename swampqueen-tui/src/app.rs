@@ -0,0 +1,696 @@
+//! The state of the full-screen frontend: what the player is being asked, and what has happened
+//! so far. Drawing lives in [`crate::view`].
+
+use std::{cell::RefCell, rc::Rc};
+
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+use swampqueen_core::{
+    adventure::Adventure,
+    background::Background,
+    character::{BuildStep, CharacterBuilder, Class, Race, Stat, StatValues, validate_name},
+    creation::CreationMode,
+    event::GameEvent,
+    game::Game,
+    script::ScriptOutcome,
+};
+
+/// The most entries kept in the dice log.
+const DICE_LOG_LENGTH: usize = 50;
+
+/// The option offered in every creation prompt for going back a step.
+const BACK: &str = "<- Back";
+
+/// What the player is currently being asked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Prompt {
+    /// Type a line of text.
+    Input {
+        /// What is being asked for.
+        label: String,
+        /// What has been typed so far.
+        buffer: String,
+    },
+    /// Pick one of a list of options.
+    Select {
+        /// What is being asked for.
+        label: String,
+        /// The options, in order.
+        options: Vec<String>,
+        /// The index of the highlighted option.
+        selected: usize,
+    },
+}
+impl Prompt {
+    fn input(label: &str) -> Self {
+        Self::Input {
+            label: String::from(label),
+            buffer: String::new(),
+        }
+    }
+
+    fn select(label: &str, options: Vec<String>) -> Self {
+        Self::Select {
+            label: String::from(label),
+            options,
+            selected: 0,
+        }
+    }
+}
+
+/// What an answer to the current [`Prompt`] will be used for.
+#[derive(Debug)]
+enum Pending {
+    Adventure {
+        ids: Vec<String>,
+        names: Vec<String>,
+    },
+    Step(BuildStep),
+    PointBuy {
+        stat: usize,
+        purchased: StatValues,
+    },
+    KeepRolledStats,
+    Recruit,
+    /// The IDs of the choices offered at the location, then the directions of its exits.
+    Choice {
+        choices: Vec<String>,
+        exits: Vec<String>,
+    },
+}
+
+/// The whole frontend.
+#[derive(Debug)]
+pub struct App {
+    game: Game,
+    adventure: Option<Adventure>,
+    builder: Option<CharacterBuilder>,
+    backgrounds: Vec<Background>,
+    pending: Pending,
+    prompt: Prompt,
+    story: Vec<String>,
+    dice_log: Rc<RefCell<Vec<String>>>,
+    status: Option<String>,
+    shown_member: usize,
+    quit: bool,
+}
+impl App {
+    /// Start at adventure selection.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the adventures or backgrounds can't be loaded.
+    pub fn new() -> color_eyre::Result<Self> {
+        let adventures = Adventure::try_list()?;
+        let names: Vec<String> = adventures
+            .iter()
+            .map(|id| Adventure::try_load(id).map(|adventure| String::from(adventure.name())))
+            .collect::<Result<_, _>>()?;
+
+        let dice_log = Rc::new(RefCell::new(Vec::new()));
+        let mut game = Game::default();
+        let log = Rc::clone(&dice_log);
+        game.subscribe(move |event| {
+            let entry = match event {
+                GameEvent::DiceRolled { results } => results.to_string(),
+                GameEvent::CheckMade { character, check } => format!("{character}: {check}"),
+                _ => return,
+            };
+            let mut log = log.borrow_mut();
+            log.push(entry);
+            if log.len() > DICE_LOG_LENGTH {
+                log.remove(0);
+            }
+        });
+
+        Ok(Self {
+            game,
+            adventure: None,
+            builder: None,
+            backgrounds: Background::try_load_all()?,
+            prompt: Prompt::select("Please select an adventure", names.clone()),
+            pending: Pending::Adventure {
+                ids: adventures,
+                names,
+            },
+            story: vec![String::from("Welcome to the swamp.")],
+            dice_log,
+            status: None,
+            shown_member: 0,
+            quit: false,
+        })
+    }
+
+    /// The game being played.
+    pub const fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// What the player is currently being asked.
+    pub const fn prompt(&self) -> &Prompt {
+        &self.prompt
+    }
+
+    /// Everything that has been narrated so far, oldest first.
+    pub fn story(&self) -> &[String] {
+        &self.story
+    }
+
+    /// The most recent dice rolls and checks, oldest first.
+    pub fn dice_log(&self) -> Vec<String> {
+        self.dice_log.borrow().clone()
+    }
+
+    /// A message about the last answer, such as why it was rejected.
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_deref()
+    }
+
+    /// The index of the party member whose character sheet is shown.
+    pub const fn shown_member(&self) -> usize {
+        self.shown_member
+    }
+
+    /// Whether the player has asked to quit.
+    pub const fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// React to a key press.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Tab => {
+                let members = self.game.party().map_or(0, |party| party.members().len());
+                if members > 0 {
+                    self.shown_member = (self.shown_member + 1) % members;
+                }
+            }
+            KeyCode::Enter => self.submit(),
+            code => match &mut self.prompt {
+                Prompt::Input { buffer, .. } => match code {
+                    KeyCode::Char(c) => buffer.push(c),
+                    KeyCode::Backspace => {
+                        buffer.pop();
+                    }
+                    _ => {}
+                },
+                Prompt::Select {
+                    options, selected, ..
+                } if !options.is_empty() => match code {
+                    KeyCode::Up | KeyCode::Char('k') => {
+                        *selected = selected.checked_sub(1).unwrap_or(options.len() - 1);
+                    }
+                    KeyCode::Down | KeyCode::Char('j') => {
+                        *selected = (*selected + 1) % options.len();
+                    }
+                    _ => {}
+                },
+                Prompt::Select { .. } => {}
+            },
+        }
+    }
+
+    /// Answer the current prompt with what has been typed or highlighted.
+    fn submit(&mut self) {
+        self.status = None;
+        let answer = match &self.prompt {
+            Prompt::Input { buffer, .. } => Answer::Text(buffer.clone()),
+            Prompt::Select {
+                options, selected, ..
+            } => match options.get(*selected) {
+                Some(option) => Answer::Option(*selected, option.clone()),
+                None => {
+                    self.status = Some(String::from("There is nothing to choose here"));
+                    return;
+                }
+            },
+        };
+        if let Err(message) = self.answer(answer) {
+            self.status = Some(message);
+        }
+    }
+
+    /// Use an answer, then move on to the next prompt. Returns a message for the player if the
+    /// answer was rejected, leaving the prompt as it was.
+    fn answer(&mut self, answer: Answer) -> Result<(), String> {
+        let pending = std::mem::replace(&mut self.pending, Pending::Recruit);
+        let result = self.apply(&pending, answer);
+        if let Err(message) = result {
+            self.pending = pending;
+            return Err(message);
+        }
+        self.next_prompt().map_err(|err| err.to_string())
+    }
+
+    fn apply(&mut self, pending: &Pending, answer: Answer) -> Result<(), String> {
+        if let (Pending::Step(_), Answer::Option(_, option)) = (pending, &answer)
+            && option == BACK
+        {
+            if let Some(builder) = &mut self.builder {
+                builder.undo();
+            }
+            return Ok(());
+        }
+
+        match (pending, answer) {
+            (Pending::Adventure { ids, .. }, Answer::Option(index, _)) => {
+                let adventure = Adventure::try_load(&ids[index]).map_err(|e| e.to_string())?;
+                self.story
+                    .push(format!("You set off on {}.", adventure.name()));
+                self.builder = Some(CharacterBuilder::new(&adventure));
+                self.adventure = Some(adventure);
+            }
+            (Pending::Step(BuildStep::Name), Answer::Text(name)) => {
+                validate_name(&name).map_err(|e| e.to_string())?;
+                self.builder_mut()?
+                    .set_name(&name)
+                    .map_err(|e| e.to_string())?;
+            }
+            (Pending::Step(BuildStep::Class), Answer::Option(index, _)) => {
+                self.builder_mut()?.set_class(Class::ALL[index]);
+            }
+            (Pending::Step(BuildStep::Race), Answer::Option(index, _)) => {
+                self.builder_mut()?.set_race(Race::ALL[index]);
+            }
+            (Pending::Step(BuildStep::Background), Answer::Option(index, _)) => {
+                let background = self.backgrounds.get(index).cloned();
+                self.builder_mut()?.set_background(background);
+            }
+            (Pending::Step(BuildStep::BonusStat), Answer::Option(index, _)) => {
+                self.builder_mut()?
+                    .add_bonus_stat(Stat::ALL[index])
+                    .map_err(|e| e.to_string())?;
+            }
+            (Pending::PointBuy { stat, purchased }, Answer::Text(text)) => {
+                let value = text
+                    .trim()
+                    .parse::<isize>()
+                    .map_err(|_| String::from("Please enter a number"))?;
+                if let Some(CreationMode::PointBuy {
+                    budget,
+                    max_per_stat,
+                }) = self.builder.as_ref().map(CharacterBuilder::mode)
+                {
+                    let remaining = budget - purchased.total();
+                    if !(0..=*max_per_stat).contains(&value) {
+                        return Err(format!("Must be between 0 and {max_per_stat}"));
+                    }
+                    if value > remaining {
+                        return Err(format!("You only have {remaining} point(s) left"));
+                    }
+                }
+                let mut purchased = purchased.clone();
+                purchased.set(Stat::ALL[*stat], value);
+                if *stat + 1 < Stat::ALL.len() {
+                    self.pending = Pending::PointBuy {
+                        stat: stat + 1,
+                        purchased,
+                    };
+                } else {
+                    self.builder_mut()?
+                        .set_purchased_stats(purchased)
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+            (Pending::KeepRolledStats, Answer::Option(index, _)) => {
+                if index == 1 {
+                    self.builder_mut()?.reroll_stats();
+                    self.pending = Pending::KeepRolledStats;
+                } else {
+                    self.finish_character()?;
+                }
+            }
+            (Pending::Recruit, Answer::Option(index, _)) => {
+                if index == 0 {
+                    let adventure = self.adventure.as_ref().ok_or("No adventure chosen")?;
+                    self.builder = Some(CharacterBuilder::new(adventure));
+                } else {
+                    self.set_off()?;
+                }
+            }
+            (Pending::Choice { choices, exits }, Answer::Option(index, option)) => {
+                self.story.push(format!("> {option}"));
+                if let Some(choice_id) = choices.get(index) {
+                    let outcome = self.game.choose(choice_id).map_err(|e| e.to_string())?;
+                    self.narrate(&outcome)?;
+                } else {
+                    let direction = &exits[index - choices.len()];
+                    let location_id = self
+                        .game
+                        .location()
+                        .and_then(|location| location.exit(direction))
+                        .map(String::from)
+                        .ok_or_else(|| format!("There is no way {direction} from here"))?;
+                    self.enter(&location_id)?;
+                }
+            }
+            _ => return Err(String::from("That isn't an answer to this question")),
+        }
+        Ok(())
+    }
+
+    fn builder_mut(&mut self) -> Result<&mut CharacterBuilder, String> {
+        self.builder
+            .as_mut()
+            .ok_or_else(|| String::from("No character is being created"))
+    }
+
+    /// Add the finished character to the party.
+    fn finish_character(&mut self) -> Result<(), String> {
+        let builder = self.builder.take().ok_or("No character is being created")?;
+        self.game
+            .create_character(&builder)
+            .map_err(|e| e.to_string())?;
+        if let Some(party) = self.game.party() {
+            self.shown_member = party.members().len() - 1;
+            self.story.push(format!(
+                "{} joins the party.",
+                party.members()[self.shown_member].name()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Enter the adventure's first location.
+    fn set_off(&mut self) -> Result<(), String> {
+        let start = self
+            .adventure
+            .as_ref()
+            .map(|adventure| String::from(adventure.start_location()))
+            .ok_or("No adventure chosen")?;
+        self.enter(&start)
+    }
+
+    fn enter(&mut self, location_id: &str) -> Result<(), String> {
+        let outcome = self
+            .game
+            .enter_location(location_id)
+            .map_err(|e| e.to_string())?;
+        if let (Some(location), Some(context)) =
+            (self.game.location(), self.game.condition_context())
+        {
+            self.story.push(format!("== {} ==", location.name()));
+            self.story
+                .extend(location.paragraphs_for("intro", &context).map(String::from));
+        }
+        self.narrate(&outcome)
+    }
+
+    /// Show what a script said, following it if it sent the party elsewhere.
+    fn narrate(&mut self, outcome: &ScriptOutcome) -> Result<(), String> {
+        self.story.extend(outcome.messages.iter().cloned());
+        match &outcome.go_to {
+            Some(location_id) => self.enter(location_id),
+            None => Ok(()),
+        }
+    }
+
+    /// Work out what to ask next.
+    fn next_prompt(&mut self) -> color_eyre::Result<()> {
+        if matches!(
+            self.pending,
+            Pending::PointBuy { .. } | Pending::KeepRolledStats
+        ) {
+            self.prompt = self.prompt_for_pending();
+            return Ok(());
+        }
+
+        if let Some(builder) = &mut self.builder {
+            match builder.next_step() {
+                Some(BuildStep::RollStats) => {
+                    let rolled = builder.roll_stats()?;
+                    self.story.push(format!("You rolled:\n{}", rolled.stats()));
+                    self.pending = Pending::KeepRolledStats;
+                    if rolled.rerolls_left() == 0 {
+                        self.finish_character()
+                            .map_err(color_eyre::eyre::Report::msg)?;
+                        return self.next_prompt();
+                    }
+                }
+                Some(BuildStep::PointBuy) => {
+                    self.pending = Pending::PointBuy {
+                        stat: 0,
+                        purchased: StatValues::default(),
+                    };
+                }
+                Some(step) => self.pending = Pending::Step(step),
+                None => {
+                    self.finish_character()
+                        .map_err(color_eyre::eyre::Report::msg)?;
+                    return self.next_prompt();
+                }
+            }
+            self.prompt = self.prompt_for_pending();
+            return Ok(());
+        }
+
+        let party_is_full = self.game.party().is_some_and(|party| party.is_full());
+        if self.game.location().is_none() && !party_is_full {
+            self.pending = Pending::Recruit;
+        } else if self.game.location().is_none() {
+            self.set_off().map_err(color_eyre::eyre::Report::msg)?;
+            return self.next_prompt();
+        } else {
+            let choices = self
+                .game
+                .condition_context()
+                .map_or_else(Vec::new, |context| {
+                    self.game.location().map_or_else(Vec::new, |location| {
+                        location
+                            .choices_for(&context)
+                            .map(|choice| String::from(choice.id()))
+                            .collect()
+                    })
+                });
+            let exits = self.game.location().map_or_else(Vec::new, |location| {
+                location.exits().map(String::from).collect()
+            });
+            self.pending = Pending::Choice { choices, exits };
+        }
+        self.prompt = self.prompt_for_pending();
+        Ok(())
+    }
+
+    fn prompt_for_pending(&self) -> Prompt {
+        let with_back = |mut options: Vec<String>| {
+            options.push(String::from(BACK));
+            options
+        };
+        match &self.pending {
+            Pending::Adventure { names, .. } => {
+                Prompt::select("Please select an adventure", names.clone())
+            }
+            Pending::Step(BuildStep::Name) => Prompt::input("Character name"),
+            Pending::Step(BuildStep::Class) => Prompt::select(
+                "Please select your class",
+                with_back(Class::ALL.into_iter().map(String::from).collect()),
+            ),
+            Pending::Step(BuildStep::Race) => Prompt::select(
+                "Please select your race",
+                with_back(Race::ALL.into_iter().map(String::from).collect()),
+            ),
+            Pending::Step(BuildStep::Background) => {
+                let mut options: Vec<String> = self.backgrounds.iter().map(String::from).collect();
+                options.push(String::from("None"));
+                Prompt::select("Please select your background", with_back(options))
+            }
+            Pending::Step(BuildStep::BonusStat) => Prompt::select(
+                "Please select a bonus stat",
+                with_back(Stat::ALL.into_iter().map(String::from).collect()),
+            ),
+            Pending::Step(BuildStep::PointBuy | BuildStep::RollStats) => {
+                Prompt::input("Press Enter to continue")
+            }
+            Pending::PointBuy { stat, purchased } => {
+                let label = match self.builder.as_ref().map(CharacterBuilder::mode) {
+                    Some(CreationMode::PointBuy {
+                        budget,
+                        max_per_stat,
+                    }) => format!(
+                        "{} (0-{max_per_stat}, {} point(s) left)",
+                        Stat::ALL[*stat],
+                        budget - purchased.total()
+                    ),
+                    _ => Stat::ALL[*stat].to_string(),
+                };
+                Prompt::input(&label)
+            }
+            Pending::KeepRolledStats => Prompt::select(
+                "Keep or reroll?",
+                vec![String::from("Keep these stats"), String::from("Reroll")],
+            ),
+            Pending::Recruit => Prompt::select(
+                "Your party is ready?",
+                vec![
+                    String::from("Recruit another adventurer"),
+                    String::from("Set off"),
+                ],
+            ),
+            Pending::Choice { exits, .. } => {
+                let mut options: Vec<String> =
+                    self.game
+                        .condition_context()
+                        .map_or_else(Vec::new, |context| {
+                            self.game.location().map_or_else(Vec::new, |location| {
+                                location.choices_for(&context).map(String::from).collect()
+                            })
+                        });
+                options.extend(exits.iter().map(|direction| format!("Go {direction}")));
+                Prompt::select("Choose an action", options)
+            }
+        }
+    }
+}
+
+/// An answer to a [`Prompt`].
+#[derive(Debug)]
+enum Answer {
+    Text(String),
+    Option(usize, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn press(app: &mut App, code: KeyCode) {
+        app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+    }
+
+    fn type_line(app: &mut App, text: &str) {
+        for c in text.chars() {
+            press(app, KeyCode::Char(c));
+        }
+        press(app, KeyCode::Enter);
+    }
+
+    /// Highlight the option with the given text and choose it.
+    fn choose(app: &mut App, option: &str) {
+        let Prompt::Select { options, .. } = app.prompt() else {
+            panic!("expected a selection, got {:?}", app.prompt());
+        };
+        let index = options.iter().position(|o| o == option).unwrap();
+        for _ in 0..index {
+            press(app, KeyCode::Down);
+        }
+        press(app, KeyCode::Enter);
+    }
+
+    fn create(app: &mut App, name: &str) {
+        type_line(app, name);
+        choose(app, "Tresspasser");
+        choose(app, "Goblinoid Fae");
+        choose(app, "Bog Smuggler");
+        choose(app, "Cunning");
+        choose(app, "Bulk");
+    }
+
+    #[test]
+    fn play_through_creation_and_a_choice() {
+        let mut app = App::new().unwrap();
+        choose(&mut app, "Test Adventure");
+
+        // Invalid names and duplicate bonus stats are rejected with a message
+        type_line(&mut app, "   ");
+        assert!(app.status().is_some());
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Backspace);
+        type_line(&mut app, "Smuggs");
+        choose(&mut app, "Bastion");
+        choose(&mut app, BACK);
+        choose(&mut app, "Tresspasser");
+        choose(&mut app, "Goblinoid Fae");
+        choose(&mut app, "Bog Smuggler");
+        choose(&mut app, "Cunning");
+        choose(&mut app, "Cunning");
+        assert!(app.status().is_some());
+        choose(&mut app, "Bulk");
+
+        let party = app.game().party().unwrap();
+        assert_eq!(party.members()[0].class(), Class::Trespasser);
+        assert!(party.inventory().contains("hooded_lantern"));
+
+        choose(&mut app, "Recruit another adventurer");
+        create(&mut app, "Sneaks");
+        press(&mut app, KeyCode::Tab);
+        assert_eq!(app.shown_member(), 0);
+
+        choose(&mut app, "Set off");
+        assert!(app.story().iter().any(|s| s.contains("rusty key")));
+        assert!(app.game().state().journal().has_visited("testplace"));
+
+        choose(&mut app, "Look for the way out");
+        assert!(app.story().iter().any(|s| s.contains("swings open")));
+        assert!(
+            !app.game()
+                .party()
+                .unwrap()
+                .inventory()
+                .contains("rusty_key")
+        );
+
+        // A location with no choices still offers its exits
+        choose(&mut app, "Go north");
+        assert!(app.game().state().journal().has_visited("testcorridor"));
+        let Prompt::Select { options, .. } = app.prompt() else {
+            panic!("expected a selection, got {:?}", app.prompt());
+        };
+        assert_eq!(options, &["Go south"]);
+        choose(&mut app, "Go south");
+        assert_eq!(app.game().location().unwrap().id(), "testplace");
+
+        app.prompt = Prompt::select("Nothing to do", Vec::new());
+        press(&mut app, KeyCode::Up);
+        press(&mut app, KeyCode::Down);
+        press(&mut app, KeyCode::Enter);
+        assert_eq!(app.status(), Some("There is nothing to choose here"));
+
+        press(&mut app, KeyCode::Esc);
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn point_buy_and_rolled_stats() {
+        let mut app = App::new().unwrap();
+        choose(&mut app, "Test Adventure (Point Buy)");
+        type_line(&mut app, "Buyer");
+        choose(&mut app, "Warden");
+        choose(&mut app, "Insectoid Fae");
+        choose(&mut app, "None");
+        type_line(&mut app, "lots");
+        assert!(app.status().is_some());
+        for _ in 0..4 {
+            press(&mut app, KeyCode::Backspace);
+        }
+        // Each stat is checked against the cap and what is left of the budget
+        type_line(&mut app, "99");
+        assert_eq!(app.status(), Some("Must be between 0 and 5"));
+        press(&mut app, KeyCode::Backspace);
+        press(&mut app, KeyCode::Backspace);
+        for value in ["3", "3", "3", "3"] {
+            type_line(&mut app, value);
+        }
+        type_line(&mut app, "5");
+        assert_eq!(app.status(), Some("You only have 2 point(s) left"));
+        press(&mut app, KeyCode::Backspace);
+        type_line(&mut app, "2");
+        assert!(app.status().is_none(), "{:?}", app.status());
+        assert_eq!(app.game().party().unwrap().members().len(), 1);
+
+        let mut app = App::new().unwrap();
+        choose(&mut app, "Test Adventure (Rolled Stats)");
+        type_line(&mut app, "Roller");
+        choose(&mut app, "Hunter");
+        choose(&mut app, "Alligator Folk");
+        choose(&mut app, "None");
+        choose(&mut app, "Reroll");
+        choose(&mut app, "Keep these stats");
+        assert_eq!(app.game().party().unwrap().members().len(), 1);
+    }
+}
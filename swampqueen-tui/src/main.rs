@@ -0,0 +1,30 @@
+//! A full-screen terminal frontend for Swamp Queen, built on the core [`Game`] API.
+//!
+//! [`Game`]: swampqueen_core::game::Game
+
+use ratatui::crossterm::event::{self, Event};
+
+use crate::app::App;
+
+mod app;
+mod view;
+
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+    let mut app = App::new()?;
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+/// Draw the app and pass it key presses until the player quits.
+fn run(terminal: &mut ratatui::DefaultTerminal, app: &mut App) -> color_eyre::Result<()> {
+    while !app.should_quit() {
+        terminal.draw(|frame| view::draw(frame, app))?;
+        if let Event::Key(key) = event::read()? {
+            app.handle_key(key);
+        }
+    }
+    Ok(())
+}
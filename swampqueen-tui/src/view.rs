@@ -0,0 +1,174 @@
+//! Drawing the [`App`]: the story and the current prompt on the left, and the party on the right.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Text},
+    widgets::{Block, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::{App, Prompt};
+
+/// Draw every pane of the app.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [main, help] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [left, right] =
+        Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)]).areas(main);
+    let [story, prompt] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(10)]).areas(left);
+    let [sheet, inventory, dice] = Layout::vertical([
+        Constraint::Percentage(55),
+        Constraint::Percentage(20),
+        Constraint::Percentage(25),
+    ])
+    .areas(right);
+
+    draw_story(frame, app, story);
+    draw_prompt(frame, app, prompt);
+    draw_sheet(frame, app, sheet);
+    draw_inventory(frame, app, inventory);
+    draw_dice_log(frame, app, dice);
+
+    let help_text = app.status().map_or_else(
+        || String::from("Enter: choose  Up/Down: move  Tab: next party member  Esc: quit"),
+        String::from,
+    );
+    frame.render_widget(Paragraph::new(help_text), help);
+}
+
+/// The story so far, scrolled so the latest paragraph is visible.
+fn draw_story(frame: &mut Frame, app: &App, area: Rect) {
+    let title = app
+        .game()
+        .location()
+        .map_or("The Swamp", |location| location.name());
+    let mut text = Text::default();
+    for paragraph in app.story() {
+        text.extend(Text::raw(paragraph.as_str()));
+        text.push_line(Line::default());
+    }
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false });
+    let inner_height = area.height.saturating_sub(2);
+    let lines = paragraph.line_count(area.width.saturating_sub(2));
+    let scroll = u16::try_from(lines)
+        .unwrap_or(u16::MAX)
+        .saturating_sub(inner_height);
+    frame.render_widget(
+        paragraph
+            .block(Block::bordered().title(title))
+            .scroll((scroll, 0)),
+        area,
+    );
+}
+
+/// The current question, either as a text field or a list of options.
+fn draw_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    match app.prompt() {
+        Prompt::Input { label, buffer } => {
+            let field = Paragraph::new(format!("> {buffer}_"))
+                .block(Block::bordered().title(label.as_str()));
+            frame.render_widget(field, area);
+        }
+        Prompt::Select {
+            label,
+            options,
+            selected,
+        } => {
+            let items: Vec<ListItem> = options
+                .iter()
+                .map(|option| ListItem::new(option.as_str()))
+                .collect();
+            let list = List::new(items)
+                .block(Block::bordered().title(label.as_str()))
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .highlight_symbol("> ");
+            let mut state = ListState::default().with_selected(Some(*selected));
+            frame.render_stateful_widget(list, area, &mut state);
+        }
+    }
+}
+
+/// The shown party member's character sheet.
+fn draw_sheet(frame: &mut Frame, app: &App, area: Rect) {
+    let block = Block::bordered().title("Character");
+    let sheet = app
+        .game()
+        .party()
+        .and_then(|party| party.members().get(app.shown_member()))
+        .map_or_else(|| String::from("No one yet."), ToString::to_string);
+    frame.render_widget(
+        Paragraph::new(sheet)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+/// The party's shared inventory.
+fn draw_inventory(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .game()
+        .party()
+        .map(|party| {
+            party
+                .inventory()
+                .iter()
+                .map(|(item, count)| ListItem::new(format!("{item} x{count}")))
+                .collect()
+        })
+        .unwrap_or_default();
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title("Inventory")),
+        area,
+    );
+}
+
+/// The latest dice rolls and checks, newest at the bottom.
+fn draw_dice_log(frame: &mut Frame, app: &App, area: Rect) {
+    let log = app.dice_log();
+    let shown = usize::from(area.height.saturating_sub(2));
+    let items: Vec<ListItem> = log
+        .iter()
+        .skip(log.len().saturating_sub(shown))
+        .map(|entry| ListItem::new(entry.as_str()))
+        .collect();
+    frame.render_widget(
+        List::new(items).block(Block::bordered().title("Dice")),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{Terminal, backend::TestBackend};
+
+    use super::*;
+
+    #[test]
+    fn draws_every_pane() {
+        let app = App::new().unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+        terminal.draw(|frame| draw(frame, &app)).unwrap();
+
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        for expected in [
+            "The Swamp",
+            "Please select an adventure",
+            "Test Adventure",
+            "Character",
+            "Inventory",
+            "Dice",
+            "Esc: quit",
+        ] {
+            assert!(screen.contains(expected), "missing {expected}");
+        }
+    }
+}
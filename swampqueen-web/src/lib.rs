@@ -0,0 +1,354 @@
+//! A browser frontend for Swamp Queen. [`WebGame`] is compiled to WebAssembly with
+//! `wasm-bindgen` and driven by the page in `www/`: the page asks whatever
+//! [`WebGame::prompt`] says, passes the player's answer to [`WebGame::answer`] until the party is
+//! ready, then passes every line typed to [`WebGame::command`].
+//!
+//! The default adventure's content is built in, and more can be given to
+//! [`provide_content`] as strings, since there are no files to load it from.
+
+use swampqueen_core::{
+    adventure::Adventure,
+    background::Background,
+    character::{BuildStep, CharacterBuilder, Class, Race, Stat, StatValues},
+    combat::{Combat, CombatOutcome, Creature},
+    command::{Command, Vocabulary, execute, parse},
+    content::{ContentKind, provide},
+    creation::CreationMode,
+    game::{Game, GameError, SavedGame},
+};
+use wasm_bindgen::prelude::*;
+
+/// Give the game a piece of content as a string, e.g. a location fetched by the page. `kind` is
+/// the name of the content's folder, such as `locations`.
+///
+/// # Errors
+///
+/// This function returns an error if `kind` isn't a kind of content.
+#[wasm_bindgen(js_name = provideContent)]
+pub fn provide_content(kind: &str, id: &str, text: &str) -> Result<(), JsError> {
+    let kind: ContentKind = kind
+        .parse()
+        .map_err(|err| JsError::new(&format!("{err}")))?;
+    provide(kind, id, text);
+    Ok(())
+}
+
+/// What the page is being asked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Asking {
+    /// A step of creating the party's character.
+    Step(BuildStep),
+    /// Whether to keep the stats just rolled.
+    KeepRolledStats,
+    /// Nothing; the party is exploring and takes typed commands.
+    Command,
+}
+
+/// A game in progress in the browser.
+#[wasm_bindgen]
+#[derive(Debug)]
+pub struct WebGame {
+    game: Game,
+    adventure: Adventure,
+    builder: CharacterBuilder,
+    backgrounds: Vec<Background>,
+    asking: Asking,
+}
+#[wasm_bindgen]
+impl WebGame {
+    /// Start a new game of the adventure with the given ID, beginning with character creation.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the adventure or its backgrounds can't be loaded.
+    #[wasm_bindgen(constructor)]
+    pub fn new(adventure_id: &str) -> Result<Self, JsError> {
+        let adventure = Adventure::try_load(adventure_id)?;
+        let builder = CharacterBuilder::new(&adventure);
+        let mut game = Self {
+            game: Game::default(),
+            adventure,
+            builder,
+            backgrounds: Background::try_load_all()?,
+            asking: Asking::Command,
+        };
+        game.asking = game.next_asking();
+        Ok(game)
+    }
+
+    /// The question the page should ask next, or [`None`] once the party is ready and the player
+    /// should type [commands](swampqueen_core::command).
+    #[must_use]
+    pub fn prompt(&self) -> Option<String> {
+        let prompt = match self.asking {
+            Asking::Step(BuildStep::Name) => String::from("Character name"),
+            Asking::Step(BuildStep::Class) => String::from("Please select your class"),
+            Asking::Step(BuildStep::Race) => String::from("Please select your race"),
+            Asking::Step(BuildStep::Background) => String::from("Please select your background"),
+            Asking::Step(BuildStep::BonusStat) => String::from("Please select a bonus stat"),
+            Asking::Step(BuildStep::PointBuy) => {
+                let CreationMode::PointBuy {
+                    budget,
+                    max_per_stat,
+                } = *self.builder.mode()
+                else {
+                    unreachable!("point buy is only asked for in point-buy mode");
+                };
+                let stats: Vec<String> = Stat::ALL.iter().map(String::from).collect();
+                format!(
+                    "Spend up to {budget} points on {}, separated by spaces (0-{max_per_stat} each)",
+                    stats.join(", ")
+                )
+            }
+            Asking::Step(BuildStep::RollStats) => String::from("Roll your stats"),
+            Asking::KeepRolledStats => String::from("Keep or reroll?"),
+            Asking::Command => return None,
+        };
+        Some(prompt)
+    }
+
+    /// The answers to choose from for the current [prompt](WebGame::prompt). If there are none,
+    /// the answer is typed.
+    #[must_use]
+    pub fn options(&self) -> Vec<String> {
+        match self.asking {
+            Asking::Step(BuildStep::Class) => Class::ALL.iter().map(String::from).collect(),
+            Asking::Step(BuildStep::Race) => Race::ALL.iter().map(String::from).collect(),
+            Asking::Step(BuildStep::Background) => self
+                .backgrounds
+                .iter()
+                .map(String::from)
+                .chain([String::from("None")])
+                .collect(),
+            Asking::Step(BuildStep::BonusStat) => Stat::ALL.iter().map(String::from).collect(),
+            Asking::Step(BuildStep::RollStats) => vec![String::from("Roll")],
+            Asking::KeepRolledStats => {
+                vec![String::from("Keep these stats"), String::from("Reroll")]
+            }
+            Asking::Step(BuildStep::Name | BuildStep::PointBuy) | Asking::Command => Vec::new(),
+        }
+    }
+
+    /// Answer the current [prompt](WebGame::prompt), returning the lines to show the player. Once
+    /// the character is finished, the party sets off and the first location is described.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error, to be shown before asking again, if the answer isn't
+    /// allowed.
+    pub fn answer(&mut self, answer: &str) -> Result<Vec<String>, JsError> {
+        let answer = answer.trim();
+        let chosen = self.options().iter().position(|option| option == answer);
+        let choose =
+            || chosen.ok_or_else(|| JsError::new(&format!("\"{answer}\" isn't an option")));
+        let mut lines = Vec::new();
+
+        match self.asking {
+            Asking::Step(BuildStep::Name) => {
+                self.builder.set_name(answer)?;
+            }
+            Asking::Step(BuildStep::Class) => {
+                self.builder.set_class(Class::ALL[choose()?]);
+            }
+            Asking::Step(BuildStep::Race) => {
+                self.builder.set_race(Race::ALL[choose()?]);
+            }
+            Asking::Step(BuildStep::Background) => {
+                let background = self.backgrounds.get(choose()?).cloned();
+                self.builder.set_background(background);
+            }
+            Asking::Step(BuildStep::BonusStat) => {
+                self.builder.add_bonus_stat(Stat::ALL[choose()?])?;
+            }
+            Asking::Step(BuildStep::PointBuy) => {
+                self.builder.set_purchased_stats(parse_stats(answer)?)?;
+            }
+            Asking::Step(BuildStep::RollStats) => {
+                choose()?;
+                let rolled = self.builder.roll_stats()?;
+                lines.push(rolled.stats().to_string());
+                if rolled.rerolls_left() > 0 {
+                    self.asking = Asking::KeepRolledStats;
+                    return Ok(lines);
+                }
+            }
+            Asking::KeepRolledStats => {
+                if choose()? == 1 {
+                    self.builder.reroll_stats();
+                    let rolled = self.builder.roll_stats()?;
+                    lines.push(rolled.stats().to_string());
+                    if rolled.rerolls_left() > 0 {
+                        return Ok(lines);
+                    }
+                }
+            }
+            Asking::Command => return Err(JsError::new("The party is already on its way")),
+        }
+
+        self.asking = self.next_asking();
+        if self.asking == Asking::Command {
+            lines.append(&mut self.set_off()?);
+        }
+        Ok(lines)
+    }
+
+    /// Carry out a typed [command](swampqueen_core::command), returning the lines to show the
+    /// player. Commands that can't be understood or carried out explain why instead. A fight
+    /// started in conversation is fought out straight away.
+    #[must_use]
+    pub fn command(&mut self, input: &str) -> Vec<String> {
+        if self.asking != Asking::Command {
+            return vec![String::from("Finish creating your character first.")];
+        }
+        let mut lines = match parse(input, &Vocabulary::new(&self.game)) {
+            Ok(Command::Quit) => vec![String::from("Your story rests here, for now.")],
+            Ok(command) => {
+                execute(&mut self.game, &command).unwrap_or_else(|err| vec![err.to_string()])
+            }
+            Err(err) => vec![err.to_string()],
+        };
+        if let Some(creature_ids) = self.game.take_fight() {
+            lines.extend(
+                self.fight(&creature_ids)
+                    .unwrap_or_else(|err| vec![err.to_string()]),
+            );
+        }
+        lines
+    }
+
+    /// The story so far, the party and where they are, as JSON the page can keep and
+    /// [load](WebGame::load) later.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the game can't be serialized.
+    pub fn save(&self) -> Result<String, JsError> {
+        Ok(serde_json::to_string(&self.game.save())?)
+    }
+
+    /// Continue the story from JSON made by [`WebGame::save`]. If the save has a party at a
+    /// location, character creation is skipped and the lines returned describe where they are;
+    /// otherwise the player creates their character from the start. If the save can't be loaded,
+    /// the game carries on as it was.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if `json` isn't a saved game or its location can't be
+    /// loaded.
+    pub fn load(&mut self, json: &str) -> Result<Vec<String>, JsError> {
+        let saved: SavedGame = serde_json::from_str(json)?;
+        self.game = Game::resume(saved)?;
+        if self.game.party().is_none() || self.game.location().is_none() {
+            self.builder = CharacterBuilder::new(&self.adventure);
+            self.asking = self.next_asking();
+            return Ok(Vec::new());
+        }
+        self.asking = Asking::Command;
+        let mut lines = execute(&mut self.game, &Command::Look)?;
+        lines.push(String::from("Type \"help\" for a list of commands."));
+        Ok(lines)
+    }
+}
+impl WebGame {
+    /// What to ask after the character's last completed step.
+    fn next_asking(&self) -> Asking {
+        self.builder
+            .next_step()
+            .map_or(Asking::Command, Asking::Step)
+    }
+
+    /// Fight the creatures with the given IDs until one side is beaten. The page has no way to
+    /// choose actions in combat yet, so every standing party member attacks the first standing
+    /// enemy each round.
+    fn fight(&mut self, creature_ids: &[String]) -> Result<Vec<String>, GameError> {
+        let enemies = creature_ids
+            .iter()
+            .map(|id| Creature::try_load(id))
+            .collect::<Result<_, _>>()
+            .map_err(GameError::Io)?;
+        let mut combat = Combat::new(enemies);
+        let mut lines = Vec::new();
+        let outcome = loop {
+            let party = self.game.party().ok_or(GameError::NoParty)?;
+            let outcome = combat.outcome(party.members());
+            if outcome != CombatOutcome::Ongoing {
+                break outcome;
+            }
+            lines.push(format!("--- Round {} ---", combat.round()));
+            let standing: Vec<usize> = (0..party.members().len())
+                .filter(|&member| !party.members()[member].is_down())
+                .collect();
+            for member in standing {
+                let Some((target, _)) = combat.standing_enemies().next() else {
+                    break;
+                };
+                if let Some(attack) = self.game.attack(&mut combat, member, target)? {
+                    lines.push(format!(
+                        "{} hits the {} for {} ({}).",
+                        attack.attacker, attack.target, attack.damage, attack.rolls
+                    ));
+                }
+            }
+            for attack in self.game.enemy_turn(&mut combat)? {
+                lines.push(format!(
+                    "The {} hits {} for {} ({}).",
+                    attack.attacker, attack.target, attack.damage, attack.rolls
+                ));
+            }
+            let heroes = self
+                .game
+                .party_mut()
+                .ok_or(GameError::NoParty)?
+                .members_mut();
+            for (name, tick) in combat.end_round(heroes).ticks {
+                for (kind, damage) in &tick.damage_rolls {
+                    lines.push(format!("{name} is {kind}: took {damage} damage"));
+                }
+            }
+        };
+
+        if outcome == CombatOutcome::Victory {
+            lines.extend(self.game.end_combat(&combat)?);
+        } else {
+            lines.push(String::from("Your party has been defeated by the swamp..."));
+        }
+        Ok(lines)
+    }
+
+    /// Add the finished character to the party and enter the adventure's first location.
+    fn set_off(&mut self) -> Result<Vec<String>, JsError> {
+        self.game.create_character(&self.builder)?;
+        let mut lines: Vec<String> = self
+            .game
+            .party()
+            .into_iter()
+            .flat_map(|party| party.members())
+            .map(ToString::to_string)
+            .collect();
+        let mut outcome = self.game.enter_location(self.adventure.start_location())?;
+        lines.append(&mut execute(&mut self.game, &Command::Look)?);
+        lines.append(&mut outcome.messages);
+        lines.push(String::from("Type \"help\" for a list of commands."));
+        Ok(lines)
+    }
+}
+
+/// Parse one number per [`Stat`], in the order of [`Stat::ALL`].
+fn parse_stats(answer: &str) -> Result<StatValues, JsError> {
+    let values: Vec<isize> = answer
+        .split([' ', ','])
+        .filter(|value| !value.is_empty())
+        .map(str::parse)
+        .collect::<Result<_, _>>()?;
+    if values.len() != Stat::ALL.len() {
+        return Err(JsError::new(&format!(
+            "Please enter {} numbers",
+            Stat::ALL.len()
+        )));
+    }
+    let mut stats = StatValues::default();
+    for (stat, value) in Stat::ALL.into_iter().zip(values) {
+        stats.set(stat, value);
+    }
+    Ok(stats)
+}
@@ -0,0 +1,98 @@
+//! Tests run in node with `cargo test -p swampqueen-web --target wasm32-unknown-unknown`, which
+//! needs `wasm-bindgen-test-runner` from `wasm-bindgen-cli`.
+#![cfg(target_arch = "wasm32")]
+
+use swampqueen_web::{WebGame, provide_content};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+/// Create a character for the default adventure, returning what the game said once they set off.
+fn create_character(game: &mut WebGame) -> Vec<String> {
+    let mut lines = Vec::new();
+    for answer in [
+        "Smuggs",
+        "Hunter",
+        "Alligator Folk",
+        "None",
+        "Cunning",
+        "Bulk",
+    ] {
+        lines = game.answer(answer).unwrap();
+    }
+    lines
+}
+
+#[wasm_bindgen_test]
+fn create_character_and_explore() {
+    let mut game = WebGame::new("testadventure").unwrap();
+    assert_eq!(game.prompt().as_deref(), Some("Character name"));
+    assert!(game.options().is_empty());
+    assert!(game.answer("   ").is_err());
+
+    let set_off = create_character(&mut game);
+    assert_eq!(game.prompt(), None);
+    assert!(set_off.iter().any(|line| line == "== Test Place =="));
+
+    assert_eq!(game.command("take cable"), ["You take the frayed cable."]);
+    assert!(game.command("go north")[0].contains("Test Corridor"));
+    assert!(game.command("dance")[0].contains("dance"));
+
+    let mut resumed = WebGame::new("testadventure").unwrap();
+    let lines = resumed.load(&game.save().unwrap()).unwrap();
+    assert_eq!(resumed.prompt(), None);
+    assert_eq!(lines[0], "== Test Corridor ==");
+    assert_eq!(resumed.command("inventory"), game.command("inventory"));
+    assert_eq!(resumed.save().unwrap(), game.save().unwrap());
+
+    // Insulting his hat starts a fight, fought out by attacking
+    assert!(game.command("talk to mossback")[2].contains("Ask about the bog"));
+    let fight = game.command("4");
+    assert!(fight.contains(&String::from("A fight breaks out with the bog lurker!")));
+    assert!(fight.contains(&String::from("--- Round 1 ---")));
+    assert!(fight.iter().any(|line| {
+        line.starts_with("Victory!") || line == "Your party has been defeated by the swamp..."
+    }));
+}
+
+#[wasm_bindgen_test]
+fn provided_content_and_saves() {
+    provide_content(
+        "adventures",
+        "webadventure",
+        r#"{ "id": "webadventure", "name": "Web Adventure", "start_location": "webplace" }"#,
+    )
+    .unwrap();
+    provide_content(
+        "locations",
+        "webplace",
+        r#"{
+            "id": "webplace",
+            "name": "A Place Provided By The Page",
+            "text": { "intro": ["It smells faintly of JavaScript."] }
+        }"#,
+    )
+    .unwrap();
+    assert!(provide_content("maps", "webplace", "{}").is_err());
+
+    let mut game = WebGame::new("webadventure").unwrap();
+    let set_off = create_character(&mut game);
+    assert!(set_off.contains(&String::from("== A Place Provided By The Page ==")));
+
+    assert!(game.command("roll 2d6")[0].starts_with("2d6: "));
+    let saved = game.save().unwrap();
+    let mut loaded = WebGame::new("webadventure").unwrap();
+    assert!(!loaded.load(&saved).unwrap().is_empty());
+    assert_eq!(loaded.save().unwrap(), saved);
+    assert_eq!(loaded.save().unwrap(), saved);
+
+    // A save whose location is gone is rejected, leaving the game as it was
+    let stale = saved.replace("\"webplace\"", "\"vanished\"");
+    assert!(loaded.load(&stale).is_err());
+    assert_eq!(loaded.prompt(), None);
+
+    // A save with no party goes back to creating a character
+    assert!(loaded.load("{}").unwrap().is_empty());
+    assert_eq!(loaded.prompt().as_deref(), Some("Character name"));
+    assert!(
+        create_character(&mut loaded).contains(&String::from("== A Place Provided By The Page =="))
+    );
+}
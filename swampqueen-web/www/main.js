@@ -0,0 +1,73 @@
+// Build with `wasm-pack build --target web --out-dir www/pkg` from swampqueen-web, then serve
+// this folder with any static file server.
+import init, { WebGame } from "./pkg/swampqueen_web.js";
+
+const story = document.getElementById("story");
+const form = document.getElementById("prompt");
+const label = document.getElementById("label");
+const options = document.getElementById("options");
+const input = document.getElementById("input");
+
+function show(lines, className) {
+  for (const line of lines) {
+    const paragraph = document.createElement("p");
+    paragraph.textContent = line;
+    if (className) paragraph.className = className;
+    story.append(paragraph);
+  }
+  story.lastElementChild?.scrollIntoView({ block: "end" });
+}
+
+// Show the game's current question, with a button for each option it offers
+function ask(game) {
+  const prompt = game.prompt();
+  label.textContent = prompt ?? "What now?";
+  options.replaceChildren(
+    ...game.options().map((option) => {
+      const button = document.createElement("button");
+      button.type = "button";
+      button.textContent = option;
+      button.addEventListener("click", () => submit(game, option));
+      return button;
+    }),
+  );
+  input.hidden = game.options().length > 0;
+  input.focus();
+}
+
+function submit(game, text) {
+  show([`> ${text}`], "echo");
+  try {
+    if (game.prompt() === undefined) {
+      show(game.command(text));
+      localStorage.setItem("swampqueen-save", game.save());
+    } else {
+      show(game.answer(text));
+    }
+  } catch (error) {
+    show([error.message], "error");
+  }
+  ask(game);
+}
+
+await init();
+const adventure = new URLSearchParams(location.search).get("adventure") ?? "testadventure";
+const game = new WebGame(adventure);
+const saved = localStorage.getItem("swampqueen-save");
+if (saved) {
+  try {
+    show(game.load(saved));
+  } catch (error) {
+    // A save the game can no longer load, e.g. after its content changed, would fail every time
+    show([`Your saved game couldn't be loaded: ${error.message}`], "error");
+    localStorage.removeItem("swampqueen-save");
+  }
+}
+
+form.addEventListener("submit", (event) => {
+  event.preventDefault();
+  const text = input.value;
+  input.value = "";
+  submit(game, text);
+});
+ask(game);